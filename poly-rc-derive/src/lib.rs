@@ -0,0 +1,153 @@
+//! Derive macros for `poly-rc`.
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{Data, DeriveInput, Field, Fields, LitStr, Type, parse_macro_input};
+
+/// Derive `poly_rc::shared::QueryParams` for a struct with named fields.
+///
+/// Every field becomes a query parameter named after the field, in
+/// declaration order. `Option` fields are left out when `None`. Values are
+/// formatted with `Display`.
+///
+/// Field attributes:
+/// * `#[query(rename = "name")]` - Use another parameter name
+/// * `#[query(skip)]` - Never add the field
+/// * `#[query(comma)]` - Join a `Vec` with commas, `key=a,b`
+/// * `#[query(repeat)]` - Add the key once per element of a `Vec`, `key=a&key=b`
+/// * `#[query(timestamp)]` - Format a `DateTime<Utc>` as RFC 3339
+#[proc_macro_derive(QueryParams, attributes(query))]
+pub fn derive_query_params(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand(input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Format {
+    Display,
+    Comma,
+    Repeat,
+    Timestamp,
+}
+
+struct FieldOptions {
+    name: String,
+    skip: bool,
+    format: Format,
+}
+
+fn expand(input: DeriveInput) -> syn::Result<TokenStream2> {
+    let fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => &fields.named,
+            _ => {
+                return Err(syn::Error::new(
+                    input.ident.span(),
+                    "QueryParams can only be derived for structs with named fields",
+                ));
+            }
+        },
+        _ => {
+            return Err(syn::Error::new(
+                input.ident.span(),
+                "QueryParams can only be derived for structs",
+            ));
+        }
+    };
+
+    let mut calls = Vec::new();
+    for field in fields {
+        let options = field_options(field)?;
+        if options.skip {
+            continue;
+        }
+        calls.push(builder_call(field, &options));
+    }
+
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics ::poly_rc::shared::QueryParams for #ident #ty_generics #where_clause {
+            fn as_query_params(&self) -> ::std::vec::Vec<(::std::string::String, ::std::string::String)> {
+                ::poly_rc::shared::query::QueryBuilder::new()
+                    #(#calls)*
+                    .build()
+            }
+        }
+    })
+}
+
+fn field_options(field: &Field) -> syn::Result<FieldOptions> {
+    let ident = field.ident.as_ref().expect("named field");
+    let mut options = FieldOptions {
+        name: ident.to_string().trim_start_matches("r#").to_string(),
+        skip: false,
+        format: Format::Display,
+    };
+
+    for attr in field
+        .attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("query"))
+    {
+        attr.parse_nested_meta(|meta| {
+            let format = if meta.path.is_ident("rename") {
+                options.name = meta.value()?.parse::<LitStr>()?.value();
+                return Ok(());
+            } else if meta.path.is_ident("skip") {
+                options.skip = true;
+                return Ok(());
+            } else if meta.path.is_ident("comma") {
+                Format::Comma
+            } else if meta.path.is_ident("repeat") {
+                Format::Repeat
+            } else if meta.path.is_ident("timestamp") {
+                Format::Timestamp
+            } else {
+                return Err(
+                    meta.error("expected `rename`, `skip`, `comma`, `repeat` or `timestamp`")
+                );
+            };
+            if options.format != Format::Display {
+                return Err(meta.error("only one of `comma`, `repeat` and `timestamp` may be used"));
+            }
+            options.format = format;
+            Ok(())
+        })?;
+    }
+
+    Ok(options)
+}
+
+fn builder_call(field: &Field, options: &FieldOptions) -> TokenStream2 {
+    let ident = field.ident.as_ref().expect("named field");
+    let name = &options.name;
+    let optional = is_option(&field.ty);
+    let value = if optional {
+        quote! { self.#ident.as_ref() }
+    } else {
+        quote! { ::std::option::Option::Some(&self.#ident) }
+    };
+
+    match options.format {
+        Format::Display if optional => quote! { .opt(#name, #value) },
+        Format::Display => quote! { .param(#name, &self.#ident) },
+        Format::Comma => quote! { .comma_list(#name, #value) },
+        Format::Repeat => quote! { .repeated(#name, #value) },
+        Format::Timestamp => quote! { .timestamp(#name, #value) },
+    }
+}
+
+fn is_option(ty: &Type) -> bool {
+    match ty {
+        Type::Path(path) if path.qself.is_none() => path
+            .path
+            .segments
+            .last()
+            .is_some_and(|segment| segment.ident == "Option"),
+        _ => false,
+    }
+}
@@ -1,5 +1,6 @@
 use alloy::primitives::{Address, address};
 
+#[derive(Debug, Clone, Copy)]
 pub struct ContractConfig {
     pub exchange_contract: Address,
     pub collateral_contract: Address,
@@ -7,6 +8,7 @@ pub struct ContractConfig {
     pub neg_risk_contract: Option<Address>,
 }
 
+#[derive(Debug, Clone, Copy)]
 pub struct ChainConfig {
     pub neg_risk_config: ContractConfig,
     pub standard_config: ContractConfig,
@@ -39,7 +41,7 @@ impl Chains {
     };
 
     const AMOY_NEG_RISK_CONFIG: ContractConfig = ContractConfig {
-        exchange_contract: address!("0xd91E80cF2E7be2e162c6513ceD06f1dD0dA35296"),
+        exchange_contract: address!("0xC5d563A36AE78145C45a50134d48A1215220f80a"),
         collateral_contract: Self::AMOY_COLLATERAL_CONTRACT,
         ctf_contract: Self::AMOY_CTF_CONTRACT,
         neg_risk_contract: Some(address!("0xd91E80cF2E7be2e162c6513ceD06f1dD0dA35296")),
@@ -66,9 +68,42 @@ impl Chains {
                 neg_risk_config: Self::POLYGON_NEG_RISK_CONFIG,
             },
             Chains::Amoy => ChainConfig {
-                standard_config: Self::POLYGON_STD_CONFIG,
-                neg_risk_config: Self::POLYGON_NEG_RISK_CONFIG,
+                standard_config: Self::AMOY_STD_CONFIG,
+                neg_risk_config: Self::AMOY_NEG_RISK_CONFIG,
             },
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_amoy_uses_its_own_contracts() {
+        let config = Chains::Amoy.config();
+
+        assert_eq!(
+            config.standard_config.exchange_contract,
+            address!("0xdFE02Eb6733538f8Ea35D585af8DE5958AD99E40")
+        );
+        assert_eq!(
+            config.neg_risk_config.exchange_contract,
+            address!("0xC5d563A36AE78145C45a50134d48A1215220f80a")
+        );
+        assert_eq!(
+            config.neg_risk_config.neg_risk_contract,
+            Some(address!("0xd91E80cF2E7be2e162c6513ceD06f1dD0dA35296"))
+        );
+        for contracts in [config.standard_config, config.neg_risk_config] {
+            assert_eq!(
+                contracts.collateral_contract,
+                address!("0x9c4e1703476e875070ee25b56a58b008cfb8fa78")
+            );
+            assert_eq!(
+                contracts.ctf_contract,
+                address!("0x69308FB512518e39F9b16112fA8d994F4e2Bf8bB")
+            );
+        }
+    }
+}
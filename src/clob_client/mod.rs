@@ -21,9 +21,10 @@
 pub mod config;
 pub mod models;
 pub mod orders;
+pub mod paper;
 
 use crate::clob_client::models::{ClobAuth, ClobClientArgs, L1Headers, L2Credentials, L2Headers};
-use crate::public::{AsyncHttpClient, PubClient};
+use crate::public::AsyncHttpClient;
 use crate::shared::ApiError;
 use alloy::dyn_abi::Eip712Domain;
 use alloy::primitives::{Address, U256};
@@ -35,7 +36,7 @@ use reqwest::header::HeaderMap;
 use sha2::Sha256;
 use std::{borrow::Cow, env};
 use crate::clob_client::orders::models::{Order, OrderType};
-use crate::clob_client::orders::{Orders, models::{OrderData}};
+use crate::clob_client::orders::models::OrderData;
 
 /// Main client for interacting with the CLOB API.
 ///
@@ -304,13 +305,3 @@ impl ClobClient {
         todo!()
     }
 
-
-    impl Orders for ClobClient {
-        fn get_private_clob_client(&self) -> &ClobClient {
-            self
-        }
-
-        fn get_public_client(&self) -> PubClient {
-            PubClient::new()
-        }
-    }
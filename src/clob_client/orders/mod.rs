@@ -1,46 +1,67 @@
 use crate::clob_client::ClobClient;
 use crate::clob_client::config::Chains;
+use crate::clob_client::models::ClobClientArgs;
 use crate::clob_client::orders::models::{Order, OrderCreateDTO, OrderCreateResponse, OrderType};
-use crate::public::{ApiError, AsyncHttpClient, PubClient, TokenId};
+use crate::clob_client::paper::{PaperConfig, PaperExchange};
+use crate::public::{ApiError, PubClient, TokenId};
 use crate::shared::constants::{ORDER_NAME, ORDER_VERSION};
 use alloy::dyn_abi::Eip712Domain;
 use alloy::primitives::{Address, U256};
-use alloy::signers::k256::Secp256k1;
 use alloy::signers::k256::ecdsa::SigningKey;
-use alloy::signers::local::{LocalSigner, PrivateKeySigner};
-use alloy::signers::Signer;
+use alloy::signers::local::LocalSigner;
+use async_trait::async_trait;
 use reqwest::Response;
 use crate::public::orderbook::OrderBook;
 
 pub mod models;
 
+/// Places orders on an exchange.
+///
+/// [`ClobClient`] sends them to the CLOB and [`PaperExchange`] fills them
+/// against a simulated book. Both return the same [`OrderCreateResponse`], so
+/// strategy code written against this trait runs unchanged on either one,
+/// see [`Trader`].
+#[async_trait]
 pub trait Orders {
-    fn get_private_clob_client(&self) -> &ClobClient;
-    fn get_public_client(&self) -> PubClient;
+    /// Post a signed order
+    /// # Arguments
+    /// * `body` - The order, see [`Order`]
+    /// * `defer_exec` - Whether the exchange may delay matching the order
+    /// * `order_type` - How long the order stays on the book, see [`OrderType`]
+    /// * `owner` - The API key of the order owner
+    /// # Returns
+    /// * `Result<OrderCreateResponse, ApiError>` - The placed order, or an [`ApiError`] if the exchange rejects it
+    async fn post_order(&self, body: Order, defer_exec:bool, order_type: OrderType, owner: String) -> Result<OrderCreateResponse, ApiError>;
+}
 
+#[async_trait]
+impl Orders for ClobClient {
     async fn post_order(&self, body: Order, defer_exec:bool, order_type: OrderType, owner: String) -> Result<OrderCreateResponse, ApiError> {
 
-        let private_client = self.get_private_clob_client();
-        let pub_client = self.get_public_client();
+        let pub_client = PubClient::new();
 
         let neg_risk = pub_client.get_neg_risk(TokenId {token_id:body.tokenId.to_string()}).await?;
-        let domain = self.build_order_domain(private_client.signer.clone(), neg_risk.neg_risk ).await?;
+        let domain = self.build_order_domain(self.signer.clone(), neg_risk.neg_risk ).await?;
 
-        let signed_order = OrderCreateDTO::new(body.clone(), order_type, owner,defer_exec, domain, &private_client.signer).await;
+        let signed_order = OrderCreateDTO::new(body, order_type, owner,defer_exec, domain, &self.signer).await?;
 
-        let str_body = serde_json::to_string(&body)?;
+        let str_body = serde_json::to_string(&signed_order)?;
 
-        let l2_headers = private_client
+        let l2_headers = self
             .generate_l2_headers("POST", "/order", Some(str_body.as_str()))
             .await?;
-        let response: Response = private_client
+        let response: Response = self
             .client
             .post(Some("/order"), Some(signed_order), None, Some(l2_headers.into()))
             .await?;
         Ok(response.json().await?)
     }
+}
 
-    async fn build_order_domain(
+impl ClobClient {
+    /// Builds the EIP-712 domain orders are signed against, which names the
+    /// exchange contract of the market
+    pub async fn build_order_domain(
         &self,
         signer: LocalSigner<SigningKey>,
         neg_risk: bool,
@@ -79,3 +100,48 @@ pub trait Orders {
         })
     }
 }
+
+/// Where a [`Trader`] sends its orders
+#[derive(Debug, Clone)]
+pub enum TradingMode {
+    /// To the CLOB, authenticated with these args
+    Live(ClobClientArgs),
+    /// To a local [`PaperExchange`]
+    Paper(PaperConfig),
+}
+
+/// An [`Orders`] implementation picked at runtime by a [`TradingMode`], so a
+/// strategy switches between live and paper trading with one config value
+#[derive(Debug)]
+pub enum Trader {
+    Live(ClobClient),
+    Paper(PaperExchange),
+}
+
+impl Trader {
+    /// Connect to the CLOB, or open a paper exchange
+    pub async fn new(mode: TradingMode) -> Result<Self, ApiError> {
+        Ok(match mode {
+            TradingMode::Live(args) => Trader::Live(ClobClient::new(args).await?),
+            TradingMode::Paper(config) => Trader::Paper(PaperExchange::new(config)),
+        })
+    }
+
+    /// The simulated exchange, to feed it books or read its balances
+    pub fn paper(&self) -> Option<&PaperExchange> {
+        match self {
+            Trader::Live(_) => None,
+            Trader::Paper(exchange) => Some(exchange),
+        }
+    }
+}
+
+#[async_trait]
+impl Orders for Trader {
+    async fn post_order(&self, body: Order, defer_exec: bool, order_type: OrderType, owner: String) -> Result<OrderCreateResponse, ApiError> {
+        match self {
+            Trader::Live(client) => client.post_order(body, defer_exec, order_type, owner).await,
+            Trader::Paper(exchange) => exchange.post_order(body, defer_exec, order_type, owner).await,
+        }
+    }
+}
@@ -17,7 +17,7 @@ pub struct CreateOrderDTO {
     order: Order,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
 pub enum OrderCreateStatus {
     Live,
@@ -25,7 +25,7 @@ pub enum OrderCreateStatus {
     Delayed,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum OrderType {
     GTC,
     FOK,
@@ -0,0 +1,389 @@
+//! The matching engine of a single token.
+
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+use crate::clob_client::orders::models::OrderType;
+use crate::public::orderbook::{OrderBookSnapshot, PriceLevel};
+use crate::shared::Side;
+
+/// A simulated order resting on the book
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PaperOrder {
+    pub order_id: String,
+    pub token_id: String,
+    pub side: Side,
+    pub price: Decimal,
+    pub original_size: Decimal,
+    pub size_matched: Decimal,
+    pub order_type: OrderType,
+    /// When a GTD order leaves the book
+    pub expiration: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl PaperOrder {
+    pub fn remaining(&self) -> Decimal {
+        self.original_size - self.size_matched
+    }
+}
+
+/// A fill of one simulated order
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Fill {
+    pub order_id: String,
+    pub side: Side,
+    pub price: Decimal,
+    pub size: Decimal,
+}
+
+/// Matches simulated orders of one token against the liquidity of the last
+/// snapshot of its book.
+///
+/// Incoming orders take liquidity level by level at the prices of the book.
+/// Liquidity they take stays gone until the next snapshot replaces the book.
+/// Orders that rest are filled at their own price once a snapshot shows the
+/// other side trading through it, best price first and oldest first at the
+/// same price. Simulated orders never match each other.
+#[derive(Debug, Clone)]
+pub struct MatchingEngine {
+    bids: Vec<PriceLevel>,
+    asks: Vec<PriceLevel>,
+    tick_size: Option<Decimal>,
+    min_order_size: Option<Decimal>,
+    clock: Option<DateTime<Utc>>,
+    orders: Vec<PaperOrder>,
+}
+
+impl Default for MatchingEngine {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MatchingEngine {
+    /// An engine with an empty book
+    pub fn new() -> Self {
+        Self {
+            bids: vec![],
+            asks: vec![],
+            tick_size: None,
+            min_order_size: None,
+            clock: None,
+            orders: vec![],
+        }
+    }
+
+    /// The time of the last snapshot, or the current time before the first one
+    pub fn now(&self) -> DateTime<Utc> {
+        self.clock.unwrap_or_else(Utc::now)
+    }
+
+    /// The tick size of the book, `None` before the first snapshot
+    pub fn tick_size(&self) -> Option<Decimal> {
+        self.tick_size
+    }
+
+    /// The minimum size of a resting order, `None` before the first snapshot
+    pub fn min_order_size(&self) -> Option<Decimal> {
+        self.min_order_size
+    }
+
+    /// The resting orders, oldest first
+    pub fn open_orders(&self) -> &[PaperOrder] {
+        &self.orders
+    }
+
+    /// Replace the book with `snapshot`, drop the GTD orders it expires and
+    /// fill the resting orders it trades through.
+    ///
+    /// Snapshots older than the current book are ignored.
+    pub fn apply_snapshot(&mut self, snapshot: OrderBookSnapshot) -> Vec<Fill> {
+        if self.clock.is_some_and(|clock| snapshot.timestamp < clock) {
+            return vec![];
+        }
+        self.clock = Some(snapshot.timestamp);
+        self.bids = snapshot.bids;
+        self.asks = snapshot.asks;
+        self.tick_size = Some(snapshot.tick_size);
+        self.min_order_size = Some(snapshot.min_order_size);
+
+        let now = snapshot.timestamp;
+        self.orders
+            .retain(|order| order.expiration.is_none_or(|expiration| expiration > now));
+        self.fill_resting()
+    }
+
+    /// The size an order could take right now at `limit` or better
+    pub fn liquidity(&self, side: Side, limit: Decimal) -> Decimal {
+        self.opposite(side)
+            .iter()
+            .take_while(|level| crosses(side, limit, level.price))
+            .map(|level| level.size)
+            .sum()
+    }
+
+    /// Take up to `size` at `limit` or better from the book, best price first
+    pub fn take(&mut self, order_id: &str, side: Side, limit: Decimal, size: Decimal) -> Vec<Fill> {
+        let levels = match side {
+            Side::BUY => &mut self.asks,
+            Side::SELL => &mut self.bids,
+        };
+        let mut fills = vec![];
+        let mut remaining = size;
+        while remaining > Decimal::ZERO
+            && let Some(level) = levels.first_mut()
+            && crosses(side, limit, level.price)
+        {
+            let matched = remaining.min(level.size);
+            fills.push(Fill {
+                order_id: order_id.to_string(),
+                side,
+                price: level.price,
+                size: matched,
+            });
+            remaining -= matched;
+            level.size -= matched;
+            if level.size <= Decimal::ZERO {
+                levels.remove(0);
+            }
+        }
+        fills
+    }
+
+    /// Put an order on the book behind the orders already resting at its price
+    pub fn rest(&mut self, order: PaperOrder) {
+        self.orders.push(order);
+    }
+
+    /// Remove a resting order
+    pub fn cancel(&mut self, order_id: &str) -> Option<PaperOrder> {
+        let index = self
+            .orders
+            .iter()
+            .position(|order| order.order_id == order_id)?;
+        Some(self.orders.remove(index))
+    }
+
+    fn opposite(&self, side: Side) -> &[PriceLevel] {
+        match side {
+            Side::BUY => &self.asks,
+            Side::SELL => &self.bids,
+        }
+    }
+
+    /// Fill resting orders against the liquidity that crosses them
+    fn fill_resting(&mut self) -> Vec<Fill> {
+        // Price-time priority on each side: best price first, then arrival order
+        let mut queue: Vec<usize> = (0..self.orders.len()).collect();
+        queue.sort_by_key(|&index| {
+            let order = &self.orders[index];
+            let price = match order.side {
+                Side::BUY => -order.price,
+                Side::SELL => order.price,
+            };
+            (u8::from(order.side), price, index)
+        });
+
+        let mut fills = vec![];
+        for index in queue {
+            let order = &self.orders[index];
+            let (order_id, side, price) = (order.order_id.clone(), order.side, order.price);
+            let taken = self.take(&order_id, side, price, self.orders[index].remaining());
+            for mut fill in taken {
+                // The resting order is the maker and trades at its own price
+                fill.price = price;
+                self.orders[index].size_matched += fill.size;
+                fills.push(fill);
+            }
+        }
+        self.orders
+            .retain(|order| order.remaining() > Decimal::ZERO);
+        fills
+    }
+}
+
+/// Whether an order on `side` with price `limit` trades with a level at `price`
+fn crosses(side: Side, limit: Decimal, price: Decimal) -> bool {
+    match side {
+        Side::BUY => price <= limit,
+        Side::SELL => price >= limit,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeDelta;
+    use rust_decimal::dec;
+
+    fn snapshot(
+        at: DateTime<Utc>,
+        bids: &[(Decimal, Decimal)],
+        asks: &[(Decimal, Decimal)],
+    ) -> OrderBookSnapshot {
+        let levels = |levels: &[(Decimal, Decimal)]| {
+            levels
+                .iter()
+                .map(|&(price, size)| PriceLevel { price, size })
+                .collect()
+        };
+        OrderBookSnapshot {
+            market: "0xmarket".to_string(),
+            asset_id: "1".to_string(),
+            timestamp: at,
+            hash: String::new(),
+            bids: levels(bids),
+            asks: levels(asks),
+            min_order_size: dec!(5),
+            tick_size: dec!(0.01),
+            neg_risk: false,
+        }
+    }
+
+    fn order(order_id: &str, side: Side, price: Decimal, size: Decimal) -> PaperOrder {
+        PaperOrder {
+            order_id: order_id.to_string(),
+            token_id: "1".to_string(),
+            side,
+            price,
+            original_size: size,
+            size_matched: Decimal::ZERO,
+            order_type: OrderType::GTC,
+            expiration: None,
+            created_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_take_walks_levels_and_consumes_them() {
+        let mut engine = MatchingEngine::new();
+        engine.apply_snapshot(snapshot(
+            Utc::now(),
+            &[],
+            &[
+                (dec!(0.50), dec!(10)),
+                (dec!(0.52), dec!(20)),
+                (dec!(0.60), dec!(50)),
+            ],
+        ));
+
+        assert_eq!(engine.liquidity(Side::BUY, dec!(0.52)), dec!(30));
+        let fills = engine.take("a", Side::BUY, dec!(0.55), dec!(25));
+
+        assert_eq!(fills.len(), 2);
+        assert_eq!((fills[0].price, fills[0].size), (dec!(0.50), dec!(10)));
+        assert_eq!((fills[1].price, fills[1].size), (dec!(0.52), dec!(15)));
+        // The taken liquidity is gone until the next snapshot
+        assert_eq!(engine.liquidity(Side::BUY, dec!(0.55)), dec!(5));
+    }
+
+    #[test]
+    fn test_resting_orders_fill_in_price_time_priority() {
+        let start = Utc::now();
+        let mut engine = MatchingEngine::new();
+        engine.apply_snapshot(snapshot(start, &[], &[(dec!(0.60), dec!(100))]));
+        engine.rest(order("first", Side::BUY, dec!(0.50), dec!(10)));
+        engine.rest(order("better", Side::BUY, dec!(0.52), dec!(10)));
+        engine.rest(order("second", Side::BUY, dec!(0.50), dec!(10)));
+
+        let fills = engine.apply_snapshot(snapshot(
+            start + TimeDelta::seconds(1),
+            &[],
+            &[(dec!(0.49), dec!(15))],
+        ));
+
+        let filled: Vec<(&str, Decimal, Decimal)> = fills
+            .iter()
+            .map(|fill| (fill.order_id.as_str(), fill.price, fill.size))
+            .collect();
+        assert_eq!(
+            filled,
+            vec![
+                ("better", dec!(0.52), dec!(10)),
+                ("first", dec!(0.50), dec!(5))
+            ]
+        );
+        let open: Vec<(&str, Decimal)> = engine
+            .open_orders()
+            .iter()
+            .map(|order| (order.order_id.as_str(), order.remaining()))
+            .collect();
+        assert_eq!(open, vec![("first", dec!(5)), ("second", dec!(10))]);
+    }
+
+    #[test]
+    fn test_mixed_sides_fill_in_price_time_priority() {
+        let start = Utc::now();
+        let mut engine = MatchingEngine::new();
+        engine.apply_snapshot(snapshot(start, &[], &[]));
+        // 30 orders alternating sides, with several orders at each price
+        let prices = [dec!(0.40), dec!(0.45), dec!(0.50), dec!(0.55), dec!(0.60)];
+        let mut resting = vec![];
+        for index in 0..30 {
+            let side = if index % 3 == 0 {
+                Side::BUY
+            } else {
+                Side::SELL
+            };
+            let price = prices[(index * 3) % prices.len()];
+            resting.push((index, side, price));
+            engine.rest(order(&index.to_string(), side, price, dec!(10)));
+        }
+
+        // Enough liquidity on each side for six orders
+        let fills = engine.apply_snapshot(snapshot(
+            start + TimeDelta::seconds(1),
+            &[(dec!(0.99), dec!(60))],
+            &[(dec!(0.01), dec!(60))],
+        ));
+
+        for side in [Side::BUY, Side::SELL] {
+            let mut expected: Vec<(Decimal, usize)> = resting
+                .iter()
+                .filter(|order| order.1 == side)
+                .map(|&(index, _, price)| match side {
+                    Side::BUY => (-price, index),
+                    Side::SELL => (price, index),
+                })
+                .collect();
+            expected.sort();
+            let expected: Vec<String> = expected[..6]
+                .iter()
+                .map(|(_, index)| index.to_string())
+                .collect();
+            let filled: Vec<String> = fills
+                .iter()
+                .filter(|fill| fill.side == side)
+                .map(|fill| fill.order_id.clone())
+                .collect();
+            assert_eq!(filled, expected, "{side} fills out of priority");
+        }
+        assert_eq!(engine.open_orders().len(), 18);
+    }
+
+    #[test]
+    fn test_snapshot_expires_gtd_orders_and_ignores_stale_books() {
+        let start = Utc::now();
+        let mut engine = MatchingEngine::new();
+        engine.apply_snapshot(snapshot(start, &[], &[]));
+        let mut gtd = order("gtd", Side::SELL, dec!(0.70), dec!(10));
+        gtd.order_type = OrderType::GTD;
+        gtd.expiration = Some(start + TimeDelta::seconds(30));
+        engine.rest(gtd);
+        engine.rest(order("gtc", Side::SELL, dec!(0.70), dec!(10)));
+
+        let stale = engine.apply_snapshot(snapshot(
+            start - TimeDelta::seconds(1),
+            &[(dec!(0.80), dec!(100))],
+            &[],
+        ));
+        assert!(stale.is_empty());
+        assert_eq!(engine.now(), start);
+
+        engine.apply_snapshot(snapshot(start + TimeDelta::seconds(30), &[], &[]));
+        assert_eq!(engine.open_orders().len(), 1);
+        assert_eq!(engine.open_orders()[0].order_id, "gtc");
+    }
+}
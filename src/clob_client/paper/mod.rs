@@ -0,0 +1,849 @@
+//! Paper trading: a local exchange that fills orders against real or recorded
+//! order books without sending anything to the CLOB.
+//!
+//! [`PaperExchange`] implements [`Orders`], so strategy code posts the same
+//! signed [`Order`]s and reads the same [`OrderCreateResponse`]s as with a
+//! [`ClobClient`](crate::clob_client::ClobClient). Pick one or the other at
+//! runtime with a [`Trader`](crate::clob_client::orders::Trader).
+//!
+//! # Key Types
+//!
+//! - [`PaperExchange`]: The simulated exchange, with a virtual USDC balance and positions.
+//! - [`MatchingEngine`]: The book and resting orders of a single token.
+//! - [`PaperTrade`]: A fill of a simulated order.
+
+pub mod engine;
+
+use alloy::primitives::U256;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+use crate::clob_client::orders::Orders;
+use crate::clob_client::orders::models::{
+    Order, OrderCreateResponse, OrderCreateStatus, OrderType,
+};
+use crate::public::PubClient;
+use crate::public::orderbook::{OrderBook, OrderBookSnapshot, OrderbookSummary};
+use crate::shared::constants::USDC_DECIMALS;
+use crate::shared::{ApiError, HttpError, Side, TokenId};
+pub use engine::{Fill, MatchingEngine, PaperOrder};
+
+/// Settings of a [`PaperExchange`]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PaperConfig {
+    /// The virtual USDC balance the exchange starts with
+    pub starting_balance: Decimal,
+    /// Fetch a fresh book from the CLOB before matching each order. Turn it
+    /// off to feed recorded books with [`PaperExchange::apply_snapshot`]
+    pub refresh_books: bool,
+}
+
+/// A fill of a simulated order
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PaperTrade {
+    pub trade_id: String,
+    pub order_id: String,
+    pub token_id: String,
+    pub side: Side,
+    pub price: Decimal,
+    pub size: Decimal,
+    /// Whether the order was resting on the book when it was filled
+    pub maker: bool,
+    pub timestamp: DateTime<Utc>,
+}
+
+#[derive(Debug, Default)]
+struct PaperState {
+    balance: Decimal,
+    positions: HashMap<String, Decimal>,
+    engines: HashMap<String, MatchingEngine>,
+    trades: Vec<PaperTrade>,
+    next_order: u64,
+    next_trade: u64,
+}
+
+impl PaperState {
+    /// The balance not reserved by resting buy orders
+    fn available_balance(&self) -> Decimal {
+        let reserved: Decimal = self
+            .open_orders()
+            .filter(|order| order.side == Side::BUY)
+            .map(|order| order.price * order.remaining())
+            .sum();
+        self.balance - reserved
+    }
+
+    /// The shares of a token not reserved by resting sell orders
+    fn available_shares(&self, token_id: &str) -> Decimal {
+        let reserved: Decimal = self
+            .open_orders()
+            .filter(|order| order.side == Side::SELL && order.token_id == token_id)
+            .map(|order| order.remaining())
+            .sum();
+        self.positions.get(token_id).copied().unwrap_or_default() - reserved
+    }
+
+    fn open_orders(&self) -> impl Iterator<Item = &PaperOrder> {
+        self.engines
+            .values()
+            .flat_map(|engine| engine.open_orders())
+    }
+
+    /// Move the balance and position of a fill and record it
+    fn settle(
+        &mut self,
+        token_id: &str,
+        fill: Fill,
+        maker: bool,
+        timestamp: DateTime<Utc>,
+    ) -> String {
+        let notional = fill.price * fill.size;
+        let position = self.positions.entry(token_id.to_string()).or_default();
+        match fill.side {
+            Side::BUY => {
+                self.balance -= notional;
+                *position += fill.size;
+            }
+            Side::SELL => {
+                self.balance += notional;
+                *position -= fill.size;
+            }
+        }
+
+        self.next_trade += 1;
+        let trade_id = format!("paper-trade-{}", self.next_trade);
+        self.trades.push(PaperTrade {
+            trade_id: trade_id.clone(),
+            order_id: fill.order_id,
+            token_id: token_id.to_string(),
+            side: fill.side,
+            price: fill.price,
+            size: fill.size,
+            maker,
+            timestamp,
+        });
+        trade_id
+    }
+}
+
+/// A simulated exchange with one [`MatchingEngine`] per token.
+///
+/// Books come from [`apply_snapshot`](Self::apply_snapshot), or from the CLOB
+/// before every order when [`PaperConfig::refresh_books`] is set. Orders are
+/// checked like the CLOB checks them, against the tick size, the minimum
+/// size and the balance not yet reserved by resting orders, and rejected with
+/// an [`ApiError::Http`] with status 400. Fees are not charged.
+///
+/// * `GTC` - Takes what it can, the rest rests until filled or cancelled
+/// * `GTD` - As `GTC`, and leaves the book at its expiration
+/// * `FOK` - Fills completely right away or is rejected
+/// * `FAK` - Takes what it can and drops the rest, rejected if nothing fills
+///
+/// The clock of each token is the timestamp of its last book, so recorded
+/// books replay with their original expirations.
+#[derive(Debug)]
+pub struct PaperExchange {
+    config: PaperConfig,
+    client: PubClient,
+    state: Mutex<PaperState>,
+}
+
+impl PaperExchange {
+    pub fn new(config: PaperConfig) -> Self {
+        let state = PaperState {
+            balance: config.starting_balance,
+            ..PaperState::default()
+        };
+        Self {
+            config,
+            client: PubClient::new(),
+            state: Mutex::new(state),
+        }
+    }
+
+    pub fn config(&self) -> &PaperConfig {
+        &self.config
+    }
+
+    /// Replace the book of a token and fill the resting orders it trades through
+    /// # Arguments
+    /// * `snapshot` - The new book, older books than the current one are ignored
+    /// # Returns
+    /// * `Vec<PaperTrade>` - The fills of resting orders
+    pub fn apply_snapshot(&self, snapshot: OrderBookSnapshot) -> Vec<PaperTrade> {
+        let mut state = self.state.lock().unwrap();
+        let token_id = snapshot.asset_id.clone();
+        let timestamp = snapshot.timestamp;
+        let fills = state
+            .engines
+            .entry(token_id.clone())
+            .or_default()
+            .apply_snapshot(snapshot);
+
+        let first = state.trades.len();
+        for fill in fills {
+            state.settle(&token_id, fill, true, timestamp);
+        }
+        state.trades[first..].to_vec()
+    }
+
+    /// [`apply_snapshot`](Self::apply_snapshot) with a book in the wire format
+    pub fn apply_summary(&self, summary: &OrderbookSummary) -> Result<Vec<PaperTrade>, ApiError> {
+        Ok(self.apply_snapshot(OrderBookSnapshot::try_from(summary)?))
+    }
+
+    /// Fetch the current book of a token from the CLOB and apply it
+    pub async fn refresh(&self, token_id: &str) -> Result<Vec<PaperTrade>, ApiError> {
+        let snapshot = self
+            .client
+            .get_orderbook_snapshot(TokenId {
+                token_id: token_id.to_string(),
+            })
+            .await?;
+        Ok(self.apply_snapshot(snapshot))
+    }
+
+    /// Remove a resting order, returning it if it was still on the book
+    pub fn cancel_order(&self, order_id: &str) -> Option<PaperOrder> {
+        let mut state = self.state.lock().unwrap();
+        state
+            .engines
+            .values_mut()
+            .find_map(|engine| engine.cancel(order_id))
+    }
+
+    /// The resting orders of every token
+    pub fn open_orders(&self) -> Vec<PaperOrder> {
+        self.state.lock().unwrap().open_orders().cloned().collect()
+    }
+
+    /// The virtual USDC balance, including what resting orders reserve
+    pub fn balance(&self) -> Decimal {
+        self.state.lock().unwrap().balance
+    }
+
+    /// The USDC balance not reserved by resting buy orders
+    pub fn available_balance(&self) -> Decimal {
+        self.state.lock().unwrap().available_balance()
+    }
+
+    /// The shares held of a token
+    pub fn position(&self, token_id: &str) -> Decimal {
+        let state = self.state.lock().unwrap();
+        state.positions.get(token_id).copied().unwrap_or_default()
+    }
+
+    /// The shares held of every token traded so far
+    pub fn positions(&self) -> HashMap<String, Decimal> {
+        self.state.lock().unwrap().positions.clone()
+    }
+
+    /// Every fill so far, oldest first
+    pub fn trades(&self) -> Vec<PaperTrade> {
+        self.state.lock().unwrap().trades.clone()
+    }
+
+    /// Match an order against the current book of its token
+    fn place(&self, body: &Order, order_type: OrderType) -> Result<OrderCreateResponse, ApiError> {
+        let terms = OrderTerms::try_from(body).map_err(rejected)?;
+        let mut state = self.state.lock().unwrap();
+        let engine = state.engines.entry(terms.token_id.clone()).or_default();
+        let now = engine.now();
+
+        match (order_type, terms.expiration) {
+            (OrderType::GTD, Some(expiration)) if expiration <= now => {
+                return Err(rejected(format!(
+                    "invalid expiration value ({expiration}), it must be in the future"
+                )));
+            }
+            (OrderType::GTD, None) => {
+                return Err(rejected("GTD orders need an expiration".to_string()));
+            }
+            (OrderType::GTC | OrderType::FOK | OrderType::FAK, Some(_)) => {
+                return Err(rejected(format!(
+                    "only GTD orders may have an expiration, not {order_type:?}"
+                )));
+            }
+            _ => {}
+        }
+        // The amounts of market orders are rounded, so the price they imply
+        // is only close to the tick the order was priced at
+        if matches!(order_type, OrderType::GTC | OrderType::GTD)
+            && let Some(tick_size) = engine.tick_size()
+            && !(terms.price % tick_size).is_zero()
+        {
+            return Err(rejected(format!(
+                "order price ({}) breaks minimum tick size rule: {tick_size}",
+                terms.price
+            )));
+        }
+        if matches!(order_type, OrderType::GTC | OrderType::GTD)
+            && let Some(min_order_size) = engine.min_order_size()
+            && terms.size < min_order_size
+        {
+            return Err(rejected(format!(
+                "order size ({}) lower than the minimum: {min_order_size}",
+                terms.size
+            )));
+        }
+        let liquidity = engine.liquidity(terms.side, terms.price);
+
+        let available = match terms.side {
+            Side::BUY => state.available_balance() / terms.price,
+            Side::SELL => state.available_shares(&terms.token_id),
+        };
+        if terms.size > available {
+            return Err(rejected(format!(
+                "not enough balance / allowance: {} shares at {} needs more than the available {}",
+                terms.size,
+                terms.price,
+                match terms.side {
+                    Side::BUY => state.available_balance(),
+                    Side::SELL => available,
+                }
+            )));
+        }
+        if order_type == OrderType::FOK && liquidity < terms.size {
+            return Err(rejected(
+                "order couldn't be fully filled. FOK orders are fully filled or killed."
+                    .to_string(),
+            ));
+        }
+        if order_type == OrderType::FAK && liquidity.is_zero() {
+            return Err(rejected(
+                "no orders found to match with FAK order. FAK orders are partially filled or killed if no match is found."
+                    .to_string(),
+            ));
+        }
+
+        state.next_order += 1;
+        let order_id = format!("paper-order-{}", state.next_order);
+        let engine = state.engines.get_mut(&terms.token_id).unwrap();
+        let fills = engine.take(&order_id, terms.side, terms.price, terms.size);
+        let size_matched: Decimal = fills.iter().map(|fill| fill.size).sum();
+        let notional: Decimal = fills.iter().map(|fill| fill.price * fill.size).sum();
+        if matches!(order_type, OrderType::GTC | OrderType::GTD) && size_matched < terms.size {
+            engine.rest(PaperOrder {
+                order_id: order_id.clone(),
+                token_id: terms.token_id.clone(),
+                side: terms.side,
+                price: terms.price,
+                original_size: terms.size,
+                size_matched,
+                order_type,
+                expiration: terms.expiration,
+                created_at: now,
+            });
+        }
+
+        let trade_ids: Vec<String> = fills
+            .into_iter()
+            .map(|fill| state.settle(&terms.token_id, fill, false, now))
+            .collect();
+        let (making_amount, taking_amount) = match terms.side {
+            Side::BUY => (notional, size_matched),
+            Side::SELL => (size_matched, notional),
+        };
+        Ok(OrderCreateResponse {
+            success: true,
+            order_id,
+            status: if trade_ids.is_empty() {
+                OrderCreateStatus::Live
+            } else {
+                OrderCreateStatus::Matched
+            },
+            making_amount: making_amount.normalize().to_string(),
+            taking_amount: taking_amount.normalize().to_string(),
+            transaction_hashes: vec![],
+            trade_ids,
+            error_msg: String::new(),
+        })
+    }
+}
+
+#[async_trait]
+impl Orders for PaperExchange {
+    async fn post_order(
+        &self,
+        body: Order,
+        _defer_exec: bool,
+        order_type: OrderType,
+        _owner: String,
+    ) -> Result<OrderCreateResponse, ApiError> {
+        if self.config.refresh_books {
+            self.refresh(&body.tokenId.to_string()).await?;
+        }
+        self.place(&body, order_type)
+    }
+}
+
+/// The price, size and lifetime of a signed [`Order`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct OrderTerms {
+    token_id: String,
+    side: Side,
+    price: Decimal,
+    size: Decimal,
+    expiration: Option<DateTime<Utc>>,
+}
+
+impl TryFrom<&Order> for OrderTerms {
+    type Error = String;
+
+    /// A buy gives `makerAmount` USDC for `takerAmount` shares and a sell
+    /// gives `makerAmount` shares for `takerAmount` USDC
+    fn try_from(order: &Order) -> Result<Self, Self::Error> {
+        let side = match order.side {
+            0 => Side::BUY,
+            1 => Side::SELL,
+            side => return Err(format!("invalid order side {side}")),
+        };
+        let maker_amount = base_units(order.makerAmount)?;
+        let taker_amount = base_units(order.takerAmount)?;
+        if maker_amount.is_zero() || taker_amount.is_zero() {
+            return Err("invalid order amounts, they must be positive".to_string());
+        }
+        let (price, size) = match side {
+            Side::BUY => (maker_amount / taker_amount, taker_amount),
+            Side::SELL => (taker_amount / maker_amount, maker_amount),
+        };
+        if price >= Decimal::ONE {
+            return Err(format!("invalid order price ({price}), it must be below 1"));
+        }
+        let expiration = match order.expiration {
+            expiration if expiration.is_zero() => None,
+            expiration => Some(
+                u64::try_from(expiration)
+                    .ok()
+                    .and_then(|seconds| i64::try_from(seconds).ok())
+                    .and_then(|seconds| DateTime::from_timestamp(seconds, 0))
+                    .ok_or_else(|| format!("invalid expiration value ({expiration})"))?,
+            ),
+        };
+
+        Ok(Self {
+            token_id: order.tokenId.to_string(),
+            side,
+            price: price.normalize(),
+            size,
+            expiration,
+        })
+    }
+}
+
+/// An amount of USDC or shares, both with 6 decimals on-chain
+fn base_units(amount: U256) -> Result<Decimal, String> {
+    u64::try_from(amount)
+        .ok()
+        .map(|amount| Decimal::from_i128_with_scale(amount.into(), USDC_DECIMALS.into()))
+        .ok_or_else(|| format!("invalid order amount ({amount})"))
+}
+
+/// The error the CLOB answers an invalid order with
+fn rejected(message: String) -> ApiError {
+    ApiError::Http(HttpError {
+        status: 400,
+        url: None,
+        body: serde_json::json!({ "error": message }).to_string(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clob_client::orders::{Trader, TradingMode};
+    use crate::public::orderbook::PriceLevel;
+    use alloy::primitives::Address;
+    use chrono::TimeDelta;
+    use rust_decimal::dec;
+    use std::sync::Arc;
+
+    const TOKEN: &str = "1234";
+
+    fn exchange(starting_balance: Decimal) -> PaperExchange {
+        PaperExchange::new(PaperConfig {
+            starting_balance,
+            refresh_books: false,
+        })
+    }
+
+    fn snapshot(
+        at: DateTime<Utc>,
+        bids: &[(Decimal, Decimal)],
+        asks: &[(Decimal, Decimal)],
+    ) -> OrderBookSnapshot {
+        let levels = |levels: &[(Decimal, Decimal)]| {
+            levels
+                .iter()
+                .map(|&(price, size)| PriceLevel { price, size })
+                .collect()
+        };
+        OrderBookSnapshot {
+            market: "0xmarket".to_string(),
+            asset_id: TOKEN.to_string(),
+            timestamp: at,
+            hash: String::new(),
+            bids: levels(bids),
+            asks: levels(asks),
+            min_order_size: dec!(5),
+            tick_size: dec!(0.01),
+            neg_risk: false,
+        }
+    }
+
+    /// A signed-order body for `size` shares at `price`
+    fn order(
+        side: Side,
+        price: Decimal,
+        size: Decimal,
+        expiration: Option<DateTime<Utc>>,
+    ) -> Order {
+        let units =
+            |amount: Decimal| U256::from((amount * dec!(1_000_000)).trunc().mantissa() as u64);
+        let (maker_amount, taker_amount) = match side {
+            Side::BUY => (price * size, size),
+            Side::SELL => (size, price * size),
+        };
+        Order {
+            maker: Address::ZERO,
+            signer: Address::ZERO,
+            taker: Address::ZERO,
+            tokenId: TOKEN.parse().unwrap(),
+            makerAmount: units(maker_amount),
+            takerAmount: units(taker_amount),
+            expiration: U256::from(expiration.map_or(0, |at| at.timestamp() as u64)),
+            nonce: U256::ZERO,
+            feeRateBps: U256::ZERO,
+            side: side.into(),
+            signatureType: 0,
+            salt: U256::ZERO,
+        }
+    }
+
+    async fn post(
+        exchange: &PaperExchange,
+        order: Order,
+        order_type: OrderType,
+    ) -> Result<OrderCreateResponse, ApiError> {
+        exchange
+            .post_order(order, false, order_type, String::new())
+            .await
+    }
+
+    fn rejection(result: Result<OrderCreateResponse, ApiError>) -> String {
+        match result {
+            Err(ApiError::Http(error)) => {
+                assert_eq!(error.status, 400);
+                error.body
+            }
+            other => panic!("expected a rejected order, got {other:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_gtc_takes_liquidity_and_rests_the_rest() {
+        let exchange = exchange(dec!(100));
+        exchange.apply_snapshot(snapshot(
+            Utc::now(),
+            &[(dec!(0.45), dec!(100))],
+            &[(dec!(0.50), dec!(10)), (dec!(0.60), dec!(100))],
+        ));
+
+        let response = post(
+            &exchange,
+            order(Side::BUY, dec!(0.55), dec!(30), None),
+            OrderType::GTC,
+        )
+        .await
+        .unwrap();
+
+        assert!(response.success);
+        assert_eq!(response.status, OrderCreateStatus::Matched);
+        assert_eq!(response.making_amount, "5");
+        assert_eq!(response.taking_amount, "10");
+        assert_eq!(response.trade_ids.len(), 1);
+        assert_eq!(exchange.position(TOKEN), dec!(10));
+        assert_eq!(exchange.balance(), dec!(95));
+        // 20 shares at 0.55 stay reserved
+        assert_eq!(exchange.available_balance(), dec!(84));
+        let open = exchange.open_orders();
+        assert_eq!(open.len(), 1);
+        assert_eq!(open[0].remaining(), dec!(20));
+    }
+
+    #[tokio::test]
+    async fn test_resting_order_fills_on_a_crossing_book() {
+        let start = Utc::now();
+        let exchange = exchange(dec!(100));
+        exchange.apply_snapshot(snapshot(
+            start,
+            &[(dec!(0.40), dec!(50))],
+            &[(dec!(0.60), dec!(50))],
+        ));
+        let response = post(
+            &exchange,
+            order(Side::BUY, dec!(0.50), dec!(20), None),
+            OrderType::GTC,
+        )
+        .await
+        .unwrap();
+        assert_eq!(response.status, OrderCreateStatus::Live);
+        assert!(response.trade_ids.is_empty());
+
+        let trades = exchange.apply_snapshot(snapshot(
+            start + TimeDelta::seconds(5),
+            &[(dec!(0.40), dec!(50))],
+            &[(dec!(0.48), dec!(50))],
+        ));
+
+        assert_eq!(trades.len(), 1);
+        assert!(trades[0].maker);
+        assert_eq!(trades[0].order_id, response.order_id);
+        assert_eq!((trades[0].price, trades[0].size), (dec!(0.50), dec!(20)));
+        assert_eq!(exchange.balance(), dec!(90));
+        assert_eq!(exchange.position(TOKEN), dec!(20));
+        assert!(exchange.open_orders().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_fok_fills_completely_or_is_killed() {
+        let exchange = exchange(dec!(100));
+        exchange.apply_snapshot(snapshot(
+            Utc::now(),
+            &[],
+            &[(dec!(0.50), dec!(10)), (dec!(0.52), dec!(10))],
+        ));
+
+        let killed = post(
+            &exchange,
+            order(Side::BUY, dec!(0.52), dec!(25), None),
+            OrderType::FOK,
+        )
+        .await;
+        assert!(rejection(killed).contains("FOK"));
+        assert_eq!(exchange.balance(), dec!(100));
+
+        let filled = post(
+            &exchange,
+            order(Side::BUY, dec!(0.52), dec!(20), None),
+            OrderType::FOK,
+        )
+        .await
+        .unwrap();
+        assert_eq!(filled.taking_amount, "20");
+        assert_eq!(filled.making_amount, "10.2");
+        assert!(exchange.open_orders().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_fak_fills_what_it_can() {
+        let exchange = exchange(dec!(100));
+        exchange.apply_snapshot(snapshot(Utc::now(), &[], &[(dec!(0.50), dec!(10))]));
+
+        let response = post(
+            &exchange,
+            order(Side::BUY, dec!(0.55), dec!(30), None),
+            OrderType::FAK,
+        )
+        .await
+        .unwrap();
+        assert_eq!(response.taking_amount, "10");
+        assert!(exchange.open_orders().is_empty());
+
+        // The book is now empty on the ask side
+        let killed = post(
+            &exchange,
+            order(Side::BUY, dec!(0.55), dec!(30), None),
+            OrderType::FAK,
+        )
+        .await;
+        assert!(rejection(killed).contains("FAK"));
+    }
+
+    #[tokio::test]
+    async fn test_only_gtd_orders_expire() {
+        let start = Utc::now();
+        let exchange = exchange(dec!(100));
+        exchange.apply_snapshot(snapshot(start, &[(dec!(0.40), dec!(10))], &[]));
+
+        let expired = post(
+            &exchange,
+            order(Side::BUY, dec!(0.30), dec!(5), Some(start)),
+            OrderType::GTD,
+        )
+        .await;
+        assert!(rejection(expired).contains("expiration"));
+        let without_expiration = post(
+            &exchange,
+            order(Side::BUY, dec!(0.30), dec!(5), None),
+            OrderType::GTD,
+        )
+        .await;
+        assert!(rejection(without_expiration).contains("GTD"));
+        let gtc_with_expiration = post(
+            &exchange,
+            order(
+                Side::BUY,
+                dec!(0.30),
+                dec!(5),
+                Some(start + TimeDelta::seconds(60)),
+            ),
+            OrderType::GTC,
+        )
+        .await;
+        assert!(rejection(gtc_with_expiration).contains("GTD"));
+    }
+
+    #[tokio::test]
+    async fn test_orders_need_the_available_balance() {
+        let start = Utc::now();
+        let exchange = exchange(dec!(10));
+        exchange.apply_snapshot(snapshot(
+            start,
+            &[(dec!(0.40), dec!(100))],
+            &[(dec!(0.60), dec!(100))],
+        ));
+
+        // 20 shares at 0.50 reserve the whole balance
+        post(
+            &exchange,
+            order(Side::BUY, dec!(0.50), dec!(20), None),
+            OrderType::GTC,
+        )
+        .await
+        .unwrap();
+        let broke = post(
+            &exchange,
+            order(Side::BUY, dec!(0.50), dec!(5), None),
+            OrderType::GTC,
+        )
+        .await;
+        assert!(rejection(broke).contains("not enough balance"));
+        let no_shares = post(
+            &exchange,
+            order(Side::SELL, dec!(0.40), dec!(5), None),
+            OrderType::FOK,
+        )
+        .await;
+        assert!(rejection(no_shares).contains("not enough balance"));
+
+        let cancelled = exchange.cancel_order(&exchange.open_orders()[0].order_id);
+        assert!(cancelled.is_some());
+        assert_eq!(exchange.available_balance(), dec!(10));
+
+        // A GTD order that outlives one book but not the next
+        post(
+            &exchange,
+            order(
+                Side::BUY,
+                dec!(0.50),
+                dec!(10),
+                Some(start + TimeDelta::seconds(60)),
+            ),
+            OrderType::GTD,
+        )
+        .await
+        .unwrap();
+        assert_eq!(exchange.available_balance(), dec!(5));
+        exchange.apply_snapshot(snapshot(
+            start + TimeDelta::seconds(60),
+            &[(dec!(0.40), dec!(100))],
+            &[(dec!(0.60), dec!(100))],
+        ));
+        assert!(exchange.open_orders().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_orders_respect_the_tick_and_minimum_size() {
+        let exchange = exchange(dec!(100));
+        exchange.apply_snapshot(snapshot(Utc::now(), &[], &[(dec!(0.60), dec!(100))]));
+
+        let off_tick = post(
+            &exchange,
+            order(Side::BUY, dec!(0.505), dec!(10), None),
+            OrderType::GTC,
+        )
+        .await;
+        assert!(rejection(off_tick).contains("tick size"));
+        let too_small = post(
+            &exchange,
+            order(Side::BUY, dec!(0.50), dec!(2), None),
+            OrderType::GTC,
+        )
+        .await;
+        assert!(rejection(too_small).contains("minimum"));
+    }
+
+    #[tokio::test]
+    async fn test_market_buy_with_rounded_amounts_is_not_off_tick() {
+        let exchange = exchange(dec!(100));
+        exchange.apply_snapshot(snapshot(Utc::now(), &[], &[(dec!(0.57), dec!(100))]));
+        // 10 USDC at 0.57 buys 17.5438 shares after rounding, a price of 0.570001...
+        let mut market_buy = order(Side::BUY, dec!(0.57), dec!(10), None);
+        market_buy.makerAmount = U256::from(10_000_000u64);
+        market_buy.takerAmount = U256::from(17_543_800u64);
+
+        let response = post(&exchange, market_buy, OrderType::FOK).await.unwrap();
+
+        assert_eq!(response.taking_amount, "17.5438");
+        assert_eq!(exchange.position(TOKEN), dec!(17.5438));
+    }
+
+    #[tokio::test]
+    async fn test_paper_trader_runs_in_spawned_tasks() {
+        let trader = Trader::new(TradingMode::Paper(PaperConfig {
+            starting_balance: dec!(100),
+            refresh_books: false,
+        }))
+        .await
+        .unwrap();
+        let trader = Arc::new(trader);
+        let exchange = trader.paper().unwrap();
+        exchange.apply_snapshot(snapshot(Utc::now(), &[], &[(dec!(0.50), dec!(100))]));
+
+        let handles: Vec<_> = (0..4)
+            .map(|_| {
+                let trader = trader.clone();
+                tokio::spawn(async move {
+                    trader
+                        .post_order(
+                            order(Side::BUY, dec!(0.50), dec!(10), None),
+                            false,
+                            OrderType::FOK,
+                            String::new(),
+                        )
+                        .await
+                })
+            })
+            .collect();
+        for handle in handles {
+            assert!(handle.await.unwrap().unwrap().success);
+        }
+
+        assert_eq!(trader.paper().unwrap().position(TOKEN), dec!(40));
+    }
+
+    #[test]
+    fn test_order_terms_from_amounts() {
+        let buy = OrderTerms::try_from(&order(Side::BUY, dec!(0.55), dec!(10), None)).unwrap();
+        assert_eq!(
+            (buy.side, buy.price, buy.size),
+            (Side::BUY, dec!(0.55), dec!(10))
+        );
+
+        let sell = OrderTerms::try_from(&order(Side::SELL, dec!(0.3), dec!(12.5), None)).unwrap();
+        assert_eq!(
+            (sell.side, sell.price, sell.size),
+            (Side::SELL, dec!(0.3), dec!(12.5))
+        );
+        assert_eq!(sell.token_id, TOKEN);
+
+        let mut invalid = order(Side::BUY, dec!(0.5), dec!(10), None);
+        invalid.side = 2;
+        assert!(OrderTerms::try_from(&invalid).is_err());
+    }
+}
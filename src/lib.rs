@@ -1,4 +1,8 @@
+// Lets the derive macros refer to `::poly_rc` from inside this crate
+extern crate self as poly_rc;
+
 pub mod clob_client;
+pub mod onchain;
 pub mod public;
 pub mod shared;
 
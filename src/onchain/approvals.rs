@@ -0,0 +1,304 @@
+//! The token approvals a wallet needs before it can trade.
+//!
+//! The exchanges move the collateral and the outcome tokens of the wallet
+//! when orders are matched, and the neg-risk adapter does the same when
+//! positions are split, merged or converted. Each of them needs a USDC
+//! allowance and an ERC-1155 operator approval on the Conditional Tokens
+//! contract.
+
+use alloy::primitives::{Address, TxHash, U256};
+use alloy::providers::{Provider, WalletProvider};
+use serde::{Deserialize, Serialize};
+
+use crate::clob_client::config::ChainConfig;
+use crate::onchain::contracts::{IConditionalTokens, IERC20};
+use crate::onchain::ctf::confirm;
+use crate::shared::ApiError;
+
+/// Which approval a spender needs
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum ApprovalKind {
+    /// An ERC-20 `approve` of the collateral
+    Collateral,
+    /// An ERC-1155 `setApprovalForAll` of the outcome tokens
+    OutcomeTokens,
+}
+
+/// One approval of a token to a spender
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct RequiredApproval {
+    pub kind: ApprovalKind,
+    /// The collateral or Conditional Tokens contract
+    pub token: Address,
+    /// The exchange or adapter allowed to move the tokens
+    pub spender: Address,
+}
+
+/// The state of one approval after [`ensure_approvals`] or [`check_approvals`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ApprovalStatus {
+    pub approval: RequiredApproval,
+    /// Whether the approval was already granted
+    pub granted: bool,
+    /// The transaction granting it, `None` if it was already granted or in a dry run
+    pub transaction: Option<TxHash>,
+}
+
+/// What [`ensure_approvals`] or [`check_approvals`] found and changed
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ApprovalReport {
+    pub owner: Address,
+    pub dry_run: bool,
+    pub approvals: Vec<ApprovalStatus>,
+}
+
+impl ApprovalReport {
+    /// The approvals that were not granted before the call
+    pub fn missing(&self) -> impl Iterator<Item = &ApprovalStatus> {
+        self.approvals.iter().filter(|status| !status.granted)
+    }
+
+    /// The approvals granted by the call
+    pub fn changed(&self) -> impl Iterator<Item = &ApprovalStatus> {
+        self.approvals
+            .iter()
+            .filter(|status| status.transaction.is_some())
+    }
+
+    /// Whether the wallet can trade on every exchange
+    pub fn is_complete(&self) -> bool {
+        self.approvals
+            .iter()
+            .all(|status| status.granted || status.transaction.is_some())
+    }
+}
+
+/// Allowances at or above this are considered unlimited. USDC decrements the
+/// allowance on every transfer, so a `U256::MAX` approval slowly goes down
+const UNLIMITED_ALLOWANCE: U256 = U256::from_limbs([0, 0, 0, 1 << 63]);
+
+/// The approvals needed to trade on the standard and neg-risk exchanges of a chain.
+///
+/// The standard exchange, the neg-risk exchange and the neg-risk adapter each
+/// need both kinds of approval. Duplicate spenders are listed once.
+pub fn required_approvals(config: &ChainConfig) -> Vec<RequiredApproval> {
+    let spenders = [
+        Some(config.standard_config.exchange_contract),
+        Some(config.neg_risk_config.exchange_contract),
+        config.neg_risk_config.neg_risk_contract,
+    ];
+    let mut approvals: Vec<RequiredApproval> = Vec::new();
+    for spender in spenders.into_iter().flatten() {
+        for (kind, token) in [
+            (
+                ApprovalKind::Collateral,
+                config.standard_config.collateral_contract,
+            ),
+            (
+                ApprovalKind::OutcomeTokens,
+                config.standard_config.ctf_contract,
+            ),
+        ] {
+            let approval = RequiredApproval {
+                kind,
+                token,
+                spender,
+            };
+            if !approvals.contains(&approval) {
+                approvals.push(approval);
+            }
+        }
+    }
+    approvals
+}
+
+/// Read every approval the wallet of `provider` needs on `config` and grant
+/// the missing ones.
+///
+/// Collateral is approved for `U256::MAX`. Transactions are sent one at a time
+/// from the default signer of the provider, which is the wallet checked, and
+/// each one is awaited until mined. With `dry_run` nothing is sent and the
+/// report only lists what is missing.
+/// # Arguments
+/// * `provider` - The provider, with the wallet to approve from
+/// * `config` - The contracts to approve, e.g. `Chains::Polygon.config()`
+/// * `dry_run` - Only report the missing approvals
+/// # Returns
+/// * `Result<ApprovalReport, ApiError>` - The state of every approval, or an [`ApiError`] if a call or transaction fails
+pub async fn ensure_approvals<P: Provider + WalletProvider>(
+    provider: &P,
+    config: &ChainConfig,
+    dry_run: bool,
+) -> Result<ApprovalReport, ApiError> {
+    let owner = provider.default_signer_address();
+    let mut report = check_approvals(provider, config, owner).await?;
+    if dry_run {
+        return Ok(report);
+    }
+    for status in report.approvals.iter_mut().filter(|status| !status.granted) {
+        status.transaction = Some(grant(provider, &status.approval).await?);
+    }
+    report.dry_run = false;
+    Ok(report)
+}
+
+/// Read every approval `owner` needs on `config` without sending anything,
+/// e.g. to check a wallet the provider cannot sign for
+/// # Arguments
+/// * `provider` - Any provider, no wallet is needed
+/// * `config` - The contracts to check, e.g. `Chains::Polygon.config()`
+/// * `owner` - The wallet to check
+/// # Returns
+/// * `Result<ApprovalReport, ApiError>` - The state of every approval as a dry run, or an [`ApiError`] if a call fails
+pub async fn check_approvals<P: Provider>(
+    provider: &P,
+    config: &ChainConfig,
+    owner: Address,
+) -> Result<ApprovalReport, ApiError> {
+    let mut approvals = Vec::new();
+    for approval in required_approvals(config) {
+        approvals.push(ApprovalStatus {
+            approval,
+            granted: is_granted(provider, &approval, owner).await?,
+            transaction: None,
+        });
+    }
+    Ok(ApprovalReport {
+        owner,
+        dry_run: true,
+        approvals,
+    })
+}
+
+async fn is_granted<P: Provider>(
+    provider: &P,
+    approval: &RequiredApproval,
+    owner: Address,
+) -> Result<bool, ApiError> {
+    Ok(match approval.kind {
+        ApprovalKind::Collateral => {
+            IERC20::new(approval.token, provider)
+                .allowance(owner, approval.spender)
+                .call()
+                .await?
+                >= UNLIMITED_ALLOWANCE
+        }
+        ApprovalKind::OutcomeTokens => {
+            IConditionalTokens::new(approval.token, provider)
+                .isApprovedForAll(owner, approval.spender)
+                .call()
+                .await?
+        }
+    })
+}
+
+async fn grant<P: Provider>(provider: &P, approval: &RequiredApproval) -> Result<TxHash, ApiError> {
+    let pending = match approval.kind {
+        ApprovalKind::Collateral => {
+            IERC20::new(approval.token, provider)
+                .approve(approval.spender, U256::MAX)
+                .send()
+                .await?
+        }
+        ApprovalKind::OutcomeTokens => {
+            IConditionalTokens::new(approval.token, provider)
+                .setApprovalForAll(approval.spender, true)
+                .send()
+                .await?
+        }
+    };
+    Ok(confirm(pending).await?.transaction_hash)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clob_client::config::Chains;
+    use alloy::providers::ProviderBuilder;
+    use alloy::signers::local::PrivateKeySigner;
+
+    #[test]
+    fn test_required_approvals() {
+        let config = Chains::Polygon.config();
+
+        let approvals = required_approvals(&config);
+
+        // Two approvals for each of the standard exchange, neg-risk exchange and adapter
+        assert_eq!(approvals.len(), 6);
+        let spenders: Vec<Address> = approvals.iter().map(|a| a.spender).collect();
+        assert!(spenders.contains(&config.standard_config.exchange_contract));
+        assert!(spenders.contains(&config.neg_risk_config.exchange_contract));
+        assert!(spenders.contains(&config.neg_risk_config.neg_risk_contract.unwrap()));
+        assert_eq!(
+            approvals
+                .iter()
+                .filter(|a| a.kind == ApprovalKind::OutcomeTokens)
+                .count(),
+            3
+        );
+    }
+
+    #[test]
+    fn test_required_approvals_skip_duplicate_spenders() {
+        let mut config = Chains::Polygon.config();
+        config.neg_risk_config.neg_risk_contract = Some(config.neg_risk_config.exchange_contract);
+
+        assert_eq!(required_approvals(&config).len(), 4);
+    }
+
+    #[test]
+    fn test_report_counts_changes() {
+        let approvals = required_approvals(&Chains::Polygon.config());
+        let report = ApprovalReport {
+            owner: Address::ZERO,
+            dry_run: false,
+            approvals: vec![
+                ApprovalStatus {
+                    approval: approvals[0],
+                    granted: true,
+                    transaction: None,
+                },
+                ApprovalStatus {
+                    approval: approvals[1],
+                    granted: false,
+                    transaction: Some(TxHash::ZERO),
+                },
+                ApprovalStatus {
+                    approval: approvals[2],
+                    granted: false,
+                    transaction: None,
+                },
+            ],
+        };
+
+        assert_eq!(report.missing().count(), 2);
+        assert_eq!(report.changed().count(), 1);
+        assert!(!report.is_complete());
+    }
+
+    #[tokio::test]
+    #[ignore = "needs an anvil node forking Polygon"]
+    async fn test_ensure_approvals_on_fork() {
+        // The first default anvil account
+        let signer: PrivateKeySigner =
+            "0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80"
+                .parse()
+                .unwrap();
+        let owner = signer.address();
+        let url = std::env::var("ANVIL_RPC_URL").unwrap_or("http://127.0.0.1:8545".to_string());
+        let provider = ProviderBuilder::new()
+            .wallet(signer)
+            .connect_http(url.parse().unwrap());
+        let config = Chains::Polygon.config();
+
+        let dry_run = ensure_approvals(&provider, &config, true).await.unwrap();
+        assert_eq!(dry_run.owner, owner);
+        assert_eq!(dry_run.changed().count(), 0);
+
+        let report = ensure_approvals(&provider, &config, false).await.unwrap();
+        assert!(report.is_complete());
+
+        let again = check_approvals(&provider, &config, owner).await.unwrap();
+        assert_eq!(again.missing().count(), 0);
+    }
+}
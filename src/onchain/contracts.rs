@@ -0,0 +1,61 @@
+//! Bindings of the contracts Polymarket settles on.
+//!
+//! Only the functions the SDK calls are declared. The addresses of each
+//! deployment are in [`Chains::config`](crate::clob_client::config::Chains::config).
+
+use alloy::sol;
+
+sol! {
+    /// The Gnosis Conditional Tokens contract (ERC-1155) holding every outcome token
+    #[sol(rpc)]
+    interface IConditionalTokens {
+        function prepareCondition(address oracle, bytes32 questionId, uint256 outcomeSlotCount) external;
+        function reportPayouts(bytes32 questionId, uint256[] calldata payouts) external;
+        function splitPosition(
+            address collateralToken,
+            bytes32 parentCollectionId,
+            bytes32 conditionId,
+            uint256[] calldata partition,
+            uint256 amount
+        ) external;
+        function mergePositions(
+            address collateralToken,
+            bytes32 parentCollectionId,
+            bytes32 conditionId,
+            uint256[] calldata partition,
+            uint256 amount
+        ) external;
+        function redeemPositions(
+            address collateralToken,
+            bytes32 parentCollectionId,
+            bytes32 conditionId,
+            uint256[] calldata indexSets
+        ) external;
+        function getConditionId(address oracle, bytes32 questionId, uint256 outcomeSlotCount) external pure returns (bytes32);
+        function getCollectionId(bytes32 parentCollectionId, bytes32 conditionId, uint256 indexSet) external view returns (bytes32);
+        function getPositionId(address collateralToken, bytes32 collectionId) external pure returns (uint256);
+        function getOutcomeSlotCount(bytes32 conditionId) external view returns (uint256);
+        function payoutDenominator(bytes32 conditionId) external view returns (uint256);
+        function balanceOf(address owner, uint256 id) external view returns (uint256);
+        function isApprovedForAll(address owner, address operator) external view returns (bool);
+        function setApprovalForAll(address operator, bool approved) external;
+    }
+
+    /// The adapter wrapping the collateral of neg-risk markets
+    #[sol(rpc)]
+    interface INegRiskAdapter {
+        function splitPosition(bytes32 conditionId, uint256 amount) external;
+        function mergePositions(bytes32 conditionId, uint256 amount) external;
+        function redeemPositions(bytes32 conditionId, uint256[] calldata amounts) external;
+        function wcol() external view returns (address);
+    }
+
+    /// The collateral token, USDC
+    #[sol(rpc)]
+    interface IERC20 {
+        function balanceOf(address owner) external view returns (uint256);
+        function allowance(address owner, address spender) external view returns (uint256);
+        function approve(address spender, uint256 amount) external returns (bool);
+        function transfer(address to, uint256 amount) external returns (bool);
+    }
+}
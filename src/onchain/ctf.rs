@@ -0,0 +1,574 @@
+use alloy::network::Ethereum;
+use alloy::primitives::{Address, B256, U256};
+use alloy::providers::{PendingTransactionBuilder, Provider};
+use alloy::rpc::types::TransactionReceipt;
+
+use crate::clob_client::config::{Chains, ContractConfig};
+use crate::onchain::contracts::{IConditionalTokens, INegRiskAdapter};
+use crate::shared::ApiError;
+
+/// The index sets of the two outcomes of a binary condition, YES then NO
+pub const BINARY_PARTITION: [u8; 2] = [1, 2];
+
+/// Splits, merges and redeems outcome tokens on the Conditional Tokens contract.
+///
+/// Amounts are in base units of the collateral, 6 decimals for USDC. Every
+/// transaction is signed by the wallet of the provider and the call returns
+/// once it is mined. The contracts must already be approved to move the
+/// collateral and the outcome tokens of the wallet.
+///
+/// Neg-risk markets hold wrapped collateral, so they are split, merged and
+/// redeemed through the neg-risk adapter with the `_neg_risk` variants.
+#[derive(Debug, Clone)]
+pub struct CtfClient<P> {
+    provider: P,
+    ctf: Address,
+    collateral: Address,
+    neg_risk_adapter: Option<Address>,
+}
+
+impl<P: Provider> CtfClient<P> {
+    /// Use the contracts of a custom deployment, e.g. on a local node
+    pub fn new(provider: P, contracts: &ContractConfig) -> Self {
+        Self {
+            provider,
+            ctf: contracts.ctf_contract,
+            collateral: contracts.collateral_contract,
+            neg_risk_adapter: contracts.neg_risk_contract,
+        }
+    }
+
+    /// Use the Polymarket contracts of `chain`.
+    ///
+    /// Only the neg-risk config names the adapter. Its Conditional Tokens and
+    /// collateral contracts are the same as the standard config's, so the
+    /// client handles both kinds of market
+    pub fn for_chain(provider: P, chain: Chains) -> Self {
+        Self::new(provider, &chain.config().neg_risk_config)
+    }
+
+    pub fn provider(&self) -> &P {
+        &self.provider
+    }
+
+    fn conditional_tokens(&self) -> IConditionalTokens::IConditionalTokensInstance<&P> {
+        IConditionalTokens::new(self.ctf, &self.provider)
+    }
+
+    fn neg_risk_adapter(&self) -> Result<INegRiskAdapter::INegRiskAdapterInstance<&P>, ApiError> {
+        let adapter = self
+            .neg_risk_adapter
+            .ok_or_else(|| ApiError::Contract("No neg-risk adapter is configured".to_string()))?;
+        Ok(INegRiskAdapter::new(adapter, &self.provider))
+    }
+
+    /// Lock `amount` of collateral and receive `amount` of both outcome tokens
+    /// # Arguments
+    /// * `condition_id` - The condition id of the market
+    /// * `amount` - The collateral to split, in base units
+    /// # Returns
+    /// * `Result<TransactionReceipt, ApiError>` - The receipt, or an [`ApiError`] if the transaction fails or reverts
+    pub async fn split_position(
+        &self,
+        condition_id: B256,
+        amount: U256,
+    ) -> Result<TransactionReceipt, ApiError> {
+        let pending = self
+            .conditional_tokens()
+            .splitPosition(
+                self.collateral,
+                B256::ZERO,
+                condition_id,
+                binary_partition(),
+                amount,
+            )
+            .send()
+            .await?;
+        confirm(pending).await
+    }
+
+    /// Burn `amount` of both outcome tokens and get `amount` of collateral back
+    /// # Arguments
+    /// * `condition_id` - The condition id of the market
+    /// * `amount` - The number of full sets to merge, in base units
+    /// # Returns
+    /// * `Result<TransactionReceipt, ApiError>` - The receipt, or an [`ApiError`] if the transaction fails or reverts
+    pub async fn merge_positions(
+        &self,
+        condition_id: B256,
+        amount: U256,
+    ) -> Result<TransactionReceipt, ApiError> {
+        let pending = self
+            .conditional_tokens()
+            .mergePositions(
+                self.collateral,
+                B256::ZERO,
+                condition_id,
+                binary_partition(),
+                amount,
+            )
+            .send()
+            .await?;
+        confirm(pending).await
+    }
+
+    /// Burn every outcome token of a resolved condition held by the wallet
+    /// and receive the collateral they pay out
+    /// # Arguments
+    /// * `condition_id` - The condition id of the market
+    /// # Returns
+    /// * `Result<TransactionReceipt, ApiError>` - The receipt, or an [`ApiError`] if the transaction fails or reverts
+    pub async fn redeem_positions(
+        &self,
+        condition_id: B256,
+    ) -> Result<TransactionReceipt, ApiError> {
+        let pending = self
+            .conditional_tokens()
+            .redeemPositions(
+                self.collateral,
+                B256::ZERO,
+                condition_id,
+                binary_partition(),
+            )
+            .send()
+            .await?;
+        confirm(pending).await
+    }
+
+    /// [`split_position`](Self::split_position) for a neg-risk market
+    pub async fn split_position_neg_risk(
+        &self,
+        condition_id: B256,
+        amount: U256,
+    ) -> Result<TransactionReceipt, ApiError> {
+        let pending = self
+            .neg_risk_adapter()?
+            .splitPosition(condition_id, amount)
+            .send()
+            .await?;
+        confirm(pending).await
+    }
+
+    /// [`merge_positions`](Self::merge_positions) for a neg-risk market
+    pub async fn merge_positions_neg_risk(
+        &self,
+        condition_id: B256,
+        amount: U256,
+    ) -> Result<TransactionReceipt, ApiError> {
+        let pending = self
+            .neg_risk_adapter()?
+            .mergePositions(condition_id, amount)
+            .send()
+            .await?;
+        confirm(pending).await
+    }
+
+    /// Redeem the outcome tokens of a resolved neg-risk market. Unlike
+    /// [`redeem_positions`](Self::redeem_positions), the adapter needs the amounts to burn
+    /// # Arguments
+    /// * `condition_id` - The condition id of the market
+    /// * `amounts` - The YES and NO amounts to redeem, in base units
+    /// # Returns
+    /// * `Result<TransactionReceipt, ApiError>` - The receipt, or an [`ApiError`] if the transaction fails or reverts
+    pub async fn redeem_positions_neg_risk(
+        &self,
+        condition_id: B256,
+        amounts: [U256; 2],
+    ) -> Result<TransactionReceipt, ApiError> {
+        let pending = self
+            .neg_risk_adapter()?
+            .redeemPositions(condition_id, amounts.to_vec())
+            .send()
+            .await?;
+        confirm(pending).await
+    }
+
+    /// The balance of an outcome token
+    /// # Arguments
+    /// * `owner` - The holder of the tokens
+    /// * `position_id` - The ERC-1155 id of the token, i.e. the CLOB token id
+    pub async fn balance_of(&self, owner: Address, position_id: U256) -> Result<U256, ApiError> {
+        Ok(self
+            .conditional_tokens()
+            .balanceOf(owner, position_id)
+            .call()
+            .await?)
+    }
+}
+
+fn binary_partition() -> Vec<U256> {
+    BINARY_PARTITION.into_iter().map(U256::from).collect()
+}
+
+/// Wait for a transaction to be mined and fail if it reverted
+pub(crate) async fn confirm(
+    pending: PendingTransactionBuilder<Ethereum>,
+) -> Result<TransactionReceipt, ApiError> {
+    let receipt = pending.get_receipt().await?;
+    if !receipt.status() {
+        return Err(ApiError::Contract(format!(
+            "Transaction {} reverted",
+            receipt.transaction_hash
+        )));
+    }
+    Ok(receipt)
+}
+
+#[cfg(test)]
+mod tests {
+    //! Spawns its own `anvil` node and deploys the contracts from their
+    //! compiled artifacts, read from the directory in `CTF_ARTIFACTS`:
+    //! * `ConditionalTokens.json`, the Gnosis Conditional Tokens
+    //! * `MockERC20.json`, solmate's mock with `mint(to, amount)`
+    //! * `NegRiskAdapter.json`, Polymarket's neg-risk adapter
+    //!
+    //! Both forge and truffle artifacts are read. The collateral is minted to
+    //! the test wallet, and the tests prepare their own conditions with the
+    //! wallet or the impersonated neg-risk adapter as the oracle.
+
+    use super::*;
+    use crate::onchain::contracts::IERC20;
+    use alloy::network::TransactionBuilder;
+    use alloy::primitives::keccak256;
+    use alloy::providers::{ProviderBuilder, ext::AnvilApi};
+    use alloy::rpc::types::TransactionRequest;
+    use alloy::signers::local::PrivateKeySigner;
+    use alloy::sol;
+    use alloy::sol_types::SolConstructor;
+    use std::process::{Child, Command, Stdio};
+    use std::time::Duration;
+
+    sol! {
+        #[sol(rpc)]
+        contract MockERC20 {
+            constructor(string name, string symbol, uint8 decimals);
+            function mint(address to, uint256 amount) external;
+        }
+
+        contract NegRiskAdapter {
+            constructor(address ctf, address collateral, address vault);
+        }
+    }
+
+    /// The first default anvil account
+    const ANVIL_KEY: &str = "0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80";
+
+    /// A local anvil node, killed when dropped
+    struct AnvilNode {
+        child: Child,
+        url: String,
+    }
+
+    impl AnvilNode {
+        async fn spawn() -> Self {
+            let port = std::net::TcpListener::bind("127.0.0.1:0")
+                .unwrap()
+                .local_addr()
+                .unwrap()
+                .port();
+            let child = Command::new("anvil")
+                .args(["--port", &port.to_string()])
+                .stdout(Stdio::null())
+                .spawn()
+                .expect("anvil should be on the PATH");
+            for _ in 0..100 {
+                if tokio::net::TcpStream::connect(("127.0.0.1", port))
+                    .await
+                    .is_ok()
+                {
+                    break;
+                }
+                tokio::time::sleep(Duration::from_millis(50)).await;
+            }
+            Self {
+                child,
+                url: format!("http://127.0.0.1:{port}"),
+            }
+        }
+    }
+
+    impl Drop for AnvilNode {
+        fn drop(&mut self) {
+            let _ = self.child.kill();
+            let _ = self.child.wait();
+        }
+    }
+
+    /// Deploy the contract of `{name}.json` with the ABI encoded constructor `args`
+    async fn deploy<P: Provider<Ethereum>>(provider: &P, name: &str, args: Vec<u8>) -> Address {
+        let dir = std::env::var("CTF_ARTIFACTS").expect("CTF_ARTIFACTS should be set");
+        let path = std::path::Path::new(&dir).join(format!("{name}.json"));
+        let artifact: serde_json::Value =
+            serde_json::from_str(&std::fs::read_to_string(path).unwrap()).unwrap();
+        // forge keeps the creation code under `bytecode.object`, truffle right under `bytecode`
+        let bytecode = artifact["bytecode"]["object"]
+            .as_str()
+            .or(artifact["bytecode"].as_str())
+            .unwrap();
+        let mut code = alloy::hex::decode(bytecode).unwrap();
+        code.extend(args);
+
+        let deployment = TransactionRequest::default().with_deploy_code(code);
+        confirm(provider.send_transaction(deployment).await.unwrap())
+            .await
+            .unwrap()
+            .contract_address
+            .unwrap()
+    }
+
+    /// Conditional Tokens, a 6 decimals collateral and a neg-risk adapter over both
+    async fn deploy_contracts<P: Provider<Ethereum>>(provider: &P) -> ContractConfig {
+        let ctf = deploy(provider, "ConditionalTokens", vec![]).await;
+        let usdc = deploy(
+            provider,
+            "MockERC20",
+            MockERC20::constructorCall {
+                name: String::from("USD Coin"),
+                symbol: String::from("USDC"),
+                decimals: 6,
+            }
+            .abi_encode(),
+        )
+        .await;
+        let vault = Address::repeat_byte(0x0a);
+        let adapter = deploy(
+            provider,
+            "NegRiskAdapter",
+            NegRiskAdapter::constructorCall {
+                ctf,
+                collateral: usdc,
+                vault,
+            }
+            .abi_encode(),
+        )
+        .await;
+
+        ContractConfig {
+            exchange_contract: Address::ZERO,
+            collateral_contract: usdc,
+            ctf_contract: ctf,
+            neg_risk_contract: Some(adapter),
+        }
+    }
+
+    /// The YES position id of a binary condition on `collateral`
+    async fn yes_position<P: Provider<Ethereum>>(
+        conditional_tokens: &IConditionalTokens::IConditionalTokensInstance<P>,
+        collateral: Address,
+        condition_id: B256,
+    ) -> U256 {
+        let yes_collection = conditional_tokens
+            .getCollectionId(B256::ZERO, condition_id, U256::from(1))
+            .call()
+            .await
+            .unwrap();
+        conditional_tokens
+            .getPositionId(collateral, yes_collection)
+            .call()
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    #[ignore = "needs anvil and the compiled contracts in CTF_ARTIFACTS"]
+    async fn test_split_merge_redeem() {
+        let node = AnvilNode::spawn().await;
+        let signer: PrivateKeySigner = ANVIL_KEY.parse().unwrap();
+        let wallet = signer.address();
+        let provider = ProviderBuilder::new()
+            .wallet(signer)
+            .connect_http(node.url.parse().unwrap());
+        let contracts = deploy_contracts(&provider).await;
+        let ctf = CtfClient::new(provider.clone(), &contracts);
+        let usdc = IERC20::new(contracts.collateral_contract, &provider);
+        let conditional_tokens = IConditionalTokens::new(contracts.ctf_contract, &provider);
+        let amount = U256::from(10_000_000u64);
+
+        confirm(
+            MockERC20::new(contracts.collateral_contract, &provider)
+                .mint(wallet, amount)
+                .send()
+                .await
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+        confirm(
+            usdc.approve(contracts.ctf_contract, U256::MAX)
+                .send()
+                .await
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+        // A binary condition with the wallet as oracle
+        let question_id = keccak256("Will it rain tomorrow?");
+        confirm(
+            conditional_tokens
+                .prepareCondition(wallet, question_id, U256::from(2))
+                .send()
+                .await
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+        let condition_id = conditional_tokens
+            .getConditionId(wallet, question_id, U256::from(2))
+            .call()
+            .await
+            .unwrap();
+        let yes = yes_position(
+            &conditional_tokens,
+            contracts.collateral_contract,
+            condition_id,
+        )
+        .await;
+
+        ctf.split_position(condition_id, amount).await.unwrap();
+        assert_eq!(ctf.balance_of(wallet, yes).await.unwrap(), amount);
+
+        ctf.merge_positions(condition_id, U256::from(4_000_000u64))
+            .await
+            .unwrap();
+        assert_eq!(
+            ctf.balance_of(wallet, yes).await.unwrap(),
+            U256::from(6_000_000u64)
+        );
+
+        confirm(
+            conditional_tokens
+                .reportPayouts(question_id, vec![U256::from(1), U256::ZERO])
+                .send()
+                .await
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+        let before = usdc.balanceOf(wallet).call().await.unwrap();
+        ctf.redeem_positions(condition_id).await.unwrap();
+        let after = usdc.balanceOf(wallet).call().await.unwrap();
+
+        assert_eq!(ctf.balance_of(wallet, yes).await.unwrap(), U256::ZERO);
+        assert_eq!(after - before, U256::from(6_000_000u64));
+    }
+
+    #[tokio::test]
+    #[ignore = "needs anvil and the compiled contracts in CTF_ARTIFACTS"]
+    async fn test_split_merge_redeem_neg_risk() {
+        let node = AnvilNode::spawn().await;
+        let signer: PrivateKeySigner = ANVIL_KEY.parse().unwrap();
+        let wallet = signer.address();
+        let provider = ProviderBuilder::new()
+            .wallet(signer)
+            .connect_http(node.url.parse().unwrap());
+        let contracts = deploy_contracts(&provider).await;
+        let adapter = contracts.neg_risk_contract.unwrap();
+        let ctf = CtfClient::new(provider.clone(), &contracts);
+        let usdc = IERC20::new(contracts.collateral_contract, &provider);
+        let conditional_tokens = IConditionalTokens::new(contracts.ctf_contract, &provider);
+        let wcol = INegRiskAdapter::new(adapter, &provider)
+            .wcol()
+            .call()
+            .await
+            .unwrap();
+        let amount = U256::from(10_000_000u64);
+
+        confirm(
+            MockERC20::new(contracts.collateral_contract, &provider)
+                .mint(wallet, amount)
+                .send()
+                .await
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+        // The adapter pulls the USDC to wrap it, and the outcome tokens to merge or redeem them
+        confirm(usdc.approve(adapter, U256::MAX).send().await.unwrap())
+            .await
+            .unwrap();
+        confirm(
+            conditional_tokens
+                .setApprovalForAll(adapter, true)
+                .send()
+                .await
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+        // A binary condition with the adapter as oracle, as the adapter
+        // prepares for the questions of its markets
+        let question_id = keccak256("Will it rain tomorrow?");
+        confirm(
+            conditional_tokens
+                .prepareCondition(adapter, question_id, U256::from(2))
+                .send()
+                .await
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+        let condition_id = conditional_tokens
+            .getConditionId(adapter, question_id, U256::from(2))
+            .call()
+            .await
+            .unwrap();
+        let yes = yes_position(&conditional_tokens, wcol, condition_id).await;
+
+        ctf.split_position_neg_risk(condition_id, amount)
+            .await
+            .unwrap();
+        assert_eq!(ctf.balance_of(wallet, yes).await.unwrap(), amount);
+
+        ctf.merge_positions_neg_risk(condition_id, U256::from(4_000_000u64))
+            .await
+            .unwrap();
+        assert_eq!(
+            ctf.balance_of(wallet, yes).await.unwrap(),
+            U256::from(6_000_000u64)
+        );
+
+        // Resolve YES as the adapter would
+        let impersonated = ProviderBuilder::new().connect_http(node.url.parse().unwrap());
+        impersonated
+            .anvil_impersonate_account(adapter)
+            .await
+            .unwrap();
+        impersonated
+            .anvil_set_balance(adapter, U256::from(10).pow(U256::from(18)))
+            .await
+            .unwrap();
+        confirm(
+            IConditionalTokens::new(contracts.ctf_contract, &impersonated)
+                .reportPayouts(question_id, vec![U256::from(1), U256::ZERO])
+                .from(adapter)
+                .send()
+                .await
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+        let before = usdc.balanceOf(wallet).call().await.unwrap();
+        ctf.redeem_positions_neg_risk(
+            condition_id,
+            [U256::from(6_000_000u64), U256::from(6_000_000u64)],
+        )
+        .await
+        .unwrap();
+        let after = usdc.balanceOf(wallet).call().await.unwrap();
+
+        assert_eq!(ctf.balance_of(wallet, yes).await.unwrap(), U256::ZERO);
+        assert_eq!(after - before, U256::from(6_000_000u64));
+    }
+
+    #[tokio::test]
+    async fn test_neg_risk_needs_adapter() {
+        let provider = ProviderBuilder::new().connect_http("http://127.0.0.1:1".parse().unwrap());
+        let ctf = CtfClient::new(provider, &Chains::Polygon.config().standard_config);
+
+        let result = ctf.split_position_neg_risk(B256::ZERO, U256::from(1)).await;
+
+        assert!(matches!(result, Err(ApiError::Contract(_))));
+    }
+}
@@ -0,0 +1,388 @@
+//! Offline derivation of the ids used by the Conditional Tokens contract.
+//!
+//! A condition is identified by its oracle, question id and number of
+//! outcomes. A collection of outcomes is a point on the alt_bn128 curve,
+//! compressed into 32 bytes, so that nested collections can be combined by
+//! point addition in any order. The ERC-1155 id of a position, which is the
+//! CLOB token id, hashes the collateral with the collection id.
+//!
+//! Neg-risk markets use the wrapped collateral of the neg-risk adapter
+//! (`INegRiskAdapter::wcol`) instead of USDC.
+
+use alloy::primitives::{Address, B256, U256, keccak256};
+
+use crate::onchain::ctf::BINARY_PARTITION;
+use crate::shared::ApiError;
+
+/// The field modulus of alt_bn128
+const P: U256 = U256::from_limbs([
+    0x3c208c16d87cfd47,
+    0x97816a916871ca8d,
+    0xb85045b68181585d,
+    0x30644e72e131a029,
+]);
+
+/// The curve is `y^2 = x^3 + 3`
+const B: U256 = U256::from_limbs([3, 0, 0, 0]);
+
+/// A point of alt_bn128 in affine coordinates, `(0, 0)` for the point at infinity
+type Point = (U256, U256);
+
+/// `keccak256(oracle ++ question_id ++ outcome_slot_count)`, as `getConditionId`
+pub fn condition_id(oracle: Address, question_id: B256, outcome_slot_count: u32) -> B256 {
+    let mut packed = Vec::with_capacity(20 + 32 + 32);
+    packed.extend_from_slice(oracle.as_slice());
+    packed.extend_from_slice(question_id.as_slice());
+    packed.extend_from_slice(&U256::from(outcome_slot_count).to_be_bytes::<32>());
+    keccak256(packed)
+}
+
+/// The id of the collection of the outcomes in `index_set`, nested in
+/// `parent_collection_id`, as `getCollectionId`
+/// # Arguments
+/// * `parent_collection_id` - The collection to nest in, [`B256::ZERO`] for a top level position
+/// * `condition_id` - The condition the outcomes belong to
+/// * `index_set` - A bit per outcome, e.g. `1` for the first outcome and `2` for the second
+/// # Returns
+/// * `Result<B256, ApiError>` - The collection id, or an [`ApiError`] if the parent is not a valid collection id
+pub fn collection_id(
+    parent_collection_id: B256,
+    condition_id: B256,
+    index_set: U256,
+) -> Result<B256, ApiError> {
+    let mut packed = [0u8; 64];
+    packed[..32].copy_from_slice(condition_id.as_slice());
+    packed[32..].copy_from_slice(&index_set.to_be_bytes::<32>());
+    let mut x = U256::from_be_bytes(keccak256(packed).0);
+    let odd = x.bit(255);
+
+    // Increment x until x^3 + 3 has a square root
+    let (x, mut y) = loop {
+        x = x.add_mod(U256::from(1), P);
+        let yy = curve(x);
+        let y = sqrt(yy);
+        if y.mul_mod(y, P) == yy {
+            break (x, y);
+        }
+    };
+    if odd != y.bit(0) {
+        y = P - y;
+    }
+
+    let mut point = (x, y);
+    if !parent_collection_id.is_zero() {
+        point = add(point, decompress(parent_collection_id)?);
+    }
+    Ok(compress(point))
+}
+
+/// The ERC-1155 id of a position, as `getPositionId`. This is the CLOB token id
+pub fn position_id(collateral: Address, collection_id: B256) -> U256 {
+    let mut packed = Vec::with_capacity(20 + 32);
+    packed.extend_from_slice(collateral.as_slice());
+    packed.extend_from_slice(collection_id.as_slice());
+    U256::from_be_bytes(keccak256(packed).0)
+}
+
+/// The YES and NO token ids of a binary market
+/// # Arguments
+/// * `collateral` - USDC, or the wrapped collateral for neg-risk markets
+/// * `condition_id` - The condition id of the market
+pub fn binary_position_ids(collateral: Address, condition_id: B256) -> Result<[U256; 2], ApiError> {
+    let [yes, no] = BINARY_PARTITION.map(|index_set| {
+        collection_id(B256::ZERO, condition_id, U256::from(index_set))
+            .map(|collection| position_id(collateral, collection))
+    });
+    Ok([yes?, no?])
+}
+
+fn curve(x: U256) -> U256 {
+    x.mul_mod(x, P).mul_mod(x, P).add_mod(B, P)
+}
+
+/// A square root modulo `P`, valid since `P = 3 mod 4`. Only meaningful if
+/// `value` is a quadratic residue, which the caller checks
+fn sqrt(value: U256) -> U256 {
+    value.pow_mod((P + U256::from(1)) >> 2, P)
+}
+
+fn sub(a: U256, b: U256) -> U256 {
+    a.add_mod(P - b, P)
+}
+
+fn div(a: U256, b: U256) -> U256 {
+    a.mul_mod(b.inv_mod(P).expect("P is prime"), P)
+}
+
+/// Point addition, as the `ecAdd` precompile
+fn add(a: Point, b: Point) -> Point {
+    let zero = U256::ZERO;
+    if a == (zero, zero) {
+        return b;
+    }
+    if b == (zero, zero) {
+        return a;
+    }
+    let slope = if a.0 == b.0 {
+        if a.1 != b.1 || a.1.is_zero() {
+            return (zero, zero);
+        }
+        div(
+            U256::from(3).mul_mod(a.0.mul_mod(a.0, P), P),
+            a.1.add_mod(a.1, P),
+        )
+    } else {
+        div(sub(b.1, a.1), sub(b.0, a.0))
+    };
+    let x = sub(sub(slope.mul_mod(slope, P), a.0), b.0);
+    let y = sub(slope.mul_mod(sub(a.0, x), P), a.1);
+    (x, y)
+}
+
+/// Store the parity of `y` in bit 254 of `x`
+fn compress((x, y): Point) -> B256 {
+    let mut x = x;
+    if y.bit(0) {
+        x ^= U256::from(1) << 254;
+    }
+    B256::from(x)
+}
+
+fn decompress(collection_id: B256) -> Result<Point, ApiError> {
+    let value = U256::from_be_bytes(collection_id.0);
+    let odd = value.bit(254);
+    let x = (value << 2) >> 2;
+    let yy = curve(x);
+    let mut y = sqrt(yy);
+    if odd != y.bit(0) {
+        y = P - y;
+    }
+    if y.mul_mod(y, P) != yy {
+        return Err(ApiError::Decode(format!(
+            "{collection_id} is not a valid collection id"
+        )));
+    }
+    Ok((x, y))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clob_client::config::Chains;
+    use crate::public::PubClient;
+    use crate::public::clob_markets::{ClobMarkets, models::ClobMarketsDTO};
+    use alloy::primitives::{address, b256};
+
+    const ORACLE: Address = address!("0x6A9D222616C90FcA5754cd1333cFD9b7fb6a4F74");
+
+    /// `INegRiskAdapter::wcol` of the Polygon neg-risk adapter
+    const POLYGON_WCOL: Address = address!("0x3A3BD7bb9528E159577F7C2e685CC81A765002E2");
+
+    /// A `(condition_id, collateral, [yes, no])` market as listed by the CLOB
+    type Vector = (&'static str, Address, [&'static str; 2]);
+
+    /// Real markets with their condition id and `clob_token_ids`
+    fn market_vectors() -> [Vector; 2] {
+        [
+            // A standard binary market of the CLOB's Amoy deployment, with USDC
+            (
+                "0x5f65177b394277fd294cd75650044e32ba009a95022d88a0c1d565897d72f8f1",
+                Chains::Amoy.config().standard_config.collateral_contract,
+                [
+                    "71321045679252212594626385532706912750332728571942532289631379312455583992563",
+                    "52114319501245915516055106046884209969926127482827954674443846427813813222426",
+                ],
+            ),
+            // "Will Donald Trump win the 2024 US Presidential Election?", a
+            // neg-risk market on Polygon backed by the adapter's wrapped collateral
+            (
+                "0xdd22472e552920b8438158ea7238bfadfa4f736aa4cee91a6b86c39ead110917",
+                POLYGON_WCOL,
+                [
+                    "21742633143463906290569050155826241533067272736897614950488156847949938836455",
+                    "48331043336612883890938759509493159234755048973500640148014422747788308965732",
+                ],
+            ),
+        ]
+    }
+
+    #[test]
+    fn test_field_constants() {
+        assert_eq!(
+            P.to_string(),
+            "21888242871839275222246405745257275088696311157297823662689037894645226208583"
+        );
+    }
+
+    #[test]
+    fn test_condition_id_hashes_packed_fields() {
+        let question_id =
+            b256!("0x0000000000000000000000000000000000000000000000000000000000000001");
+
+        let mut packed = ORACLE.to_vec();
+        packed.extend_from_slice(question_id.as_slice());
+        packed.extend_from_slice(&[0u8; 31]);
+        packed.push(2);
+        assert_eq!(condition_id(ORACLE, question_id, 2), keccak256(packed));
+        assert_ne!(
+            condition_id(ORACLE, question_id, 2),
+            condition_id(ORACLE, question_id, 3)
+        );
+    }
+
+    #[test]
+    fn test_collection_id_is_a_curve_point() {
+        let condition = condition_id(ORACLE, B256::ZERO, 2);
+
+        for index_set in [1u8, 2, 3] {
+            let collection = collection_id(B256::ZERO, condition, U256::from(index_set)).unwrap();
+            let (x, y) = decompress(collection).unwrap();
+            assert_eq!(y.mul_mod(y, P), curve(x));
+            assert_eq!(compress((x, y)), collection);
+        }
+    }
+
+    #[test]
+    fn test_nested_collections_commute() {
+        let first = condition_id(ORACLE, B256::ZERO, 2);
+        let second = condition_id(ORACLE, B256::repeat_byte(1), 3);
+
+        let a = collection_id(B256::ZERO, first, U256::from(1)).unwrap();
+        let b = collection_id(B256::ZERO, second, U256::from(4)).unwrap();
+        let a_then_b = collection_id(a, second, U256::from(4)).unwrap();
+        let b_then_a = collection_id(b, first, U256::from(1)).unwrap();
+
+        assert_eq!(a_then_b, b_then_a);
+        assert_ne!(a_then_b, a);
+    }
+
+    #[test]
+    fn test_point_doubling() {
+        let condition = condition_id(ORACLE, B256::ZERO, 2);
+        let collection = collection_id(B256::ZERO, condition, U256::from(1)).unwrap();
+        let point = decompress(collection).unwrap();
+
+        let doubled = add(point, point);
+        assert_eq!(doubled.1.mul_mod(doubled.1, P), curve(doubled.0));
+        // Adding a point and its negation gives the point at infinity
+        assert_eq!(add(point, (point.0, P - point.1)), (U256::ZERO, U256::ZERO));
+    }
+
+    #[test]
+    fn test_invalid_parent_collection() {
+        let not_on_curve = (1u64..)
+            .map(U256::from)
+            .find(|x| {
+                let y = sqrt(curve(*x));
+                y.mul_mod(y, P) != curve(*x)
+            })
+            .unwrap();
+
+        let result = collection_id(B256::from(not_on_curve), B256::ZERO, U256::from(1));
+
+        assert!(matches!(result, Err(ApiError::Decode(_))));
+    }
+
+    #[test]
+    fn test_binary_position_ids() {
+        let collateral = Chains::Polygon.config().standard_config.collateral_contract;
+        let condition = condition_id(ORACLE, B256::ZERO, 2);
+
+        let [yes, no] = binary_position_ids(collateral, condition).unwrap();
+
+        let yes_collection = collection_id(B256::ZERO, condition, U256::from(1)).unwrap();
+        assert_eq!(yes, position_id(collateral, yes_collection));
+        assert_ne!(yes, no);
+    }
+
+    #[test]
+    fn test_token_ids_of_known_markets() {
+        for (condition, collateral, [yes, no]) in market_vectors() {
+            let ids = binary_position_ids(collateral, condition.parse().unwrap()).unwrap();
+
+            assert_eq!(ids[0].to_string(), yes, "YES of {condition}");
+            assert_eq!(ids[1].to_string(), no, "NO of {condition}");
+        }
+    }
+
+    #[tokio::test]
+    #[ignore = "needs network access to the CLOB"]
+    async fn test_token_ids_of_live_markets() {
+        let client = PubClient::new();
+        let collateral = Chains::Polygon.config().standard_config.collateral_contract;
+
+        let page = client
+            .get_sampling_markets(ClobMarketsDTO::default())
+            .await
+            .unwrap();
+
+        let markets: Vec<_> = page
+            .data
+            .iter()
+            .filter(|market| !market.neg_risk && market.tokens.len() == 2)
+            .collect();
+        assert!(!markets.is_empty());
+        for market in markets {
+            let condition: B256 = market.condition_id.parse().unwrap();
+            let expected: Vec<String> = binary_position_ids(collateral, condition)
+                .unwrap()
+                .iter()
+                .map(U256::to_string)
+                .collect();
+            let token_ids: Vec<String> = market
+                .tokens
+                .iter()
+                .map(|token| token.token_id.clone())
+                .collect();
+            assert_eq!(token_ids, expected, "{}", market.condition_id);
+        }
+    }
+
+    #[tokio::test]
+    #[ignore = "needs an anvil node forking Polygon"]
+    async fn test_ids_match_the_contract() {
+        use crate::onchain::contracts::IConditionalTokens;
+        use alloy::providers::ProviderBuilder;
+
+        let url = std::env::var("ANVIL_RPC_URL").unwrap_or("http://127.0.0.1:8545".to_string());
+        let provider = ProviderBuilder::new().connect_http(url.parse().unwrap());
+        let contracts = Chains::Polygon.config().standard_config;
+        let ctf = IConditionalTokens::new(contracts.ctf_contract, &provider);
+
+        for seed in 0u8..8 {
+            let question_id = keccak256([seed]);
+            let condition = condition_id(ORACLE, question_id, 2);
+            assert_eq!(
+                ctf.getConditionId(ORACLE, question_id, U256::from(2))
+                    .call()
+                    .await
+                    .unwrap(),
+                condition
+            );
+
+            let top = collection_id(B256::ZERO, condition, U256::from(1)).unwrap();
+            assert_eq!(
+                ctf.getCollectionId(B256::ZERO, condition, U256::from(1))
+                    .call()
+                    .await
+                    .unwrap(),
+                top
+            );
+            let nested = collection_id(top, keccak256([seed, 1]), U256::from(2)).unwrap();
+            assert_eq!(
+                ctf.getCollectionId(top, keccak256([seed, 1]), U256::from(2))
+                    .call()
+                    .await
+                    .unwrap(),
+                nested
+            );
+            assert_eq!(
+                ctf.getPositionId(contracts.collateral_contract, top)
+                    .call()
+                    .await
+                    .unwrap(),
+                position_id(contracts.collateral_contract, top)
+            );
+        }
+    }
+}
@@ -0,0 +1,17 @@
+//! On-chain operations on the Polymarket contracts, through an alloy
+//! [`Provider`](alloy::providers::Provider) with a wallet.
+//!
+//! # Key Types
+//!
+//! - [`CtfClient`]: Split, merge and redeem outcome tokens.
+//! - [`ensure_approvals`]: Grant the approvals a wallet needs to trade.
+//! - [`check_approvals`]: Read them for any wallet without sending anything.
+//! - [`ids`]: Derive condition, collection and position ids without a node.
+
+pub mod approvals;
+pub mod contracts;
+pub mod ctf;
+pub mod ids;
+
+pub use approvals::{ApprovalReport, check_approvals, ensure_approvals};
+pub use ctf::CtfClient;
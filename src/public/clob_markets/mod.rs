@@ -0,0 +1,328 @@
+pub mod models;
+
+use async_trait::async_trait;
+use futures::stream::BoxStream;
+use models::{ClobMarket, ClobMarketsDTO, ClobMarketsPage, SimplifiedMarket};
+use serde::de::DeserializeOwned;
+
+use crate::shared::{ApiError, PageConfig, QueryParams, client::AsyncHttpClient, pagination};
+
+/// Get one page of a CLOB market catalog endpoint
+async fn get_page<T: DeserializeOwned>(
+    client: &AsyncHttpClient,
+    path: &str,
+    data: &ClobMarketsDTO,
+) -> Result<ClobMarketsPage<T>, ApiError> {
+    let query = data.as_query_params();
+    let response = client.get(Some(path), Some(query), None).await?;
+    let page: ClobMarketsPage<T> = response.json().await?;
+    Ok(page)
+}
+
+/// Stream every item of a CLOB market catalog endpoint
+fn stream_pages<'a, T: DeserializeOwned + Send + 'a>(
+    client: &'a AsyncHttpClient,
+    path: &'a str,
+    data: ClobMarketsDTO,
+    config: PageConfig,
+) -> BoxStream<'a, Result<T, ApiError>> {
+    pagination::paginate_cursor(config, data.next_cursor, move |next_cursor| async move {
+        let page: ClobMarketsPage<T> =
+            get_page(client, path, &ClobMarketsDTO { next_cursor }).await?;
+        Ok((page.data, page.next_cursor))
+    })
+}
+
+/// The market catalog of the CLOB. Unlike the Gamma [`Markets`](crate::public::markets::Markets),
+/// it only lists markets with an order book and pages with `next_cursor`
+#[async_trait]
+pub trait ClobMarkets {
+    fn get_clob_client(&self) -> &AsyncHttpClient;
+
+    /// Get a page of the markets of the CLOB
+    /// # Arguments
+    /// * `data` - The cursor of the page. See [`ClobMarketsDTO`] for available options.
+    /// # Returns
+    /// * `Result<ClobMarketsPage<[`ClobMarket`]>, [`ApiError`]>` - The markets and the cursor of the next page, or an [`ApiError`] if the request fails
+    async fn get_clob_markets(
+        &self,
+        data: ClobMarketsDTO,
+    ) -> Result<ClobMarketsPage<ClobMarket>, ApiError> {
+        get_page(self.get_clob_client(), "/markets", &data).await
+    }
+
+    /// Stream all the markets of the CLOB, one page at a time
+    /// # Arguments
+    /// * `data` - The cursor of the first page. See [`ClobMarketsDTO`] for available options.
+    /// * `config` - The item cap. See [`PageConfig`]
+    /// # Returns
+    /// * `BoxStream<Result<[`ClobMarket`], [`ApiError`]>>` - The markets, ending after the first [`ApiError`]
+    fn stream_clob_markets(
+        &self,
+        data: ClobMarketsDTO,
+        config: PageConfig,
+    ) -> BoxStream<'_, Result<ClobMarket, ApiError>> {
+        stream_pages(self.get_clob_client(), "/markets", data, config)
+    }
+
+    /// Get all the markets of the CLOB
+    /// # Returns
+    /// * `Result<Vec<[`ClobMarket`]>, [`ApiError`]>` - All the markets, or an [`ApiError`] if a request fails
+    async fn collect_all_clob_markets(&self) -> Result<Vec<ClobMarket>, ApiError> {
+        pagination::collect_all(
+            self.stream_clob_markets(ClobMarketsDTO::default(), PageConfig::default()),
+        )
+        .await
+    }
+
+    /// Get a market of the CLOB by condition id
+    /// # Arguments
+    /// * `condition_id` - The condition id of the market
+    /// # Returns
+    /// * `Result<[`ClobMarket`], [`ApiError`]>` - The market, or an [`ApiError`] if the request fails
+    async fn get_clob_market(&self, condition_id: String) -> Result<ClobMarket, ApiError> {
+        let path = format!("{}{}", "/markets/", condition_id);
+        let client = self.get_clob_client();
+        let response = client.get(Some(path.as_str()), None, None).await?;
+        let market: ClobMarket = response.json().await?;
+        Ok(market)
+    }
+
+    /// Get a page of the markets of the CLOB in their simplified form
+    /// # Arguments
+    /// * `data` - The cursor of the page. See [`ClobMarketsDTO`] for available options.
+    /// # Returns
+    /// * `Result<ClobMarketsPage<[`SimplifiedMarket`]>, [`ApiError`]>` - The markets and the cursor of the next page, or an [`ApiError`] if the request fails
+    async fn get_simplified_markets(
+        &self,
+        data: ClobMarketsDTO,
+    ) -> Result<ClobMarketsPage<SimplifiedMarket>, ApiError> {
+        get_page(self.get_clob_client(), "/simplified-markets", &data).await
+    }
+
+    /// Stream all the markets of the CLOB in their simplified form, one page at a time
+    /// # Arguments
+    /// * `data` - The cursor of the first page. See [`ClobMarketsDTO`] for available options.
+    /// * `config` - The item cap. See [`PageConfig`]
+    /// # Returns
+    /// * `BoxStream<Result<[`SimplifiedMarket`], [`ApiError`]>>` - The markets, ending after the first [`ApiError`]
+    fn stream_simplified_markets(
+        &self,
+        data: ClobMarketsDTO,
+        config: PageConfig,
+    ) -> BoxStream<'_, Result<SimplifiedMarket, ApiError>> {
+        stream_pages(self.get_clob_client(), "/simplified-markets", data, config)
+    }
+
+    /// Get all the markets of the CLOB in their simplified form
+    /// # Returns
+    /// * `Result<Vec<[`SimplifiedMarket`]>, [`ApiError`]>` - All the markets, or an [`ApiError`] if a request fails
+    async fn collect_all_simplified_markets(&self) -> Result<Vec<SimplifiedMarket>, ApiError> {
+        pagination::collect_all(
+            self.stream_simplified_markets(ClobMarketsDTO::default(), PageConfig::default()),
+        )
+        .await
+    }
+
+    /// Get a page of the markets with liquidity rewards
+    /// # Arguments
+    /// * `data` - The cursor of the page. See [`ClobMarketsDTO`] for available options.
+    /// # Returns
+    /// * `Result<ClobMarketsPage<[`ClobMarket`]>, [`ApiError`]>` - The markets and the cursor of the next page, or an [`ApiError`] if the request fails
+    async fn get_sampling_markets(
+        &self,
+        data: ClobMarketsDTO,
+    ) -> Result<ClobMarketsPage<ClobMarket>, ApiError> {
+        get_page(self.get_clob_client(), "/sampling-markets", &data).await
+    }
+
+    /// Stream all the markets with liquidity rewards, one page at a time
+    /// # Arguments
+    /// * `data` - The cursor of the first page. See [`ClobMarketsDTO`] for available options.
+    /// * `config` - The item cap. See [`PageConfig`]
+    /// # Returns
+    /// * `BoxStream<Result<[`ClobMarket`], [`ApiError`]>>` - The markets, ending after the first [`ApiError`]
+    fn stream_sampling_markets(
+        &self,
+        data: ClobMarketsDTO,
+        config: PageConfig,
+    ) -> BoxStream<'_, Result<ClobMarket, ApiError>> {
+        stream_pages(self.get_clob_client(), "/sampling-markets", data, config)
+    }
+
+    /// Get all the markets with liquidity rewards
+    /// # Returns
+    /// * `Result<Vec<[`ClobMarket`]>, [`ApiError`]>` - All the markets, or an [`ApiError`] if a request fails
+    async fn collect_all_sampling_markets(&self) -> Result<Vec<ClobMarket>, ApiError> {
+        pagination::collect_all(
+            self.stream_sampling_markets(ClobMarketsDTO::default(), PageConfig::default()),
+        )
+        .await
+    }
+
+    /// Get a page of the markets with liquidity rewards in their simplified form
+    /// # Arguments
+    /// * `data` - The cursor of the page. See [`ClobMarketsDTO`] for available options.
+    /// # Returns
+    /// * `Result<ClobMarketsPage<[`SimplifiedMarket`]>, [`ApiError`]>` - The markets and the cursor of the next page, or an [`ApiError`] if the request fails
+    async fn get_sampling_simplified_markets(
+        &self,
+        data: ClobMarketsDTO,
+    ) -> Result<ClobMarketsPage<SimplifiedMarket>, ApiError> {
+        get_page(
+            self.get_clob_client(),
+            "/sampling-simplified-markets",
+            &data,
+        )
+        .await
+    }
+
+    /// Stream all the markets with liquidity rewards in their simplified form, one page at a time
+    /// # Arguments
+    /// * `data` - The cursor of the first page. See [`ClobMarketsDTO`] for available options.
+    /// * `config` - The item cap. See [`PageConfig`]
+    /// # Returns
+    /// * `BoxStream<Result<[`SimplifiedMarket`], [`ApiError`]>>` - The markets, ending after the first [`ApiError`]
+    fn stream_sampling_simplified_markets(
+        &self,
+        data: ClobMarketsDTO,
+        config: PageConfig,
+    ) -> BoxStream<'_, Result<SimplifiedMarket, ApiError>> {
+        stream_pages(
+            self.get_clob_client(),
+            "/sampling-simplified-markets",
+            data,
+            config,
+        )
+    }
+
+    /// Get all the markets with liquidity rewards in their simplified form
+    /// # Returns
+    /// * `Result<Vec<[`SimplifiedMarket`]>, [`ApiError`]>` - All the markets, or an [`ApiError`] if a request fails
+    async fn collect_all_sampling_simplified_markets(
+        &self,
+    ) -> Result<Vec<SimplifiedMarket>, ApiError> {
+        pagination::collect_all(
+            self.stream_sampling_simplified_markets(
+                ClobMarketsDTO::default(),
+                PageConfig::default(),
+            ),
+        )
+        .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::public::PubClient;
+    use rust_decimal::dec;
+
+    const MARKET: &str = r#"{
+        "enable_order_book": true,
+        "active": true,
+        "closed": false,
+        "archived": false,
+        "accepting_orders": true,
+        "accepting_order_timestamp": "2024-09-10T19:02:33Z",
+        "minimum_order_size": 5,
+        "minimum_tick_size": 0.001,
+        "condition_id": "0xaf9d0e448129a9f657f851d49495ba4742055d80e0ef1166ba0ee81d4d594214",
+        "question_id": "0x7bd2e6a1c4d8f1a8f1d0b8c55b1f07fa9b1be3f8ae06a4b6d50b4a9b7cbd0d00",
+        "question": "Will it rain tomorrow?",
+        "description": "",
+        "market_slug": "will-it-rain-tomorrow",
+        "end_date_iso": "2025-01-01T00:00:00Z",
+        "game_start_time": null,
+        "seconds_delay": 0,
+        "fpmm": "",
+        "maker_base_fee": 0,
+        "taker_base_fee": 0,
+        "notifications_enabled": true,
+        "neg_risk": true,
+        "neg_risk_market_id": "0xd2a5c4b1e02b7f7c1d1bc4e3f8bd6f9e2d6b0b1e3a5c7d9f1e3a5c7d9f1e3a00",
+        "neg_risk_request_id": "",
+        "icon": "https://example.com/icon.png",
+        "image": "https://example.com/image.png",
+        "rewards": {
+            "rates": [{"asset_address": "0x2791Bca1f2de4661ED88A30C99A7a9449Aa84174", "rewards_daily_rate": 25}],
+            "min_size": 50,
+            "max_spread": 3.5
+        },
+        "is_50_50_outcome": false,
+        "tokens": [
+            {"token_id": "111", "outcome": "Yes", "price": 0.535, "winner": false},
+            {"token_id": "222", "outcome": "No", "price": 0.465, "winner": false}
+        ],
+        "tags": null
+    }"#;
+
+    #[test]
+    fn test_deserialize_clob_market() {
+        let market: ClobMarket = serde_json::from_str(MARKET).unwrap();
+
+        assert!(market.is_tradeable());
+        assert_eq!(market.minimum_tick_size, Some(dec!(0.001)));
+        assert_eq!(market.token("yes").unwrap().price, dec!(0.535));
+        assert_eq!(market.token_ids()[1].token_id, "222");
+        assert_eq!(market.rewards.max_spread, dec!(3.5));
+        assert_eq!(market.rewards.rates[0].rewards_daily_rate, dec!(25));
+        assert!(market.neg_risk_market_id.is_some());
+        assert_eq!(market.neg_risk_request_id, None);
+        assert_eq!(market.game_start_time, None);
+        assert_eq!(market.end_date_iso.unwrap().timestamp(), 1735689600);
+        assert!(market.tags.is_empty());
+    }
+
+    #[test]
+    fn test_deserialize_simplified_page() {
+        let page: ClobMarketsPage<SimplifiedMarket> = serde_json::from_str(
+            r#"{
+                "limit": 1000,
+                "count": 1,
+                "next_cursor": "LTE=",
+                "data": [{
+                    "condition_id": "0x1",
+                    "rewards": {"rates": null, "min_size": 0, "max_spread": 0},
+                    "tokens": [{"token_id": "1", "outcome": "Yes", "price": 1, "winner": true}],
+                    "active": true,
+                    "closed": true,
+                    "archived": false,
+                    "accepting_orders": false
+                }]
+            }"#,
+        )
+        .unwrap();
+
+        assert_eq!(page.next_cursor.as_deref(), Some("LTE="));
+        assert!(!page.data[0].is_tradeable());
+        assert!(page.data[0].tokens[0].winner);
+        assert!(page.data[0].rewards.rates.is_empty());
+    }
+
+    #[test]
+    fn test_clob_markets_query() {
+        assert!(ClobMarketsDTO::default().as_query_params().is_empty());
+        assert_eq!(
+            ClobMarketsDTO {
+                next_cursor: Some(String::from("MTAwMA==")),
+            }
+            .as_query_params(),
+            vec![(String::from("next_cursor"), String::from("MTAwMA=="))]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_get_sampling_simplified_markets() {
+        let client = PubClient::new();
+
+        let markets = client
+            .get_sampling_simplified_markets(ClobMarketsDTO::default())
+            .await;
+
+        if let Err(e) = &markets {
+            eprintln!("Sampling simplified markets error: {:?}", e);
+        }
+        assert!(markets.is_ok());
+    }
+}
@@ -0,0 +1,158 @@
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use serde_with::{DefaultOnNull, NoneAsEmptyString, serde_as};
+
+use crate::shared::{QueryParams, TokenId};
+
+/// Query parameters for the CLOB market catalog endpoints
+/// # Fields
+/// * `next_cursor` - The cursor of the page, `None` for the first page
+#[derive(Debug, Clone, Serialize, Deserialize, Default, QueryParams)]
+pub struct ClobMarketsDTO {
+    pub next_cursor: Option<String>,
+}
+
+/// One page of a CLOB market catalog endpoint
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ClobMarketsPage<T> {
+    pub limit: Option<u32>,
+    pub count: Option<u32>,
+    pub next_cursor: Option<String>,
+    pub data: Vec<T>,
+}
+
+/// An outcome token of a CLOB market
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ClobToken {
+    pub token_id: String,
+    pub outcome: String,
+    #[serde(default)]
+    pub price: Decimal,
+    #[serde(default)]
+    pub winner: bool,
+}
+
+/// The daily rewards paid in one asset
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RewardRate {
+    pub asset_address: String,
+    pub rewards_daily_rate: Decimal,
+}
+
+/// The liquidity rewards config of a CLOB market. Orders earn rewards when
+/// they are at least `min_size` and within `max_spread` cents of the midpoint
+#[serde_as]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
+pub struct ClobRewards {
+    #[serde_as(as = "DefaultOnNull")]
+    #[serde(default)]
+    pub rates: Vec<RewardRate>,
+    #[serde(default)]
+    pub min_size: Decimal,
+    #[serde(default)]
+    pub max_spread: Decimal,
+}
+
+/// A market as listed by the CLOB `/markets` endpoints
+#[serde_as]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ClobMarket {
+    pub condition_id: String,
+    pub question_id: Option<String>,
+    pub question: Option<String>,
+    pub description: Option<String>,
+    pub market_slug: Option<String>,
+    pub tokens: Vec<ClobToken>,
+    #[serde_as(as = "DefaultOnNull")]
+    #[serde(default)]
+    pub rewards: ClobRewards,
+    #[serde(default)]
+    pub active: bool,
+    #[serde(default)]
+    pub closed: bool,
+    #[serde(default)]
+    pub archived: bool,
+    #[serde(default)]
+    pub accepting_orders: bool,
+    #[serde_as(as = "NoneAsEmptyString")]
+    #[serde(default)]
+    pub accepting_order_timestamp: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub enable_order_book: bool,
+    pub minimum_order_size: Option<Decimal>,
+    pub minimum_tick_size: Option<Decimal>,
+    pub maker_base_fee: Option<Decimal>,
+    pub taker_base_fee: Option<Decimal>,
+    #[serde_as(as = "NoneAsEmptyString")]
+    #[serde(default)]
+    pub end_date_iso: Option<DateTime<Utc>>,
+    #[serde_as(as = "NoneAsEmptyString")]
+    #[serde(default)]
+    pub game_start_time: Option<DateTime<Utc>>,
+    pub seconds_delay: Option<u32>,
+    pub fpmm: Option<String>,
+    #[serde(default)]
+    pub neg_risk: bool,
+    #[serde_as(as = "NoneAsEmptyString")]
+    #[serde(default)]
+    pub neg_risk_market_id: Option<String>,
+    #[serde_as(as = "NoneAsEmptyString")]
+    #[serde(default)]
+    pub neg_risk_request_id: Option<String>,
+    pub icon: Option<String>,
+    pub image: Option<String>,
+    #[serde(default)]
+    pub is_50_50_outcome: bool,
+    #[serde_as(as = "DefaultOnNull")]
+    #[serde(default)]
+    pub tags: Vec<String>,
+}
+
+/// A market as listed by the CLOB `/simplified-markets` endpoints
+#[serde_as]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SimplifiedMarket {
+    pub condition_id: String,
+    pub tokens: Vec<ClobToken>,
+    #[serde_as(as = "DefaultOnNull")]
+    #[serde(default)]
+    pub rewards: ClobRewards,
+    #[serde(default)]
+    pub active: bool,
+    #[serde(default)]
+    pub closed: bool,
+    #[serde(default)]
+    pub archived: bool,
+    #[serde(default)]
+    pub accepting_orders: bool,
+}
+
+impl ClobMarket {
+    /// Whether orders can be placed on the market right now
+    pub fn is_tradeable(&self) -> bool {
+        self.active && !self.closed && !self.archived && self.accepting_orders
+    }
+
+    /// The token ids of the outcomes, in the order of `tokens`
+    pub fn token_ids(&self) -> Vec<TokenId> {
+        self.tokens
+            .iter()
+            .map(|token| TokenId::from(token.token_id.clone()))
+            .collect()
+    }
+
+    /// The token of an outcome, matched case-insensitively
+    pub fn token(&self, outcome: &str) -> Option<&ClobToken> {
+        self.tokens
+            .iter()
+            .find(|token| token.outcome.eq_ignore_ascii_case(outcome))
+    }
+}
+
+impl SimplifiedMarket {
+    /// Whether orders can be placed on the market right now
+    pub fn is_tradeable(&self) -> bool {
+        self.active && !self.closed && !self.archived && self.accepting_orders
+    }
+}
@@ -0,0 +1,142 @@
+pub mod models;
+
+use async_trait::async_trait;
+use models::{Comment, CommentIdDTO, CommentsDTO, UserCommentsDTO};
+
+use crate::shared::{ApiError, QueryParams, client::AsyncHttpClient};
+
+#[async_trait]
+pub trait Comments {
+    fn get_gamma_client(&self) -> &AsyncHttpClient;
+
+    /// Get the comments of an event, series or market
+    /// # Arguments
+    /// * `data` - The entity and the query parameters. See [`CommentsDTO`] for available options.
+    /// # Returns
+    /// * `Result<Vec<[`Comment`]>, [`ApiError`]>` - A vector of [`Comment`] objects, or an [`ApiError`] if the request fails
+    async fn get_comments(&self, data: CommentsDTO) -> Result<Vec<Comment>, ApiError> {
+        let client = self.get_gamma_client();
+        let query = data.as_query_params();
+        let response = client.get(Some("/comments"), Some(query), None).await?;
+        let comments: Vec<Comment> = response.json().await?;
+        Ok(comments)
+    }
+
+    /// Get a comment by id
+    /// # Arguments
+    /// * `id` - The id of the comment
+    /// * `data` - The query parameters for the comment. See [`CommentIdDTO`] for available options.
+    /// # Returns
+    /// * `Result<[`Comment`], [`ApiError`]>` - The [`Comment`] object, or an [`ApiError`] if the request fails
+    async fn get_comment(&self, id: String, data: CommentIdDTO) -> Result<Comment, ApiError> {
+        let path = format!("{}{}", "/comments/", id);
+        let client = self.get_gamma_client();
+        let query = data.as_query_params();
+        let response = client.get(Some(path.as_str()), Some(query), None).await?;
+        // The comment is returned in a list
+        let comments: Vec<Comment> = response.json().await?;
+        comments
+            .into_iter()
+            .next()
+            .ok_or_else(|| ApiError::Unexpected(format!("No comment with id {id}")))
+    }
+
+    /// Get the comments posted by a user
+    /// # Arguments
+    /// * `user_address` - The wallet address of the user
+    /// * `data` - The query parameters. See [`UserCommentsDTO`] for available options.
+    /// # Returns
+    /// * `Result<Vec<[`Comment`]>, [`ApiError`]>` - A vector of [`Comment`] objects, or an [`ApiError`] if the request fails
+    async fn get_user_comments(
+        &self,
+        user_address: String,
+        data: UserCommentsDTO,
+    ) -> Result<Vec<Comment>, ApiError> {
+        let path = format!("{}{}", "/comments/user_address/", user_address);
+        let client = self.get_gamma_client();
+        let query = data.as_query_params();
+        let response = client.get(Some(path.as_str()), Some(query), None).await?;
+        let comments: Vec<Comment> = response.json().await?;
+        Ok(comments)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::public::PubClient;
+    use crate::shared::query::pairs;
+    use models::EntityType;
+
+    #[tokio::test]
+    async fn test_get_comments() {
+        let client = PubClient::new();
+
+        let comments = client
+            .get_comments(CommentsDTO {
+                limit: Some(5),
+                ..CommentsDTO::new(EntityType::Event, 2909)
+            })
+            .await;
+
+        if let Err(e) = &comments {
+            eprintln!("Get comments error: {:?}", e);
+        }
+        assert!(comments.is_ok());
+    }
+
+    #[test]
+    fn test_comments_dto_query_params() {
+        let dto = CommentsDTO {
+            limit: Some(10),
+            order: Some(vec![String::from("createdAt")]),
+            ascending: Some(false),
+            holders_only: Some(true),
+            ..CommentsDTO::new(EntityType::Market, 12)
+        };
+
+        assert_eq!(
+            dto.as_query_params(),
+            pairs(&[
+                ("parent_entity_type", "market"),
+                ("parent_entity_id", "12"),
+                ("limit", "10"),
+                ("order", "createdAt"),
+                ("ascending", "false"),
+                ("holders_only", "true"),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_deserialize_comment() {
+        let body = r#"{
+            "id": "100",
+            "body": "Agreed",
+            "parentEntityType": "Event",
+            "parentEntityID": 2909,
+            "parentCommentID": "99",
+            "userAddress": "0xabc",
+            "createdAt": "2025-01-01T12:00:00Z",
+            "profile": {
+                "name": "trader",
+                "proxyWallet": "0xdef",
+                "positions": [{"tokenId": "1", "positionSize": "25"}]
+            },
+            "reactions": [{"id": "1", "commentID": 100, "reactionType": "HEART", "userAddress": "0x1"}],
+            "reactionCount": 1
+        }"#;
+
+        let comment: Comment = serde_json::from_str(body).unwrap();
+
+        assert!(comment.is_reply());
+        assert_eq!(comment.parent_entity_type, Some(EntityType::Event));
+        assert_eq!(comment.parent_entity_id, Some(2909));
+        let profile = comment.profile.unwrap();
+        assert_eq!(profile.proxy_wallet.as_deref(), Some("0xdef"));
+        assert_eq!(profile.positions.unwrap()[0].position_size, "25");
+        let reactions = comment.reactions.unwrap();
+        assert_eq!(reactions[0].reaction_type.as_deref(), Some("HEART"));
+        assert_eq!(reactions[0].comment_id, Some(100));
+    }
+}
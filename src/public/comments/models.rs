@@ -0,0 +1,149 @@
+use std::fmt;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::shared::QueryParams;
+
+/// The kind of entity a comment is posted on
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum EntityType {
+    Event,
+    Series,
+    #[serde(rename = "market")]
+    Market,
+}
+
+impl fmt::Display for EntityType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl EntityType {
+    pub fn as_str(&self) -> &str {
+        match self {
+            EntityType::Event => "Event",
+            EntityType::Series => "Series",
+            EntityType::Market => "market",
+        }
+    }
+}
+
+/// Query parameters for the comments of an event, series or market
+/// # Fields
+/// * `parent_entity_type` / `parent_entity_id` - The entity the comments are posted on
+/// * `limit` / `offset` - Pagination
+/// * `order` / `ascending` - Fields to sort by and the sort direction
+/// * `get_positions` - Include the positions of the authors
+/// * `holders_only` - Only return comments from holders of the market
+#[derive(Debug, Clone, Serialize, Deserialize, QueryParams)]
+pub struct CommentsDTO {
+    pub parent_entity_type: EntityType,
+    pub parent_entity_id: i64,
+    pub limit: Option<u32>,
+    pub offset: Option<u32>,
+    #[query(comma)]
+    pub order: Option<Vec<String>>,
+    pub ascending: Option<bool>,
+    pub get_positions: Option<bool>,
+    pub holders_only: Option<bool>,
+}
+
+impl CommentsDTO {
+    /// The comments of an entity, with the default filters
+    pub fn new(parent_entity_type: EntityType, parent_entity_id: i64) -> Self {
+        Self {
+            parent_entity_type,
+            parent_entity_id,
+            limit: None,
+            offset: None,
+            order: None,
+            ascending: None,
+            get_positions: None,
+            holders_only: None,
+        }
+    }
+}
+
+/// Query parameters for a single comment
+#[derive(Debug, Clone, Serialize, Deserialize, Default, QueryParams)]
+pub struct CommentIdDTO {
+    pub get_positions: Option<bool>,
+}
+
+/// Query parameters for the comments of a user
+#[derive(Debug, Clone, Serialize, Deserialize, Default, QueryParams)]
+pub struct UserCommentsDTO {
+    pub limit: Option<u32>,
+    pub offset: Option<u32>,
+    #[query(comma)]
+    pub order: Option<Vec<String>>,
+    pub ascending: Option<bool>,
+}
+
+/// A position held by the author of a comment
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CommentPosition {
+    pub token_id: String,
+    pub position_size: String,
+}
+
+/// The public profile of the author of a comment or reaction
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct CommentProfile {
+    pub name: Option<String>,
+    pub pseudonym: Option<String>,
+    pub display_username_public: Option<bool>,
+    pub bio: Option<String>,
+    pub is_mod: Option<bool>,
+    pub is_creator: Option<bool>,
+    pub proxy_wallet: Option<String>,
+    pub base_address: Option<String>,
+    pub profile_image: Option<String>,
+    pub positions: Option<Vec<CommentPosition>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Reaction {
+    pub id: String,
+    #[serde(rename = "commentID")]
+    pub comment_id: Option<i64>,
+    pub reaction_type: Option<String>,
+    pub icon: Option<String>,
+    pub user_address: Option<String>,
+    pub created_at: Option<DateTime<Utc>>,
+    pub profile: Option<CommentProfile>,
+}
+
+/// A comment on an event, series or market.
+///
+/// Replies have a `parent_comment_id`; top level comments don't.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Comment {
+    pub id: String,
+    pub body: Option<String>,
+    pub parent_entity_type: Option<EntityType>,
+    #[serde(rename = "parentEntityID")]
+    pub parent_entity_id: Option<i64>,
+    #[serde(rename = "parentCommentID")]
+    pub parent_comment_id: Option<String>,
+    pub user_address: Option<String>,
+    pub reply_address: Option<String>,
+    pub created_at: Option<DateTime<Utc>>,
+    pub updated_at: Option<DateTime<Utc>>,
+    pub profile: Option<CommentProfile>,
+    pub reactions: Option<Vec<Reaction>>,
+    pub report_count: Option<i64>,
+    pub reaction_count: Option<i64>,
+}
+
+impl Comment {
+    pub fn is_reply(&self) -> bool {
+        self.parent_comment_id.is_some()
+    }
+}
@@ -0,0 +1,187 @@
+pub mod models;
+
+use async_trait::async_trait;
+use models::{
+    Activity, ActivityDTO, ClosedPosition, ClosedPositionsDTO, HoldersDTO, PortfolioValue,
+    Position, PositionsDTO, TokenHolders, ValueDTO,
+};
+
+use crate::shared::{ApiError, QueryParams, client::AsyncHttpClient};
+
+/// The Polymarket Data API, for positions and activity of users
+#[async_trait]
+pub trait DataApi {
+    fn get_data_client(&self) -> &AsyncHttpClient;
+
+    /// Get the current positions of a user
+    /// # Arguments
+    /// * `data` - The user and the query parameters. See [`PositionsDTO`] for available options.
+    /// # Returns
+    /// * `Result<Vec<[`Position`]>, [`ApiError`]>` - A vector of [`Position`] objects, or an [`ApiError`] if the request fails
+    async fn get_positions(&self, data: PositionsDTO) -> Result<Vec<Position>, ApiError> {
+        let client = self.get_data_client();
+        let query = data.as_query_params();
+        let response = client.get(Some("/positions"), Some(query), None).await?;
+        let positions: Vec<Position> = response.json().await?;
+        Ok(positions)
+    }
+
+    /// Get the closed positions of a user
+    /// # Arguments
+    /// * `data` - The user and the query parameters. See [`ClosedPositionsDTO`] for available options.
+    /// # Returns
+    /// * `Result<Vec<[`ClosedPosition`]>, [`ApiError`]>` - A vector of [`ClosedPosition`] objects, or an [`ApiError`] if the request fails
+    async fn get_closed_positions(
+        &self,
+        data: ClosedPositionsDTO,
+    ) -> Result<Vec<ClosedPosition>, ApiError> {
+        let client = self.get_data_client();
+        let query = data.as_query_params();
+        let response = client
+            .get(Some("/closed-positions"), Some(query), None)
+            .await?;
+        let positions: Vec<ClosedPosition> = response.json().await?;
+        Ok(positions)
+    }
+
+    /// Get the trades, splits, merges, redemptions, rewards and conversions of a user
+    /// # Arguments
+    /// * `data` - The user and the query parameters. See [`ActivityDTO`] for available options.
+    /// # Returns
+    /// * `Result<Vec<[`Activity`]>, [`ApiError`]>` - A vector of [`Activity`] objects, or an [`ApiError`] if the request fails
+    async fn get_activity(&self, data: ActivityDTO) -> Result<Vec<Activity>, ApiError> {
+        let client = self.get_data_client();
+        let query = data.as_query_params();
+        let response = client.get(Some("/activity"), Some(query), None).await?;
+        let activity: Vec<Activity> = response.json().await?;
+        Ok(activity)
+    }
+
+    /// Get the top holders of the tokens of markets
+    /// # Arguments
+    /// * `data` - The markets and the query parameters. See [`HoldersDTO`] for available options.
+    /// # Returns
+    /// * `Result<Vec<[`TokenHolders`]>, [`ApiError`]>` - The holders of every token, or an [`ApiError`] if the request fails
+    async fn get_holders(&self, data: HoldersDTO) -> Result<Vec<TokenHolders>, ApiError> {
+        let client = self.get_data_client();
+        let query = data.as_query_params();
+        let response = client.get(Some("/holders"), Some(query), None).await?;
+        let holders: Vec<TokenHolders> = response.json().await?;
+        Ok(holders)
+    }
+
+    /// Get the total value of the positions of a user
+    /// # Arguments
+    /// * `data` - The user and the markets to value. See [`ValueDTO`] for available options.
+    /// # Returns
+    /// * `Result<[`PortfolioValue`], [`ApiError`]>` - The value of the positions, or an [`ApiError`] if the request fails
+    async fn get_portfolio_value(&self, data: ValueDTO) -> Result<PortfolioValue, ApiError> {
+        let client = self.get_data_client();
+        let query = data.as_query_params();
+        let response = client.get(Some("/value"), Some(query), None).await?;
+        // A user without positions is returned as an empty list
+        let values: Vec<PortfolioValue> = response.json().await?;
+        Ok(values.into_iter().next().unwrap_or(PortfolioValue {
+            user: data.user,
+            value: 0.0,
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::public::PubClient;
+    use crate::shared::Side;
+    use crate::shared::query::pairs;
+    use models::{ActivitySortBy, ActivityType, PositionSortBy, SortDirection};
+
+    const USER: &str = "0x56687bf447db6ffa42ffe2204a05edaa20f55839";
+
+    #[tokio::test]
+    async fn test_get_positions() {
+        let client = PubClient::new();
+
+        let positions = client
+            .get_positions(PositionsDTO {
+                limit: Some(5),
+                sort_by: Some(PositionSortBy::CashPnl),
+                ..PositionsDTO::new(USER.to_string())
+            })
+            .await;
+
+        if let Err(e) = &positions {
+            eprintln!("Get positions error: {:?}", e);
+        }
+        assert!(positions.is_ok());
+    }
+
+    #[test]
+    fn test_activity_dto_query_params() {
+        let dto = ActivityDTO {
+            activity_type: Some(vec![ActivityType::Trade, ActivityType::Redeem]),
+            market: Some(vec![String::from("0xa"), String::from("0xb")]),
+            start: Some(1700000000),
+            side: Some(Side::BUY),
+            sort_by: Some(ActivitySortBy::Cash),
+            sort_direction: Some(SortDirection::Asc),
+            ..ActivityDTO::new(USER.to_string())
+        };
+
+        assert_eq!(
+            dto.as_query_params(),
+            pairs(&[
+                ("user", USER),
+                ("type", "TRADE,REDEEM"),
+                ("market", "0xa,0xb"),
+                ("start", "1700000000"),
+                ("side", "BUY"),
+                ("sortBy", "CASH"),
+                ("sortDirection", "ASC"),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_deserialize_position() {
+        let body = r#"{
+            "proxyWallet": "0x5668",
+            "asset": "123",
+            "conditionId": "0xabc",
+            "size": 100.5,
+            "avgPrice": 0.4,
+            "initialValue": 40.2,
+            "currentValue": 50.25,
+            "cashPnl": 10.05,
+            "percentPnl": 25,
+            "curPrice": 0.5,
+            "redeemable": false,
+            "mergeable": true,
+            "outcome": "Yes",
+            "outcomeIndex": 0,
+            "negativeRisk": false
+        }"#;
+
+        let position: Position = serde_json::from_str(body).unwrap();
+
+        assert_eq!(position.size, 100.5);
+        assert_eq!(position.cash_pnl, 10.05);
+        assert_eq!(position.percent_pnl, 25.0);
+        assert_eq!(position.cur_price, 0.5);
+        assert!(!position.redeemable);
+    }
+
+    #[test]
+    fn test_deserialize_activity() {
+        let body = r#"[
+            {"proxyWallet": "0x5668", "timestamp": 1700000000, "type": "TRADE", "size": 10, "usdcSize": 4, "price": 0.4, "side": "BUY"},
+            {"proxyWallet": "0x5668", "timestamp": 1700000100, "type": "CONVERSION", "size": 10, "usdcSize": 0, "side": ""}
+        ]"#;
+
+        let activity: Vec<Activity> = serde_json::from_str(body).unwrap();
+
+        assert_eq!(activity[0].activity_type, ActivityType::Trade);
+        assert_eq!(activity[1].activity_type, ActivityType::Conversion);
+        assert_eq!(activity[1].usdc_size, 0.0);
+    }
+}
@@ -0,0 +1,387 @@
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::shared::{QueryParams, Side};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SortDirection {
+    #[serde(rename = "ASC")]
+    Asc,
+    #[serde(rename = "DESC")]
+    Desc,
+}
+
+impl fmt::Display for SortDirection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SortDirection::Asc => write!(f, "ASC"),
+            SortDirection::Desc => write!(f, "DESC"),
+        }
+    }
+}
+
+/// Fields the current positions can be sorted by
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum PositionSortBy {
+    Current,
+    Initial,
+    Tokens,
+    CashPnl,
+    PercentPnl,
+    Title,
+    Resolving,
+    Price,
+    AvgPrice,
+}
+
+impl fmt::Display for PositionSortBy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let value = match self {
+            PositionSortBy::Current => "CURRENT",
+            PositionSortBy::Initial => "INITIAL",
+            PositionSortBy::Tokens => "TOKENS",
+            PositionSortBy::CashPnl => "CASHPNL",
+            PositionSortBy::PercentPnl => "PERCENTPNL",
+            PositionSortBy::Title => "TITLE",
+            PositionSortBy::Resolving => "RESOLVING",
+            PositionSortBy::Price => "PRICE",
+            PositionSortBy::AvgPrice => "AVGPRICE",
+        };
+        write!(f, "{value}")
+    }
+}
+
+/// Fields the closed positions can be sorted by
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum ClosedPositionSortBy {
+    RealizedPnl,
+    Title,
+    Price,
+    AvgPrice,
+    Timestamp,
+}
+
+impl fmt::Display for ClosedPositionSortBy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let value = match self {
+            ClosedPositionSortBy::RealizedPnl => "REALIZEDPNL",
+            ClosedPositionSortBy::Title => "TITLE",
+            ClosedPositionSortBy::Price => "PRICE",
+            ClosedPositionSortBy::AvgPrice => "AVGPRICE",
+            ClosedPositionSortBy::Timestamp => "TIMESTAMP",
+        };
+        write!(f, "{value}")
+    }
+}
+
+/// Fields the activity can be sorted by
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum ActivitySortBy {
+    Timestamp,
+    Tokens,
+    Cash,
+}
+
+impl fmt::Display for ActivitySortBy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let value = match self {
+            ActivitySortBy::Timestamp => "TIMESTAMP",
+            ActivitySortBy::Tokens => "TOKENS",
+            ActivitySortBy::Cash => "CASH",
+        };
+        write!(f, "{value}")
+    }
+}
+
+/// The kind of an on-chain activity of a user
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum ActivityType {
+    Trade,
+    Split,
+    Merge,
+    Redeem,
+    Reward,
+    Conversion,
+}
+
+impl fmt::Display for ActivityType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let value = match self {
+            ActivityType::Trade => "TRADE",
+            ActivityType::Split => "SPLIT",
+            ActivityType::Merge => "MERGE",
+            ActivityType::Redeem => "REDEEM",
+            ActivityType::Reward => "REWARD",
+            ActivityType::Conversion => "CONVERSION",
+        };
+        write!(f, "{value}")
+    }
+}
+
+/// Query parameters for the current positions of a user
+/// # Fields
+/// * `user` - The proxy wallet address of the user
+/// * `market` - Only return positions in the markets with these condition ids
+/// * `event_id` - Only return positions in the markets of the event
+/// * `size_threshold` - Minimum position size
+/// * `redeemable` / `mergeable` - Only return redeemable or mergeable positions
+/// * `limit` / `offset` - Pagination
+/// * `sort_by` / `sort_direction` - Sorting
+/// * `title` - Filter on the market title
+#[derive(Debug, Clone, Serialize, Deserialize, QueryParams)]
+pub struct PositionsDTO {
+    pub user: String,
+    #[query(comma)]
+    pub market: Option<Vec<String>>,
+    #[query(rename = "eventId")]
+    pub event_id: Option<i64>,
+    #[query(rename = "sizeThreshold")]
+    pub size_threshold: Option<f64>,
+    pub redeemable: Option<bool>,
+    pub mergeable: Option<bool>,
+    pub limit: Option<u32>,
+    pub offset: Option<u32>,
+    #[query(rename = "sortBy")]
+    pub sort_by: Option<PositionSortBy>,
+    #[query(rename = "sortDirection")]
+    pub sort_direction: Option<SortDirection>,
+    pub title: Option<String>,
+}
+
+impl PositionsDTO {
+    /// All the current positions of a user, with the default filters
+    pub fn new(user: String) -> Self {
+        Self {
+            user,
+            market: None,
+            event_id: None,
+            size_threshold: None,
+            redeemable: None,
+            mergeable: None,
+            limit: None,
+            offset: None,
+            sort_by: None,
+            sort_direction: None,
+            title: None,
+        }
+    }
+}
+
+/// Query parameters for the closed positions of a user
+#[derive(Debug, Clone, Serialize, Deserialize, QueryParams)]
+pub struct ClosedPositionsDTO {
+    pub user: String,
+    #[query(comma)]
+    pub market: Option<Vec<String>>,
+    #[query(rename = "eventId")]
+    pub event_id: Option<i64>,
+    pub title: Option<String>,
+    pub limit: Option<u32>,
+    pub offset: Option<u32>,
+    #[query(rename = "sortBy")]
+    pub sort_by: Option<ClosedPositionSortBy>,
+    #[query(rename = "sortDirection")]
+    pub sort_direction: Option<SortDirection>,
+}
+
+impl ClosedPositionsDTO {
+    /// All the closed positions of a user, with the default filters
+    pub fn new(user: String) -> Self {
+        Self {
+            user,
+            market: None,
+            event_id: None,
+            title: None,
+            limit: None,
+            offset: None,
+            sort_by: None,
+            sort_direction: None,
+        }
+    }
+}
+
+/// Query parameters for the activity of a user
+/// # Fields
+/// * `user` - The proxy wallet address of the user
+/// * `activity_type` - Only return these kinds of activity
+/// * `market` / `event_id` - Only return activity in the markets or event
+/// * `start` / `end` - Time range, in unix seconds
+/// * `side` - Only return trades on this side
+/// * `limit` / `offset` - Pagination
+/// * `sort_by` / `sort_direction` - Sorting
+#[derive(Debug, Clone, Serialize, Deserialize, QueryParams)]
+pub struct ActivityDTO {
+    pub user: String,
+    #[query(rename = "type", comma)]
+    pub activity_type: Option<Vec<ActivityType>>,
+    #[query(comma)]
+    pub market: Option<Vec<String>>,
+    #[query(rename = "eventId")]
+    pub event_id: Option<i64>,
+    pub start: Option<i64>,
+    pub end: Option<i64>,
+    pub side: Option<Side>,
+    pub limit: Option<u32>,
+    pub offset: Option<u32>,
+    #[query(rename = "sortBy")]
+    pub sort_by: Option<ActivitySortBy>,
+    #[query(rename = "sortDirection")]
+    pub sort_direction: Option<SortDirection>,
+}
+
+impl ActivityDTO {
+    /// All the activity of a user, with the default filters
+    pub fn new(user: String) -> Self {
+        Self {
+            user,
+            activity_type: None,
+            market: None,
+            event_id: None,
+            start: None,
+            end: None,
+            side: None,
+            limit: None,
+            offset: None,
+            sort_by: None,
+            sort_direction: None,
+        }
+    }
+}
+
+/// Query parameters for the top holders of markets
+/// # Fields
+/// * `market` - The condition ids of the markets
+/// * `limit` - Maximum number of holders per token
+/// * `min_balance` - Minimum balance of a holder
+#[derive(Debug, Clone, Serialize, Deserialize, Default, QueryParams)]
+pub struct HoldersDTO {
+    #[query(comma)]
+    pub market: Vec<String>,
+    pub limit: Option<u32>,
+    #[query(rename = "minBalance")]
+    pub min_balance: Option<u32>,
+}
+
+/// Query parameters for the value of the positions of a user
+#[derive(Debug, Clone, Serialize, Deserialize, QueryParams)]
+pub struct ValueDTO {
+    pub user: String,
+    #[query(comma)]
+    pub market: Option<Vec<String>>,
+}
+
+/// A current position of a user
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Position {
+    pub proxy_wallet: String,
+    pub asset: String,
+    pub condition_id: String,
+    pub size: f64,
+    pub avg_price: f64,
+    pub initial_value: Option<f64>,
+    pub current_value: Option<f64>,
+    pub cash_pnl: f64,
+    pub percent_pnl: f64,
+    pub total_bought: Option<f64>,
+    pub realized_pnl: Option<f64>,
+    pub percent_realized_pnl: Option<f64>,
+    pub cur_price: f64,
+    pub redeemable: bool,
+    pub mergeable: Option<bool>,
+    pub title: Option<String>,
+    pub slug: Option<String>,
+    pub icon: Option<String>,
+    pub event_slug: Option<String>,
+    pub outcome: Option<String>,
+    pub outcome_index: Option<u32>,
+    pub opposite_outcome: Option<String>,
+    pub opposite_asset: Option<String>,
+    pub end_date: Option<String>,
+    pub negative_risk: Option<bool>,
+}
+
+/// A closed position of a user
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ClosedPosition {
+    pub proxy_wallet: String,
+    pub asset: String,
+    pub condition_id: String,
+    pub avg_price: f64,
+    pub total_bought: f64,
+    pub realized_pnl: f64,
+    pub cur_price: Option<f64>,
+    /// Unix seconds
+    pub timestamp: Option<i64>,
+    pub title: Option<String>,
+    pub slug: Option<String>,
+    pub icon: Option<String>,
+    pub event_slug: Option<String>,
+    pub outcome: Option<String>,
+    pub outcome_index: Option<u32>,
+    pub opposite_outcome: Option<String>,
+    pub opposite_asset: Option<String>,
+    pub end_date: Option<String>,
+}
+
+/// An on-chain activity of a user
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Activity {
+    pub proxy_wallet: String,
+    /// Unix seconds
+    pub timestamp: i64,
+    pub condition_id: Option<String>,
+    #[serde(rename = "type")]
+    pub activity_type: ActivityType,
+    pub size: f64,
+    pub usdc_size: f64,
+    pub transaction_hash: Option<String>,
+    pub price: Option<f64>,
+    pub asset: Option<String>,
+    /// The side of a trade, empty for other activity
+    pub side: Option<String>,
+    pub outcome_index: Option<u32>,
+    pub title: Option<String>,
+    pub slug: Option<String>,
+    pub icon: Option<String>,
+    pub event_slug: Option<String>,
+    pub outcome: Option<String>,
+    pub name: Option<String>,
+    pub pseudonym: Option<String>,
+}
+
+/// A holder of a token
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Holder {
+    pub proxy_wallet: String,
+    pub asset: Option<String>,
+    pub amount: f64,
+    pub outcome_index: Option<u32>,
+    pub name: Option<String>,
+    pub pseudonym: Option<String>,
+    pub bio: Option<String>,
+    pub profile_image: Option<String>,
+    pub display_username_public: Option<bool>,
+}
+
+/// The top holders of a token
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TokenHolders {
+    pub token: String,
+    pub holders: Vec<Holder>,
+}
+
+/// The total value of the positions of a user
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PortfolioValue {
+    pub user: String,
+    pub value: f64,
+}
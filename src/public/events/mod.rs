@@ -3,7 +3,8 @@ pub mod models;
 use async_trait::async_trait;
 use models::{EventDTO, EventInfo, EventTag};
 
-use crate::shared::{ApiError, QueryParams, client::AsyncHttpClient};
+use crate::shared::{ApiError, PageConfig, QueryParams, client::AsyncHttpClient, pagination};
+use futures::stream::BoxStream;
 
 #[async_trait]
 pub trait Events {
@@ -17,6 +18,39 @@ pub trait Events {
         Ok(events)
     }
 
+    /// Stream all the events matching the filters, one page at a time
+    /// # Arguments
+    /// * `data` - The query parameters. `limit` is replaced by the page size and `offset` is the offset of the first item
+    /// * `config` - The page size, concurrency and item cap. See [`PageConfig`]
+    /// # Returns
+    /// * `BoxStream<Result<[`EventInfo`], [`ApiError`]>>` - The events, ending after the first [`ApiError`]
+    fn stream_events(
+        &self,
+        data: EventDTO,
+        config: PageConfig,
+    ) -> BoxStream<'_, Result<EventInfo, ApiError>>
+    where
+        Self: Sync,
+    {
+        let start = data.offset.unwrap_or(0);
+        pagination::paginate(config, start, move |offset, limit| {
+            self.get_events(EventDTO {
+                limit: Some(limit),
+                offset: Some(offset),
+                ..data.clone()
+            })
+        })
+    }
+
+    /// Get all the events matching the filters
+    /// # Arguments
+    /// * `data` - The query parameters. See [`EventDTO`] for available options.
+    /// # Returns
+    /// * `Result<Vec<[`EventInfo`]>, [`ApiError`]>` - All the events, or an [`ApiError`] if a request fails
+    async fn collect_all_events(&self, data: EventDTO) -> Result<Vec<EventInfo>, ApiError> {
+        pagination::collect_all(self.stream_events(data, PageConfig::default())).await
+    }
+
     async fn get_event(&self, id: String, data: EventDTO) -> Result<EventInfo, ApiError> {
         let path = format!("{}{}", "/events/", id);
         let client = self.get_gamma_client();
@@ -47,6 +81,8 @@ pub trait Events {
 mod tests {
     use super::*;
     use crate::public::PubClient;
+    use crate::shared::query::pairs;
+    use chrono::{TimeZone, Utc};
 
     #[tokio::test]
     async fn test_get_events() {
@@ -144,4 +180,95 @@ mod tests {
 
         assert!(event.await.is_ok());
     }
+
+    #[test]
+    fn test_event_dto_query_params() {
+        let dto = EventDTO {
+            limit: Some(50),
+            offset: Some(100),
+            order: Some(vec![String::from("volume")]),
+            ascending: Some(false),
+            id: Some(vec![2909, 2910]),
+            tag_slug: Some(String::from("politics")),
+            related_tags: Some(true),
+            exclude_tag_id: Some(vec![1, 2]),
+            featured: Some(true),
+            cyoa: Some(false),
+            start_date_min: Some(Utc.with_ymd_and_hms(2025, 1, 1, 0, 0, 0).unwrap()),
+            liquidity_min: Some(1000.0),
+            volume_max: Some(2.5),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            dto.as_query_params(),
+            pairs(&[
+                ("limit", "50"),
+                ("offset", "100"),
+                ("order", "volume"),
+                ("ascending", "false"),
+                ("id", "2909"),
+                ("id", "2910"),
+                ("tag_slug", "politics"),
+                ("related_tags", "true"),
+                ("exclude_tag_id", "1"),
+                ("exclude_tag_id", "2"),
+                ("featured", "true"),
+                ("cyoa", "false"),
+                ("start_date_min", "2025-01-01T00:00:00Z"),
+                ("liquidity_min", "1000"),
+                ("volume_max", "2.5"),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_deserialize_event_info() {
+        let body = r#"{
+            "id": "2909",
+            "slug": "weekly-weather",
+            "title": "Weekly weather",
+            "resolutionSource": "https://weather.example",
+            "startDate": "2025-01-01T00:00:00Z",
+            "liquidity": 1500.5,
+            "volume24hr": 200,
+            "openInterest": 42,
+            "negRisk": true,
+            "negRiskMarketID": "0xabc",
+            "commentCount": 7,
+            "competitive": 0.93,
+            "markets": [{
+                "id": "1",
+                "slug": "rain",
+                "question": "Rain?",
+                "clobTokenIds": "[\"1\", \"2\"]",
+                "outcomes": "[\"Yes\", \"No\"]"
+            }],
+            "tags": [{"id": "10", "label": "Weather", "slug": "weather"}],
+            "series": [{"id": "5", "slug": "weekly-weather", "recurrence": "weekly"}]
+        }"#;
+
+        let event: EventInfo = serde_json::from_str(body).unwrap();
+
+        assert_eq!(
+            event.resolution_source.as_deref(),
+            Some("https://weather.example")
+        );
+        assert_eq!(event.start_date.as_deref(), Some("2025-01-01T00:00:00Z"));
+        assert_eq!(event.liquidity, Some(1500.5));
+        assert_eq!(event.volume24hr, Some(200.0));
+        assert_eq!(event.open_interest, Some(42.0));
+        assert_eq!(event.neg_risk, Some(true));
+        assert_eq!(event.neg_risk_market_id.as_deref(), Some("0xabc"));
+        assert_eq!(event.comment_count, Some(7));
+
+        let markets = event.markets.unwrap();
+        assert_eq!(markets[0].question, "Rain?");
+        assert_eq!(markets[0].outcome_tokens().len(), 2);
+        assert_eq!(event.tags.unwrap()[0].label.as_deref(), Some("Weather"));
+        assert_eq!(
+            event.series.unwrap()[0].recurrence.as_deref(),
+            Some("weekly")
+        );
+    }
 }
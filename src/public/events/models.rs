@@ -1,157 +1,60 @@
-use std::collections::HashMap;
-
+use crate::public::{markets::models::Market, series::models::SeriesInfo, tags::models::TagItem};
 use crate::shared::QueryParams;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone)]
-pub enum EventField {
-    Active(Option<bool>),
-    Closed(Option<bool>),
-    Limit(Option<u32>),
-    IncludeChat(Option<bool>),
-    IncludeTemplate(Option<bool>),
-}
-
-impl EventField {
-    fn as_string(&self) -> String {
-        match self {
-            Self::Active(value) => {
-                let value = if value.is_none() {
-                    String::from("")
-                } else {
-                    value.unwrap().to_string()
-                };
-                return value;
-            }
-            Self::Closed(value) => {
-                let value = if value.is_none() {
-                    String::from("")
-                } else {
-                    value.unwrap().to_string()
-                };
-                return value;
-            }
-            Self::Limit(value) => {
-                let value = if value.is_none() {
-                    String::from("")
-                } else {
-                    value.unwrap().to_string()
-                };
-                return value;
-            }
-            Self::IncludeChat(value) => {
-                let value = if value.is_none() {
-                    String::from("")
-                } else {
-                    value.unwrap().to_string()
-                };
-                return value;
-            }
-            Self::IncludeTemplate(value) => {
-                let value = if value.is_none() {
-                    String::from("")
-                } else {
-                    value.unwrap().to_string()
-                };
-                return value;
-            }
-        }
-    }
-
-    fn is_acceptable_query(&self) -> bool {
-        match self {
-            Self::Active(value) => {
-                if value.is_none() {
-                    return false;
-                } else {
-                    return true;
-                }
-            }
-            Self::Closed(value) => {
-                if value.is_none() {
-                    return false;
-                } else {
-                    return true;
-                }
-            }
-            Self::Limit(value) => {
-                if value.is_none() {
-                    return false;
-                } else {
-                    return true;
-                }
-            }
-            Self::IncludeChat(value) => {
-                if value.is_none() {
-                    return false;
-                } else {
-                    return true;
-                }
-            }
-            Self::IncludeTemplate(value) => {
-                if value.is_none() {
-                    return false;
-                } else {
-                    return true;
-                }
-            }
-        }
-    }
-}
-
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+/// Query parameters for the Gamma `/events` endpoint
+/// # Fields
+/// * `limit` / `offset` - Pagination
+/// * `order` / `ascending` - Fields to sort by and the sort direction
+/// * `id`, `slug` - Only return the listed events
+/// * `tag_id` / `tag_slug` / `related_tags` - Only return events with the tag (and its related tags)
+/// * `exclude_tag_id` - Leave out events with any of the tags
+/// * `active`, `closed`, `archived`, `featured`, `cyoa` - Filter on the event flags
+/// * `include_chat` / `include_template` - Include the chat and template data
+/// * `start_date_min` / `start_date_max`, `end_date_min` / `end_date_max` - Start and end date ranges
+/// * `liquidity_min` / `liquidity_max`, `volume_min` / `volume_max` - Liquidity and volume bounds
+#[derive(Debug, Clone, Serialize, Deserialize, Default, QueryParams)]
 #[serde(rename_all = "camelCase")]
 pub struct EventDTO {
+    pub limit: Option<u32>,
+    pub offset: Option<u32>,
+    #[query(comma)]
+    pub order: Option<Vec<String>>,
+    pub ascending: Option<bool>,
+    #[query(repeat)]
+    pub id: Option<Vec<i64>>,
+    #[query(repeat)]
+    pub slug: Option<Vec<String>>,
+    pub tag_id: Option<i64>,
+    pub tag_slug: Option<String>,
+    pub related_tags: Option<bool>,
+    #[query(repeat)]
+    pub exclude_tag_id: Option<Vec<i64>>,
     pub active: Option<bool>,
     pub closed: Option<bool>,
-    pub limit: Option<u32>,
+    pub archived: Option<bool>,
+    pub featured: Option<bool>,
+    pub cyoa: Option<bool>,
     pub include_chat: Option<bool>,
     pub include_template: Option<bool>,
+    #[query(timestamp)]
+    pub start_date_min: Option<DateTime<Utc>>,
+    #[query(timestamp)]
+    pub start_date_max: Option<DateTime<Utc>>,
+    #[query(timestamp)]
+    pub end_date_min: Option<DateTime<Utc>>,
+    #[query(timestamp)]
+    pub end_date_max: Option<DateTime<Utc>>,
+    pub liquidity_min: Option<f64>,
+    pub liquidity_max: Option<f64>,
+    pub volume_min: Option<f64>,
+    pub volume_max: Option<f64>,
 }
 
-impl EventDTO {
-    fn get(&self, field: &str) -> Option<EventField> {
-        match field {
-            "active" => Some(EventField::Active(self.active)),
-            "closed" => Some(EventField::Closed(self.closed)),
-            "limit" => Some(EventField::Limit(self.limit)),
-            "include_chat" => Some(EventField::IncludeChat(self.include_chat)),
-            "include_template" => Some(EventField::IncludeTemplate(self.include_template)),
-            _ => None,
-        }
-    }
-}
-
-impl QueryParams for EventDTO {
-    fn as_query_params(&self) -> HashMap<String, String> {
-        let fields = [
-            "active",
-            "closed",
-            "limit",
-            "include_chat",
-            "include_template",
-        ];
-
-        let mut query_params = HashMap::new();
-        for field in fields {
-            let event_field = self.get(field);
-            if event_field.is_none() {
-                continue;
-            }
-
-            let event_field_instance = event_field.unwrap();
-            // Only include the value in the query params if it's not None
-            if event_field_instance.is_acceptable_query() {
-                query_params.insert(field.to_owned(), event_field_instance.as_string());
-            }
-        }
-        query_params
-    }
-}
-
-#[derive(Debug, Clone, Serialize, Deserialize)]
-#[serde(rename = "camelCase")]
+/// A Gamma event, a group of one or more related markets
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
 pub struct EventInfo {
     pub id: String,
     pub slug: Option<String>,
@@ -167,11 +70,26 @@ pub struct EventInfo {
 
     pub restricted: Option<bool>,
     pub liquidity: Option<f64>,
+    pub liquidity_clob: Option<f64>,
     pub image: Option<String>,
     pub icon: Option<String>,
     pub active: Option<bool>,
     pub closed: Option<bool>,
+    pub archived: Option<bool>,
+    pub new: Option<bool>,
+    pub featured: Option<bool>,
+    pub cyoa: Option<bool>,
     pub volume: Option<f64>,
+    pub volume24hr: Option<f64>,
+    pub volume1wk: Option<f64>,
+    pub volume1mo: Option<f64>,
+    pub volume1yr: Option<f64>,
+
+    pub enable_order_book: Option<bool>,
+    pub neg_risk: Option<bool>,
+    pub enable_neg_risk: Option<bool>,
+    #[serde(rename = "negRiskMarketID")]
+    pub neg_risk_market_id: Option<String>,
 
     pub sort_by: Option<String>,
     pub is_template: Option<bool>,
@@ -186,7 +104,12 @@ pub struct EventInfo {
     pub created_at: Option<String>,
     pub updated_at: Option<String>,
     pub comments_enabled: Option<bool>,
-    pub competitive: Option<i64>,
+    pub comment_count: Option<i64>,
+    pub competitive: Option<f64>,
+
+    pub markets: Option<Vec<Market>>,
+    pub tags: Option<Vec<TagItem>>,
+    pub series: Option<Vec<SeriesInfo>>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -3,12 +3,18 @@ pub mod models;
 use async_trait::async_trait;
 use models::{Market, MarketDTO};
 
-use crate::shared::{ApiError, QueryParams, client::AsyncHttpClient};
+use crate::shared::{ApiError, PageConfig, QueryParams, client::AsyncHttpClient, pagination};
+use futures::stream::BoxStream;
 
 #[async_trait]
 pub trait Markets {
     fn get_gamma_client(&self) -> &AsyncHttpClient;
 
+    /// Get the markets matching the given filters
+    /// # Arguments
+    /// * `data` - The query parameters for the markets. See [`MarketDTO`] for available options.
+    /// # Returns
+    /// * `Result<Vec<[`Market`]>, [`ApiError`]>` - A vector of [`Market`] objects, or an [`ApiError`] if the request fails
     async fn get_markets(&self, data: MarketDTO) -> Result<Vec<Market>, ApiError> {
         let client = self.get_gamma_client();
         let query = data.as_query_params();
@@ -17,6 +23,57 @@ pub trait Markets {
         Ok(events)
     }
 
+    /// Stream all the markets matching the filters, one page at a time
+    /// # Arguments
+    /// * `data` - The query parameters. `limit` is replaced by the page size and `offset` is the offset of the first item
+    /// * `config` - The page size, concurrency and item cap. See [`PageConfig`]
+    /// # Returns
+    /// * `BoxStream<Result<[`Market`], [`ApiError`]>>` - The markets, ending after the first [`ApiError`]
+    fn stream_markets(
+        &self,
+        data: MarketDTO,
+        config: PageConfig,
+    ) -> BoxStream<'_, Result<Market, ApiError>>
+    where
+        Self: Sync,
+    {
+        let start = data.offset.unwrap_or(0);
+        pagination::paginate(config, start, move |offset, limit| {
+            self.get_markets(MarketDTO {
+                limit: Some(limit),
+                offset: Some(offset),
+                ..data.clone()
+            })
+        })
+    }
+
+    /// Get all the markets matching the filters
+    /// # Arguments
+    /// * `data` - The query parameters. See [`MarketDTO`] for available options.
+    /// # Returns
+    /// * `Result<Vec<[`Market`]>, [`ApiError`]>` - All the markets, or an [`ApiError`] if a request fails
+    async fn collect_all_markets(&self, data: MarketDTO) -> Result<Vec<Market>, ApiError> {
+        pagination::collect_all(self.stream_markets(data, PageConfig::default())).await
+    }
+
+    /// Get a market by id
+    /// # Arguments
+    /// * `id` - The id of the market
+    /// # Returns
+    /// * `Result<[`Market`], [`ApiError`]>` - The [`Market`] object, or an [`ApiError`] if the request fails
+    async fn get_market(&self, id: String) -> Result<Market, ApiError> {
+        let path = format!("{}{}", "/markets/", id);
+        let client = self.get_gamma_client();
+        let response = client.get(Some(path.as_str()), None, None).await?;
+        let market: Market = response.json().await?;
+        Ok(market)
+    }
+
+    /// Get a market by slug
+    /// # Arguments
+    /// * `slug` - The slug of the market
+    /// # Returns
+    /// * `Result<[`Market`], [`ApiError`]>` - The [`Market`] object, or an [`ApiError`] if the request fails
     async fn get_market_by_slug(&self, slug: String) -> Result<Market, ApiError> {
         let path = format!("{}{}", "/markets/slug/", slug);
         let client = self.get_gamma_client();
@@ -25,3 +82,182 @@ pub trait Markets {
         Ok(market)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::public::PubClient;
+    use crate::shared::TokenId;
+    use crate::shared::query::pairs;
+    use chrono::{TimeZone, Utc};
+    use rust_decimal::dec;
+
+    #[tokio::test]
+    async fn test_get_markets() {
+        let client = PubClient::new();
+
+        let markets = client
+            .get_markets(MarketDTO {
+                limit: Some(5),
+                closed: Some(false),
+                order: Some(vec![String::from("volumeNum")]),
+                ascending: Some(false),
+                ..Default::default()
+            })
+            .await;
+
+        if let Err(e) = &markets {
+            eprintln!("Get markets error: {:?}", e);
+        }
+        assert!(markets.is_ok());
+    }
+
+    #[test]
+    fn test_market_dto_query_params() {
+        let dto = MarketDTO {
+            limit: Some(10),
+            offset: Some(20),
+            order: Some(vec![String::from("volumeNum"), String::from("id")]),
+            ascending: Some(false),
+            id: Some(vec![1, 2]),
+            slug: Some(vec![String::from("a-market")]),
+            clob_token_ids: Some(vec![String::from("123")]),
+            condition_ids: Some(vec![String::from("0xabc")]),
+            tag_id: Some(100),
+            related_tags: Some(true),
+            include_tag: Some(true),
+            closed: Some(false),
+            liquidity_num_min: Some(1000.0),
+            liquidity_num_max: Some(5000.5),
+            volume_num_min: Some(10.0),
+            volume_num_max: None,
+            start_date_min: Some(Utc.with_ymd_and_hms(2025, 1, 1, 0, 0, 0).unwrap()),
+            start_date_max: None,
+            end_date_min: None,
+            end_date_max: Some(Utc.with_ymd_and_hms(2025, 12, 31, 23, 59, 59).unwrap()),
+        };
+
+        assert_eq!(
+            dto.as_query_params(),
+            pairs(&[
+                ("limit", "10"),
+                ("offset", "20"),
+                ("order", "volumeNum,id"),
+                ("ascending", "false"),
+                ("id", "1"),
+                ("id", "2"),
+                ("slug", "a-market"),
+                ("clob_token_ids", "123"),
+                ("condition_ids", "0xabc"),
+                ("tag_id", "100"),
+                ("related_tags", "true"),
+                ("include_tag", "true"),
+                ("closed", "false"),
+                ("liquidity_num_min", "1000"),
+                ("liquidity_num_max", "5000.5"),
+                ("volume_num_min", "10"),
+                ("start_date_min", "2025-01-01T00:00:00Z"),
+                ("end_date_max", "2025-12-31T23:59:59Z"),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_deserialize_market() {
+        let body = r#"{
+            "id": "253591",
+            "question": "Will it rain tomorrow?",
+            "conditionId": "0xe3b423dfad8c22ff75c9899c4e8176f628cf4ad4caa00481764d320e7415f7a9",
+            "questionID": "0x1234",
+            "slug": "will-it-rain-tomorrow",
+            "endDate": "2025-11-05T12:00:00Z",
+            "outcomes": "[\"Yes\", \"No\"]",
+            "outcomePrices": "[\"0.535\", \"0.465\"]",
+            "clobTokenIds": "[\"111\", \"222\"]",
+            "volume": "12345.67",
+            "volumeNum": 12345.67,
+            "liquidity": "500.1",
+            "liquidityNum": 500.1,
+            "negRisk": false,
+            "enableOrderBook": true,
+            "orderPriceMinTickSize": 0.01,
+            "orderMinSize": 5,
+            "rewardsMinSize": 50,
+            "rewardsMaxSpread": 3.5,
+            "clobRewards": [{"id": "1", "conditionId": "0xe3b4", "rewardsDailyRate": 10}],
+            "events": [{"id": "2909", "title": "Weather"}]
+        }"#;
+
+        let market: Market = serde_json::from_str(body).unwrap();
+
+        assert_eq!(market.question_id.as_deref(), Some("0x1234"));
+        assert_eq!(market.neg_risk, Some(false));
+        assert_eq!(market.enable_order_book, Some(true));
+        assert_eq!(market.order_price_min_tick_size, Some(0.01));
+        assert_eq!(market.order_min_size, Some(5.0));
+        assert_eq!(market.volume_num, Some(12345.67));
+        assert_eq!(market.rewards_max_spread, Some(3.5));
+        assert_eq!(
+            market.end_date,
+            Some(Utc.with_ymd_and_hms(2025, 11, 5, 12, 0, 0).unwrap())
+        );
+        assert_eq!(
+            market.clob_rewards.as_ref().unwrap()[0].rewards_daily_rate,
+            Some(10.0)
+        );
+        assert_eq!(market.events.as_ref().unwrap()[0].id, "2909");
+        assert_eq!(market.outcomes, vec!["Yes", "No"]);
+        assert_eq!(market.outcome_prices, vec![dec!(0.535), dec!(0.465)]);
+        assert_eq!(
+            market.clob_token_ids(),
+            Some((
+                &TokenId::from(String::from("111")),
+                &TokenId::from(String::from("222"))
+            ))
+        );
+    }
+
+    #[test]
+    fn test_outcome_tokens_multi_outcome() {
+        let body = r#"{
+            "id": "1",
+            "question": "Who will win?",
+            "slug": "who-will-win",
+            "outcomes": "[\"A\", \"B\", \"C\"]",
+            "outcomePrices": "[\"0.5\", \"0.3\", \"0.2\"]",
+            "clobTokenIds": "[\"1\", \"2\", \"3\"]"
+        }"#;
+
+        let market: Market = serde_json::from_str(body).unwrap();
+        let tokens = market.outcome_tokens();
+
+        assert!(market.clob_token_ids().is_none());
+        assert_eq!(tokens.len(), 3);
+        assert_eq!(tokens[2].outcome, "C");
+        assert_eq!(tokens[2].token_id.token_id, "3");
+        assert_eq!(tokens[2].price, Some(dec!(0.2)));
+
+        // Encoded back as JSON strings
+        let value = serde_json::to_value(&market).unwrap();
+        assert_eq!(value["clobTokenIds"], r#"["1","2","3"]"#);
+        let decoded: Market = serde_json::from_value(value).unwrap();
+        assert_eq!(decoded.outcome_tokens(), tokens);
+    }
+
+    #[test]
+    fn test_deserialize_market_without_tokens() {
+        let body = r#"{
+            "id": "1",
+            "question": "Not on the book yet",
+            "slug": "not-on-the-book-yet",
+            "clobTokenIds": null
+        }"#;
+
+        let market: Market = serde_json::from_str(body).unwrap();
+
+        assert!(market.clob_token_ids.is_empty());
+        assert!(market.outcomes.is_empty());
+        assert!(market.clob_token_ids().is_none());
+        assert!(market.outcome_tokens().is_empty());
+    }
+}
@@ -1,37 +1,187 @@
-use std::collections::HashMap;
-
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
+use serde_with::{DefaultOnNull, DisplayFromStr, FromInto, json::JsonString, serde_as};
+
+use crate::public::events::models::EventInfo;
+use crate::shared::{QueryParams, TokenId};
 
-use crate::shared::QueryParams;
+/// Query parameters for the Gamma `/markets` endpoint
+/// # Fields
+/// * `limit` / `offset` - Pagination
+/// * `order` / `ascending` - Fields to sort by and the sort direction
+/// * `id`, `slug`, `clob_token_ids`, `condition_ids` - Only return the listed markets
+/// * `tag_id` / `related_tags` - Only return markets with the tag (and its related tags)
+/// * `include_tag` - Include the tags of every market in the response
+/// * `closed` - Filter on whether the market is closed
+/// * `liquidity_num_min` / `liquidity_num_max`, `volume_num_min` / `volume_num_max` - Liquidity and volume bounds
+/// * `start_date_min` / `start_date_max`, `end_date_min` / `end_date_max` - Start and end date ranges
+#[derive(Debug, Clone, Serialize, Deserialize, Default, QueryParams)]
+pub struct MarketDTO {
+    pub limit: Option<u32>,
+    pub offset: Option<u32>,
+    #[query(comma)]
+    pub order: Option<Vec<String>>,
+    pub ascending: Option<bool>,
+    #[query(repeat)]
+    pub id: Option<Vec<i64>>,
+    #[query(repeat)]
+    pub slug: Option<Vec<String>>,
+    #[query(repeat)]
+    pub clob_token_ids: Option<Vec<String>>,
+    #[query(repeat)]
+    pub condition_ids: Option<Vec<String>>,
+    pub tag_id: Option<i64>,
+    pub related_tags: Option<bool>,
+    pub include_tag: Option<bool>,
+    pub closed: Option<bool>,
+    pub liquidity_num_min: Option<f64>,
+    pub liquidity_num_max: Option<f64>,
+    pub volume_num_min: Option<f64>,
+    pub volume_num_max: Option<f64>,
+    #[query(timestamp)]
+    pub start_date_min: Option<DateTime<Utc>>,
+    #[query(timestamp)]
+    pub start_date_max: Option<DateTime<Utc>>,
+    #[query(timestamp)]
+    pub end_date_min: Option<DateTime<Utc>>,
+    #[query(timestamp)]
+    pub end_date_max: Option<DateTime<Utc>>,
+}
 
+/// A liquidity rewards program attached to a market
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 #[serde(rename_all = "camelCase")]
-pub struct MarketDTO {}
-
-impl QueryParams for MarketDTO {
-    fn as_query_params(&self) -> HashMap<String, String> {
-        HashMap::new()
-    }
+pub struct ClobReward {
+    pub id: Option<String>,
+    pub condition_id: Option<String>,
+    pub asset_address: Option<String>,
+    pub rewards_amount: Option<f64>,
+    pub rewards_daily_rate: Option<f64>,
+    pub start_date: Option<String>,
+    pub end_date: Option<String>,
 }
 
+/// A Gamma market.
+///
+/// `outcomes`, `outcome_prices` and `clob_token_ids` are sent by the API as
+/// JSON encoded strings, e.g. `"[\"Yes\", \"No\"]"`. They are decoded when
+/// deserializing and encoded the same way when serializing. The three lists
+/// are index aligned, see [`Market::outcome_tokens`].
+#[serde_as]
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 #[serde(rename_all = "camelCase")]
 pub struct Market {
     pub id: String,
     pub slug: String,
     pub question: String,
-    pub clob_token_ids: String,
+    #[serde(default)]
+    #[serde_as(as = "DefaultOnNull<JsonString<Vec<FromInto<String>>>>")]
+    pub clob_token_ids: Vec<TokenId>,
+
+    pub condition_id: Option<String>,
+    #[serde(rename = "questionID")]
+    pub question_id: Option<String>,
+    pub description: Option<String>,
+    pub resolution_source: Option<String>,
+    pub image: Option<String>,
+    pub icon: Option<String>,
+    pub category: Option<String>,
+    pub group_item_title: Option<String>,
+    pub market_maker_address: Option<String>,
+
+    #[serde(default)]
+    #[serde_as(as = "DefaultOnNull<JsonString>")]
+    pub outcomes: Vec<String>,
+    #[serde(default)]
+    #[serde_as(as = "DefaultOnNull<JsonString<Vec<DisplayFromStr>>>")]
+    pub outcome_prices: Vec<Decimal>,
+
+    pub start_date: Option<DateTime<Utc>>,
+    pub end_date: Option<DateTime<Utc>>,
+    pub start_date_iso: Option<String>,
+    pub end_date_iso: Option<String>,
+    pub created_at: Option<DateTime<Utc>>,
+    pub updated_at: Option<DateTime<Utc>>,
+
+    pub active: Option<bool>,
+    pub closed: Option<bool>,
+    pub archived: Option<bool>,
+    pub restricted: Option<bool>,
+    pub featured: Option<bool>,
+    pub new: Option<bool>,
+    pub approved: Option<bool>,
+    pub ready: Option<bool>,
+    pub funded: Option<bool>,
+
+    pub enable_order_book: Option<bool>,
+    pub accepting_orders: Option<bool>,
+    pub order_price_min_tick_size: Option<f64>,
+    pub order_min_size: Option<f64>,
+    pub neg_risk: Option<bool>,
+    #[serde(rename = "negRiskMarketID")]
+    pub neg_risk_market_id: Option<String>,
+    #[serde(rename = "negRiskRequestID")]
+    pub neg_risk_request_id: Option<String>,
+
+    pub volume: Option<String>,
+    pub volume_num: Option<f64>,
+    pub volume24hr: Option<f64>,
+    pub volume1wk: Option<f64>,
+    pub volume1mo: Option<f64>,
+    pub volume1yr: Option<f64>,
+    pub liquidity: Option<String>,
+    pub liquidity_num: Option<f64>,
+
+    pub best_bid: Option<f64>,
+    pub best_ask: Option<f64>,
+    pub spread: Option<f64>,
+    pub last_trade_price: Option<f64>,
+    pub one_day_price_change: Option<f64>,
+    pub competitive: Option<f64>,
+
+    pub clob_rewards: Option<Vec<ClobReward>>,
+    pub rewards_min_size: Option<f64>,
+    pub rewards_max_spread: Option<f64>,
+
+    pub events: Option<Vec<EventInfo>>,
 }
 
-impl Market {
-    pub fn clob_token_ids(&self) -> (String, String) {
-        let clean = self
-            .clob_token_ids
-            .trim_matches(|c| c == '[' || c == ']' || c == '"');
+/// An outcome of a market with its CLOB token
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OutcomeToken {
+    pub outcome: String,
+    pub token_id: TokenId,
+    /// The last price reported by Gamma for the outcome
+    pub price: Option<Decimal>,
+}
 
-        // 2. Split by the delimiter ", "
-        let vec: Vec<String> = clean.split("\", \"").map(|s| s.to_string()).collect();
+impl Market {
+    /// The token ids of a binary market, in the order of `outcomes`
+    /// (usually YES then NO)
+    /// # Returns
+    /// * `Option<(&TokenId, &TokenId)>` - The two token ids, or `None` if the market does not have exactly two
+    pub fn clob_token_ids(&self) -> Option<(&TokenId, &TokenId)> {
+        match self.clob_token_ids.as_slice() {
+            [first, second] => Some((first, second)),
+            _ => None,
+        }
+    }
 
-        (vec[0].clone(), vec[1].clone())
+    /// Pair every outcome with its token id and price.
+    ///
+    /// Works for any number of outcomes. Outcomes without a token id (e.g. a
+    /// market that is not on the order book yet) are left out.
+    pub fn outcome_tokens(&self) -> Vec<OutcomeToken> {
+        self.outcomes
+            .iter()
+            .zip(&self.clob_token_ids)
+            .enumerate()
+            .map(|(i, (outcome, token_id))| OutcomeToken {
+                outcome: outcome.clone(),
+                token_id: token_id.clone(),
+                price: self.outcome_prices.get(i).copied(),
+            })
+            .collect()
     }
 }
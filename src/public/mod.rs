@@ -4,28 +4,46 @@ use crate::public::{orderbook::OrderBook, pricing::Pricing, spreads::Spreads};
 // pub mod client;
 // pub mod events;
 
+pub mod clob_markets;
+pub mod comments;
+pub mod data;
 pub mod events;
 pub mod markets;
+pub mod neg_risk;
 pub mod orderbook;
 pub mod pricing;
+pub mod recorder;
+pub mod search;
+pub mod series;
 pub mod sports;
 pub mod spreads;
+pub mod traders;
+pub mod websocket;
 
+use crate::public::clob_markets::ClobMarkets;
+use crate::public::comments::Comments;
+use crate::public::data::DataApi;
 use crate::public::events::Events;
+use crate::public::neg_risk::NegRisk;
 pub mod tags;
 
+use crate::public::search::Search;
+use crate::public::series::Series;
 use crate::public::sports::Sports;
 pub use crate::shared::{
     ApiError, HttpError, TokenId,
     client::AsyncHttpClient,
-    constants::{CLOB_ENDPOINT, GAMMA_ENDPOINT},
+    constants::{CLOB_ENDPOINT, DATA_ENDPOINT, GAMMA_ENDPOINT},
 };
 
 use tags::Tags;
+use traders::Traders;
 
+#[derive(Debug)]
 pub struct PubClient {
     clob_client: AsyncHttpClient,
     gamma_client: AsyncHttpClient,
+    data_client: AsyncHttpClient,
 }
 
 impl PubClient {
@@ -33,6 +51,7 @@ impl PubClient {
         Self {
             clob_client: AsyncHttpClient::new(CLOB_ENDPOINT.to_string(), None),
             gamma_client: AsyncHttpClient::new(GAMMA_ENDPOINT.to_string(), None),
+            data_client: AsyncHttpClient::new(DATA_ENDPOINT.to_string(), None),
         }
     }
 }
@@ -43,18 +62,38 @@ impl OrderBook for PubClient {
     }
 }
 
+impl NegRisk for PubClient {}
+
 impl Pricing for PubClient {
     fn get_clob_client(&self) -> &AsyncHttpClient {
         &self.clob_client
     }
 }
 
+impl ClobMarkets for PubClient {
+    fn get_clob_client(&self) -> &AsyncHttpClient {
+        &self.clob_client
+    }
+}
+
 impl Sports for PubClient {
     fn get_gamma_client(&self) -> &AsyncHttpClient {
         &self.gamma_client
     }
 }
 
+impl Comments for PubClient {
+    fn get_gamma_client(&self) -> &AsyncHttpClient {
+        &self.gamma_client
+    }
+}
+
+impl DataApi for PubClient {
+    fn get_data_client(&self) -> &AsyncHttpClient {
+        &self.data_client
+    }
+}
+
 impl Events for PubClient {
     fn get_gamma_client(&self) -> &AsyncHttpClient {
         &self.gamma_client
@@ -67,6 +106,18 @@ impl Markets for PubClient {
     }
 }
 
+impl Search for PubClient {
+    fn get_gamma_client(&self) -> &AsyncHttpClient {
+        &self.gamma_client
+    }
+}
+
+impl Series for PubClient {
+    fn get_gamma_client(&self) -> &AsyncHttpClient {
+        &self.gamma_client
+    }
+}
+
 impl Tags for PubClient {
     fn get_gamma_client(&self) -> &AsyncHttpClient {
         &self.gamma_client
@@ -78,3 +129,13 @@ impl Spreads for PubClient {
         &self.clob_client
     }
 }
+
+impl Traders for PubClient {
+    fn get_data_client(&self) -> &AsyncHttpClient {
+        &self.data_client
+    }
+
+    fn get_gamma_client(&self) -> &AsyncHttpClient {
+        &self.gamma_client
+    }
+}
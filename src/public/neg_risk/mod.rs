@@ -0,0 +1,521 @@
+//! Tools for neg-risk events.
+//!
+//! The markets of a neg-risk event are mutually exclusive: exactly one of
+//! them resolves YES. Holding one YES share of every outcome therefore pays
+//! out exactly 1, and a full set of YES shares can be minted for 1 through the
+//! neg-risk adapter. The best asks of the YES tokens should sum to a little
+//! above 1 and the best bids to a little below; when they don't, buying or
+//! selling every outcome is an arbitrage.
+
+use async_trait::async_trait;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+use crate::public::events::models::EventInfo;
+use crate::public::orderbook::{OrderBook, OrderBookSnapshot, OrderbookSummary, PriceLevel};
+use crate::shared::{ApiError, TokenId};
+
+/// One outcome of a neg-risk event, backed by a binary market
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NegRiskOutcome {
+    pub market_id: String,
+    /// The short name of the outcome, e.g. a candidate
+    pub title: String,
+    pub yes: TokenId,
+    pub no: TokenId,
+}
+
+/// The outcomes of a neg-risk event
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NegRiskGroup {
+    pub event_id: String,
+    pub neg_risk_market_id: Option<String>,
+    pub outcomes: Vec<NegRiskOutcome>,
+    /// The ids of open markets that are not on the order book yet. Their
+    /// outcomes can still win, so the group does not cover a full set
+    pub unlisted_market_ids: Vec<String>,
+}
+
+impl NegRiskGroup {
+    /// Group the outcome tokens of a neg-risk event.
+    ///
+    /// The event must be fetched with its markets. Closed markets are left
+    /// out, and open markets that are not on the order book yet are listed in
+    /// [`unlisted_market_ids`](Self::unlisted_market_ids).
+    /// # Returns
+    /// * `Result<NegRiskGroup, ApiError>` - The group, or an [`ApiError`] if the event is not neg-risk or has no open market
+    pub fn from_event(event: &EventInfo) -> Result<Self, ApiError> {
+        if event.neg_risk != Some(true) {
+            return Err(ApiError::Unexpected(format!(
+                "Event {} is not a neg-risk event",
+                event.id
+            )));
+        }
+        let mut outcomes = vec![];
+        let mut unlisted_market_ids = vec![];
+        for market in event.markets.iter().flatten() {
+            if market.closed == Some(true) {
+                continue;
+            }
+            let Some((yes, no)) = market.clob_token_ids() else {
+                unlisted_market_ids.push(market.id.clone());
+                continue;
+            };
+            outcomes.push(NegRiskOutcome {
+                market_id: market.id.clone(),
+                title: market
+                    .group_item_title
+                    .clone()
+                    .unwrap_or_else(|| market.question.clone()),
+                yes: yes.clone(),
+                no: no.clone(),
+            });
+        }
+        if outcomes.is_empty() {
+            return Err(ApiError::Unexpected(format!(
+                "Event {} has no open market, was it fetched with its markets?",
+                event.id
+            )));
+        }
+        Ok(Self {
+            event_id: event.id.clone(),
+            neg_risk_market_id: event.neg_risk_market_id.clone(),
+            outcomes,
+            unlisted_market_ids,
+        })
+    }
+
+    /// Whether every open outcome of the event is on the order book
+    pub fn is_complete(&self) -> bool {
+        self.unlisted_market_ids.is_empty()
+    }
+
+    /// The YES token ids of every outcome
+    pub fn yes_token_ids(&self) -> Vec<TokenId> {
+        self.outcomes
+            .iter()
+            .map(|outcome| outcome.yes.clone())
+            .collect()
+    }
+}
+
+/// The top of the YES book of one outcome
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OutcomeQuote {
+    pub outcome: NegRiskOutcome,
+    pub best_bid: Option<PriceLevel>,
+    pub best_ask: Option<PriceLevel>,
+}
+
+/// Which side of every outcome to take
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ArbitrageKind {
+    /// Buy YES of every outcome at the best asks, one of them pays out 1
+    BuyAll,
+    /// Mint a full set for 1 and sell YES of every outcome at the best bids
+    SellAll,
+}
+
+/// A buy-all or sell-all opportunity at the top of the books
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Arbitrage {
+    pub kind: ArbitrageKind,
+    /// The sum of the best asks (buy-all) or best bids (sell-all)
+    pub price_sum: Decimal,
+    /// The taker fees paid on one full set
+    pub fees: Decimal,
+    /// The profit of one full set after fees
+    pub edge: Decimal,
+    /// The number of full sets available at the top of every book
+    pub size: Decimal,
+}
+
+/// The top of the books of every outcome of a neg-risk event
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NegRiskBook {
+    pub event_id: String,
+    pub quotes: Vec<OutcomeQuote>,
+    /// Whether the quotes cover every open outcome, see [`NegRiskGroup::is_complete`]
+    pub complete: bool,
+}
+
+impl NegRiskBook {
+    /// Match the book summaries of the YES tokens to the outcomes of `group`
+    /// # Returns
+    /// * `Result<NegRiskBook, ApiError>` - The quotes, or an [`ApiError`] if a book is missing, not flagged as neg-risk or invalid
+    pub fn from_summaries(
+        group: &NegRiskGroup,
+        summaries: &[OrderbookSummary],
+    ) -> Result<Self, ApiError> {
+        if let Some(summary) = summaries.iter().find(|summary| !summary.neg_risk) {
+            return Err(ApiError::Unexpected(format!(
+                "The book of {} is not neg-risk",
+                summary.asset_id
+            )));
+        }
+        let books = summaries
+            .iter()
+            .map(OrderBookSnapshot::try_from)
+            .collect::<Result<Vec<_>, ApiError>>()?;
+        Self::from_books(group, &books)
+    }
+
+    /// Match the books of the YES tokens to the outcomes of `group`
+    /// # Returns
+    /// * `Result<NegRiskBook, ApiError>` - The quotes, or an [`ApiError`] if a book is missing or not flagged as neg-risk
+    pub fn from_books(group: &NegRiskGroup, books: &[OrderBookSnapshot]) -> Result<Self, ApiError> {
+        let quotes = group
+            .outcomes
+            .iter()
+            .map(|outcome| {
+                let book = books
+                    .iter()
+                    .find(|book| book.asset_id == outcome.yes.token_id)
+                    .ok_or_else(|| {
+                        ApiError::Unexpected(format!("No book returned for {}", outcome.title))
+                    })?;
+                if !book.neg_risk {
+                    return Err(ApiError::Unexpected(format!(
+                        "The book of {} is not neg-risk",
+                        outcome.title
+                    )));
+                }
+                Ok(OutcomeQuote {
+                    outcome: outcome.clone(),
+                    best_bid: book.best_bid(),
+                    best_ask: book.best_ask(),
+                })
+            })
+            .collect::<Result<Vec<_>, ApiError>>()?;
+        Ok(Self {
+            event_id: group.event_id.clone(),
+            quotes,
+            complete: group.is_complete(),
+        })
+    }
+
+    /// The cost of buying one YES share of every outcome, `None` if an outcome
+    /// has no ask or is not on the order book
+    pub fn ask_sum(&self) -> Option<Decimal> {
+        if !self.complete {
+            return None;
+        }
+        self.quotes
+            .iter()
+            .map(|quote| quote.best_ask.map(|level| level.price))
+            .sum()
+    }
+
+    /// The proceeds of selling one YES share of every listed outcome, `None`
+    /// if an outcome has no bid. A full set minted through the adapter also
+    /// holds the YES shares of unlisted outcomes, so selling the listed ones
+    /// is still an arbitrage when they sum above 1
+    pub fn bid_sum(&self) -> Option<Decimal> {
+        self.quotes
+            .iter()
+            .map(|quote| quote.best_bid.map(|level| level.price))
+            .sum()
+    }
+
+    /// How much the asks price a full set above its payout of 1
+    pub fn overround(&self) -> Option<Decimal> {
+        Some(self.ask_sum()? - Decimal::ONE)
+    }
+
+    /// Find a buy-all or sell-all arbitrage after taker fees.
+    ///
+    /// The fee of each leg is `fee_rate_bps / 10_000 * min(price, 1 - price)`
+    /// per share, as charged by the exchange. Buy-all is only offered when
+    /// every open outcome is on the order book.
+    /// # Returns
+    /// * `Option<Arbitrage>` - The opportunity, or `None` if neither side has a positive edge
+    pub fn arbitrage(&self, fee_rate_bps: u32) -> Option<Arbitrage> {
+        let profitable = |arbitrage: &Arbitrage| arbitrage.edge > Decimal::ZERO;
+        self.buy_all(fee_rate_bps)
+            .filter(profitable)
+            .or_else(|| self.sell_all(fee_rate_bps).filter(profitable))
+    }
+
+    fn buy_all(&self, fee_rate_bps: u32) -> Option<Arbitrage> {
+        let levels: Option<Vec<PriceLevel>> =
+            self.quotes.iter().map(|quote| quote.best_ask).collect();
+        let levels = levels?;
+        let price_sum = self.ask_sum()?;
+        let fees = fees(&levels, fee_rate_bps);
+        Some(Arbitrage {
+            kind: ArbitrageKind::BuyAll,
+            price_sum,
+            fees,
+            edge: Decimal::ONE - price_sum - fees,
+            size: min_size(&levels),
+        })
+    }
+
+    fn sell_all(&self, fee_rate_bps: u32) -> Option<Arbitrage> {
+        let levels: Option<Vec<PriceLevel>> =
+            self.quotes.iter().map(|quote| quote.best_bid).collect();
+        let levels = levels?;
+        let price_sum = self.bid_sum()?;
+        let fees = fees(&levels, fee_rate_bps);
+        Some(Arbitrage {
+            kind: ArbitrageKind::SellAll,
+            price_sum,
+            fees,
+            edge: price_sum - Decimal::ONE - fees,
+            size: min_size(&levels),
+        })
+    }
+}
+
+fn fees(levels: &[PriceLevel], fee_rate_bps: u32) -> Decimal {
+    let rate = Decimal::from(fee_rate_bps) / Decimal::from(10_000);
+    levels
+        .iter()
+        .map(|level| rate * level.price.min(Decimal::ONE - level.price))
+        .sum()
+}
+
+fn min_size(levels: &[PriceLevel]) -> Decimal {
+    levels
+        .iter()
+        .map(|level| level.size)
+        .min()
+        .unwrap_or(Decimal::ZERO)
+}
+
+#[async_trait]
+pub trait NegRisk: OrderBook + Sync {
+    /// Get the top of the books of every outcome of a neg-risk event.
+    ///
+    /// Every book is checked against the `neg_risk` flag of its summary, the
+    /// same flag [`get_neg_risk`](OrderBook::get_neg_risk) reads into a
+    /// `NegRiskResponse`, so no request per token is needed.
+    /// # Arguments
+    /// * `event` - The event, fetched with its markets. See [`NegRiskGroup::from_event`]
+    /// # Returns
+    /// * `Result<NegRiskBook, ApiError>` - The quotes of every outcome, or an [`ApiError`] if the event is not neg-risk or a request fails
+    async fn get_neg_risk_book(&self, event: &EventInfo) -> Result<NegRiskBook, ApiError> {
+        let group = NegRiskGroup::from_event(event)?;
+        let summaries = self.post_orderbook_summaries(group.yes_token_ids()).await?;
+        NegRiskBook::from_summaries(&group, &summaries)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::public::markets::models::Market;
+    use crate::public::orderbook::OrderSummary;
+    use chrono::Utc;
+    use rust_decimal::dec;
+
+    fn market(id: &str, title: &str, closed: bool) -> Market {
+        Market {
+            id: id.to_string(),
+            group_item_title: Some(title.to_string()),
+            clob_token_ids: vec![
+                TokenId::from(format!("{id}-yes")),
+                TokenId::from(format!("{id}-no")),
+            ],
+            closed: Some(closed),
+            ..Default::default()
+        }
+    }
+
+    fn event() -> EventInfo {
+        EventInfo {
+            id: String::from("1"),
+            neg_risk: Some(true),
+            markets: Some(vec![
+                market("a", "Alice", false),
+                market("b", "Bob", false),
+                market("c", "Carol", false),
+                market("d", "Dave", true),
+            ]),
+            ..Default::default()
+        }
+    }
+
+    fn book(asset_id: &str, bid: Decimal, ask: Decimal, size: Decimal) -> OrderBookSnapshot {
+        OrderBookSnapshot {
+            market: String::from("0xmarket"),
+            asset_id: asset_id.to_string(),
+            timestamp: Utc::now(),
+            hash: String::new(),
+            bids: vec![PriceLevel { price: bid, size }],
+            asks: vec![PriceLevel { price: ask, size }],
+            min_order_size: dec!(5),
+            tick_size: dec!(0.01),
+            neg_risk: true,
+        }
+    }
+
+    #[test]
+    fn test_group_skips_closed_markets() {
+        let group = NegRiskGroup::from_event(&event()).unwrap();
+
+        let titles: Vec<&str> = group.outcomes.iter().map(|o| o.title.as_str()).collect();
+        assert_eq!(titles, vec!["Alice", "Bob", "Carol"]);
+        assert_eq!(group.yes_token_ids()[1].token_id, "b-yes");
+    }
+
+    #[test]
+    fn test_group_lists_markets_without_tokens() {
+        assert!(NegRiskGroup::from_event(&event()).unwrap().is_complete());
+
+        let mut event = event();
+        let mut unlisted = market("e", "Eve", false);
+        unlisted.clob_token_ids = vec![];
+        event.markets.as_mut().unwrap().push(unlisted);
+
+        let group = NegRiskGroup::from_event(&event).unwrap();
+
+        assert_eq!(group.outcomes.len(), 3);
+        assert_eq!(group.unlisted_market_ids, vec!["e".to_string()]);
+        assert!(!group.is_complete());
+    }
+
+    #[test]
+    fn test_incomplete_group_refuses_buy_all() {
+        let mut event = event();
+        let mut unlisted = market("e", "Eve", false);
+        unlisted.clob_token_ids = vec![];
+        event.markets.as_mut().unwrap().push(unlisted);
+        let group = NegRiskGroup::from_event(&event).unwrap();
+
+        // The listed asks sum to 0.95, but Eve may still win
+        let books = vec![
+            book("a-yes", dec!(0.45), dec!(0.47), dec!(100)),
+            book("b-yes", dec!(0.27), dec!(0.29), dec!(100)),
+            book("c-yes", dec!(0.17), dec!(0.19), dec!(100)),
+        ];
+        let neg_risk = NegRiskBook::from_books(&group, &books).unwrap();
+        assert!(!neg_risk.complete);
+        assert_eq!(neg_risk.ask_sum(), None);
+        assert_eq!(neg_risk.arbitrage(0), None);
+
+        // Selling the listed outcomes of a minted set is still an arbitrage
+        let books = vec![
+            book("a-yes", dec!(0.53), dec!(0.55), dec!(100)),
+            book("b-yes", dec!(0.31), dec!(0.33), dec!(100)),
+            book("c-yes", dec!(0.21), dec!(0.23), dec!(100)),
+        ];
+        let neg_risk = NegRiskBook::from_books(&group, &books).unwrap();
+        assert_eq!(neg_risk.arbitrage(0).unwrap().kind, ArbitrageKind::SellAll);
+    }
+
+    #[test]
+    fn test_group_rejects_plain_events() {
+        let event = EventInfo {
+            neg_risk: Some(false),
+            ..event()
+        };
+        assert!(NegRiskGroup::from_event(&event).is_err());
+    }
+
+    #[test]
+    fn test_sums_and_overround() {
+        let group = NegRiskGroup::from_event(&event()).unwrap();
+        let books = vec![
+            book("a-yes", dec!(0.49), dec!(0.51), dec!(100)),
+            book("b-yes", dec!(0.29), dec!(0.31), dec!(100)),
+            book("c-yes", dec!(0.19), dec!(0.21), dec!(100)),
+        ];
+
+        let neg_risk = NegRiskBook::from_books(&group, &books).unwrap();
+
+        assert_eq!(neg_risk.ask_sum(), Some(dec!(1.03)));
+        assert_eq!(neg_risk.bid_sum(), Some(dec!(0.97)));
+        assert_eq!(neg_risk.overround(), Some(dec!(0.03)));
+        assert_eq!(neg_risk.arbitrage(0), None);
+    }
+
+    #[test]
+    fn test_buy_all_arbitrage_after_fees() {
+        let group = NegRiskGroup::from_event(&event()).unwrap();
+        let books = vec![
+            book("a-yes", dec!(0.45), dec!(0.47), dec!(100)),
+            book("b-yes", dec!(0.27), dec!(0.29), dec!(40)),
+            book("c-yes", dec!(0.17), dec!(0.19), dec!(250)),
+        ];
+        let neg_risk = NegRiskBook::from_books(&group, &books).unwrap();
+
+        let arbitrage = neg_risk.arbitrage(0).unwrap();
+        assert_eq!(arbitrage.kind, ArbitrageKind::BuyAll);
+        assert_eq!(arbitrage.edge, dec!(0.05));
+        assert_eq!(arbitrage.size, dec!(40));
+
+        // 1% of (0.47 + 0.29 + 0.19) = 0.0095
+        let arbitrage = neg_risk.arbitrage(100).unwrap();
+        assert_eq!(arbitrage.fees, dec!(0.0095));
+        assert_eq!(arbitrage.edge, dec!(0.0405));
+
+        // Fees larger than the edge leave no arbitrage
+        assert_eq!(neg_risk.arbitrage(1_000), None);
+    }
+
+    #[test]
+    fn test_sell_all_arbitrage() {
+        let group = NegRiskGroup::from_event(&event()).unwrap();
+        let books = vec![
+            book("a-yes", dec!(0.53), dec!(0.55), dec!(100)),
+            book("b-yes", dec!(0.31), dec!(0.33), dec!(100)),
+            book("c-yes", dec!(0.21), dec!(0.23), dec!(100)),
+        ];
+        let neg_risk = NegRiskBook::from_books(&group, &books).unwrap();
+
+        let arbitrage = neg_risk.arbitrage(0).unwrap();
+        assert_eq!(arbitrage.kind, ArbitrageKind::SellAll);
+        assert_eq!(arbitrage.price_sum, dec!(1.05));
+        assert_eq!(arbitrage.edge, dec!(0.05));
+    }
+
+    #[test]
+    fn test_books_must_be_neg_risk() {
+        let group = NegRiskGroup::from_event(&event()).unwrap();
+        let mut books = vec![
+            book("a-yes", dec!(0.49), dec!(0.51), dec!(100)),
+            book("b-yes", dec!(0.29), dec!(0.31), dec!(100)),
+        ];
+        // The book of Carol is missing
+        assert!(NegRiskBook::from_books(&group, &books).is_err());
+
+        let mut plain = book("c-yes", dec!(0.19), dec!(0.21), dec!(100));
+        plain.neg_risk = false;
+        books.push(plain);
+        assert!(NegRiskBook::from_books(&group, &books).is_err());
+    }
+
+    #[test]
+    fn test_summaries_must_be_neg_risk() {
+        let group = NegRiskGroup::from_event(&event()).unwrap();
+        let summary = |asset_id: &str, neg_risk: bool| OrderbookSummary {
+            market: String::from("0xmarket"),
+            asset_id: asset_id.to_string(),
+            timestamp: String::from("1000"),
+            hash: String::new(),
+            bids: vec![OrderSummary {
+                price: String::from("0.3"),
+                size: String::from("10"),
+            }],
+            asks: vec![OrderSummary {
+                price: String::from("0.35"),
+                size: String::from("10"),
+            }],
+            min_order_size: String::from("5"),
+            tick_size: String::from("0.01"),
+            neg_risk,
+        };
+
+        let mut summaries = vec![
+            summary("a-yes", true),
+            summary("b-yes", true),
+            summary("c-yes", true),
+        ];
+        let neg_risk = NegRiskBook::from_summaries(&group, &summaries).unwrap();
+        assert_eq!(neg_risk.ask_sum(), Some(dec!(1.05)));
+
+        summaries[1].neg_risk = false;
+        assert!(NegRiskBook::from_summaries(&group, &summaries).is_err());
+    }
+}
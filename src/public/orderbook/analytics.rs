@@ -0,0 +1,411 @@
+//! Pure order book analytics computed on an [`OrderBookSnapshot`].
+//!
+//! Functions that walk the book take the taker [`Side`]: a `BUY` consumes the
+//! asks and a `SELL` consumes the bids. Every function returns `None` when the
+//! book does not hold enough information to answer, e.g. an empty side.
+
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+use crate::public::orderbook::models::{OrderBookSnapshot, PriceLevel};
+use crate::shared::Side;
+
+/// The result of walking the book for a hypothetical market order
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Fill {
+    /// Shares filled
+    pub size: Decimal,
+    /// Collateral exchanged for the filled shares
+    pub notional: Decimal,
+    /// Volume weighted average price of the fill
+    pub vwap: Decimal,
+    /// Price of the last level touched
+    pub worst_price: Decimal,
+    /// Whether the requested amount could be filled entirely
+    pub complete: bool,
+}
+
+/// Expected execution cost of a market order relative to the midpoint
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Slippage {
+    pub fill: Fill,
+    pub midpoint: Decimal,
+    /// Price difference to the midpoint, positive when the fill is worse
+    pub absolute: Decimal,
+    /// `absolute` in basis points of the midpoint
+    pub bps: Decimal,
+}
+
+/// Levels a taker on `side` would trade against, best first
+pub fn levels(book: &OrderBookSnapshot, side: Side) -> &[PriceLevel] {
+    match side {
+        Side::BUY => &book.asks,
+        Side::SELL => &book.bids,
+    }
+}
+
+pub fn midpoint(book: &OrderBookSnapshot) -> Option<Decimal> {
+    let (bid, ask) = (book.best_bid()?, book.best_ask()?);
+    Some((bid.price + ask.price) / Decimal::TWO)
+}
+
+pub fn spread(book: &OrderBookSnapshot) -> Option<Decimal> {
+    Some(book.best_ask()?.price - book.best_bid()?.price)
+}
+
+/// Shares available to a taker on `side` at prices no worse than `limit_price`
+pub fn cumulative_depth(book: &OrderBookSnapshot, side: Side, limit_price: Decimal) -> Decimal {
+    levels(book, side)
+        .iter()
+        .take_while(|level| within_limit(side, level.price, limit_price))
+        .map(|level| level.size)
+        .sum()
+}
+
+/// Collateral needed (or received) to take all shares on `side` at prices
+/// no worse than `limit_price`
+pub fn cumulative_notional(book: &OrderBookSnapshot, side: Side, limit_price: Decimal) -> Decimal {
+    levels(book, side)
+        .iter()
+        .take_while(|level| within_limit(side, level.price, limit_price))
+        .map(|level| level.price * level.size)
+        .sum()
+}
+
+/// Walk the book for a market order of `size` shares
+pub fn vwap_for_size(book: &OrderBookSnapshot, side: Side, size: Decimal) -> Option<Fill> {
+    if size <= Decimal::ZERO {
+        return None;
+    }
+    let mut filled = Decimal::ZERO;
+    let mut notional = Decimal::ZERO;
+    let mut worst_price = None;
+
+    for level in levels(book, side) {
+        let remaining = size - filled;
+        if remaining <= Decimal::ZERO {
+            break;
+        }
+        let take = remaining.min(level.size);
+        filled += take;
+        notional += take * level.price;
+        worst_price = Some(level.price);
+    }
+
+    fill(filled, notional, worst_price?, filled >= size)
+}
+
+/// Walk the book for a market order spending (or receiving) `notional` collateral
+pub fn vwap_for_notional(book: &OrderBookSnapshot, side: Side, notional: Decimal) -> Option<Fill> {
+    if notional <= Decimal::ZERO {
+        return None;
+    }
+    let mut filled = Decimal::ZERO;
+    let mut spent = Decimal::ZERO;
+    let mut worst_price = None;
+
+    for level in levels(book, side) {
+        let remaining = notional - spent;
+        if remaining <= Decimal::ZERO {
+            break;
+        }
+        if level.price.is_zero() {
+            continue;
+        }
+        let level_notional = level.price * level.size;
+        if level_notional <= remaining {
+            filled += level.size;
+            spent += level_notional;
+        } else {
+            // `remaining / price` is rounded, so count the notional as spent
+            // exactly rather than multiplying the rounded size back
+            filled += remaining / level.price;
+            spent = notional;
+        }
+        worst_price = Some(level.price);
+    }
+
+    fill(filled, spent, worst_price?, spent >= notional)
+}
+
+/// Expected slippage versus the midpoint of a market order of `size` shares
+pub fn slippage(book: &OrderBookSnapshot, side: Side, size: Decimal) -> Option<Slippage> {
+    let midpoint = midpoint(book)?;
+    let fill = vwap_for_size(book, side, size)?;
+    let absolute = match side {
+        Side::BUY => fill.vwap - midpoint,
+        Side::SELL => midpoint - fill.vwap,
+    };
+    let bps = absolute.checked_div(midpoint)? * Decimal::from(10_000);
+
+    Some(Slippage {
+        fill,
+        midpoint,
+        absolute,
+        bps,
+    })
+}
+
+/// Largest size a taker on `side` can fill without moving more than `ticks`
+/// ticks away from the best price
+pub fn max_size_within_ticks(book: &OrderBookSnapshot, side: Side, ticks: u32) -> Decimal {
+    let Some(best) = levels(book, side).first() else {
+        return Decimal::ZERO;
+    };
+    let distance = book.tick_size * Decimal::from(ticks);
+    let limit_price = match side {
+        Side::BUY => best.price + distance,
+        Side::SELL => best.price - distance,
+    };
+    cumulative_depth(book, side, limit_price)
+}
+
+/// Book imbalance over the best `depth` levels of each side (all levels when
+/// `None`), from `-1` (only asks) to `1` (only bids)
+pub fn imbalance(book: &OrderBookSnapshot, depth: Option<usize>) -> Option<Decimal> {
+    let depth = depth.unwrap_or(usize::MAX);
+    let bid_size: Decimal = book.bids.iter().take(depth).map(|level| level.size).sum();
+    let ask_size: Decimal = book.asks.iter().take(depth).map(|level| level.size).sum();
+    (bid_size - ask_size).checked_div(bid_size + ask_size)
+}
+
+/// Top of book price weighted by the opposite side's size, which leans
+/// towards the side that is more likely to be traded through
+pub fn microprice(book: &OrderBookSnapshot) -> Option<Decimal> {
+    let (bid, ask) = (book.best_bid()?, book.best_ask()?);
+    (bid.price * ask.size + ask.price * bid.size).checked_div(bid.size + ask.size)
+}
+
+fn within_limit(side: Side, price: Decimal, limit_price: Decimal) -> bool {
+    match side {
+        Side::BUY => price <= limit_price,
+        Side::SELL => price >= limit_price,
+    }
+}
+
+fn fill(size: Decimal, notional: Decimal, worst_price: Decimal, complete: bool) -> Option<Fill> {
+    Some(Fill {
+        size,
+        notional,
+        vwap: notional.checked_div(size)?,
+        worst_price,
+        complete,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::DateTime;
+    use rust_decimal::dec;
+
+    fn level(price: Decimal, size: Decimal) -> PriceLevel {
+        PriceLevel { price, size }
+    }
+
+    /// bids 0.48 x 100, 0.47 x 200, 0.45 x 300
+    /// asks 0.52 x 50, 0.53 x 150, 0.56 x 400
+    fn book() -> OrderBookSnapshot {
+        OrderBookSnapshot {
+            market: String::from("0xmarket"),
+            asset_id: String::from("1"),
+            timestamp: DateTime::from_timestamp_millis(0).unwrap(),
+            hash: String::new(),
+            bids: vec![
+                level(dec!(0.48), dec!(100)),
+                level(dec!(0.47), dec!(200)),
+                level(dec!(0.45), dec!(300)),
+            ],
+            asks: vec![
+                level(dec!(0.52), dec!(50)),
+                level(dec!(0.53), dec!(150)),
+                level(dec!(0.56), dec!(400)),
+            ],
+            min_order_size: dec!(5),
+            tick_size: dec!(0.01),
+            neg_risk: false,
+        }
+    }
+
+    fn empty_book() -> OrderBookSnapshot {
+        OrderBookSnapshot {
+            bids: vec![],
+            asks: vec![],
+            ..book()
+        }
+    }
+
+    #[test]
+    fn test_midpoint_and_spread() {
+        assert_eq!(midpoint(&book()), Some(dec!(0.50)));
+        assert_eq!(spread(&book()), Some(dec!(0.04)));
+        assert_eq!(midpoint(&empty_book()), None);
+        assert_eq!(spread(&empty_book()), None);
+
+        let one_sided = OrderBookSnapshot {
+            asks: vec![],
+            ..book()
+        };
+        assert_eq!(midpoint(&one_sided), None);
+    }
+
+    #[test]
+    fn test_cumulative_depth() {
+        let book = book();
+
+        assert_eq!(cumulative_depth(&book, Side::BUY, dec!(0.51)), dec!(0));
+        assert_eq!(cumulative_depth(&book, Side::BUY, dec!(0.52)), dec!(50));
+        assert_eq!(cumulative_depth(&book, Side::BUY, dec!(0.55)), dec!(200));
+        assert_eq!(cumulative_depth(&book, Side::BUY, dec!(1)), dec!(600));
+
+        assert_eq!(cumulative_depth(&book, Side::SELL, dec!(0.49)), dec!(0));
+        assert_eq!(cumulative_depth(&book, Side::SELL, dec!(0.47)), dec!(300));
+        assert_eq!(cumulative_depth(&book, Side::SELL, dec!(0)), dec!(600));
+
+        assert_eq!(cumulative_depth(&empty_book(), Side::BUY, dec!(1)), dec!(0));
+    }
+
+    #[test]
+    fn test_cumulative_notional() {
+        let book = book();
+
+        assert_eq!(
+            cumulative_notional(&book, Side::BUY, dec!(0.53)),
+            dec!(105.5)
+        );
+        assert_eq!(
+            cumulative_notional(&book, Side::SELL, dec!(0.47)),
+            dec!(142)
+        );
+    }
+
+    #[test]
+    fn test_vwap_for_size_within_first_level() {
+        let fill = vwap_for_size(&book(), Side::BUY, dec!(20)).unwrap();
+
+        assert_eq!(fill.size, dec!(20));
+        assert_eq!(fill.notional, dec!(10.4));
+        assert_eq!(fill.vwap, dec!(0.52));
+        assert_eq!(fill.worst_price, dec!(0.52));
+        assert!(fill.complete);
+    }
+
+    #[test]
+    fn test_vwap_for_size_across_levels() {
+        // 50 @ 0.52 + 150 @ 0.53 + 100 @ 0.56 = 26 + 79.5 + 56
+        let fill = vwap_for_size(&book(), Side::BUY, dec!(300)).unwrap();
+        assert_eq!(fill.notional, dec!(161.5));
+        assert_eq!(fill.vwap, dec!(161.5) / dec!(300));
+        assert_eq!(fill.worst_price, dec!(0.56));
+        assert!(fill.complete);
+
+        // 100 @ 0.48 + 50 @ 0.47
+        let fill = vwap_for_size(&book(), Side::SELL, dec!(150)).unwrap();
+        assert_eq!(fill.notional, dec!(71.5));
+        assert_eq!(fill.worst_price, dec!(0.47));
+    }
+
+    #[test]
+    fn test_vwap_for_size_exhausts_book() {
+        let fill = vwap_for_size(&book(), Side::SELL, dec!(1000)).unwrap();
+
+        assert_eq!(fill.size, dec!(600));
+        assert_eq!(fill.notional, dec!(277));
+        assert_eq!(fill.worst_price, dec!(0.45));
+        assert!(!fill.complete);
+    }
+
+    #[test]
+    fn test_vwap_for_size_degenerate_inputs() {
+        assert_eq!(vwap_for_size(&book(), Side::BUY, dec!(0)), None);
+        assert_eq!(vwap_for_size(&book(), Side::BUY, dec!(-1)), None);
+        assert_eq!(vwap_for_size(&empty_book(), Side::BUY, dec!(10)), None);
+    }
+
+    #[test]
+    fn test_vwap_for_notional() {
+        // 26 buys all 50 @ 0.52, the next 5.3 buys 10 @ 0.53
+        let fill = vwap_for_notional(&book(), Side::BUY, dec!(31.3)).unwrap();
+        assert_eq!(fill.size, dec!(60));
+        assert_eq!(fill.notional, dec!(31.3));
+        assert_eq!(fill.worst_price, dec!(0.53));
+        assert!(fill.complete);
+
+        let fill = vwap_for_notional(&book(), Side::SELL, dec!(24)).unwrap();
+        assert_eq!(fill.size, dec!(50));
+        assert_eq!(fill.vwap, dec!(0.48));
+
+        let fill = vwap_for_notional(&book(), Side::BUY, dec!(10000)).unwrap();
+        assert_eq!(fill.size, dec!(600));
+        assert!(!fill.complete);
+
+        assert_eq!(vwap_for_notional(&book(), Side::BUY, dec!(0)), None);
+        assert_eq!(vwap_for_notional(&empty_book(), Side::SELL, dec!(1)), None);
+    }
+
+    #[test]
+    fn test_vwap_for_notional_with_inexact_division() {
+        // 0.9 / 0.109 has no exact decimal representation
+        let book = OrderBookSnapshot {
+            asks: vec![level(dec!(0.109), dec!(100))],
+            ..book()
+        };
+
+        let fill = vwap_for_notional(&book, Side::BUY, dec!(0.9)).unwrap();
+        assert_eq!(fill.notional, dec!(0.9));
+        assert_eq!(fill.size.round_dp(6), dec!(8.256881));
+        assert_eq!(fill.worst_price, dec!(0.109));
+        assert!(fill.complete);
+    }
+
+    #[test]
+    fn test_slippage() {
+        let buy = slippage(&book(), Side::BUY, dec!(50)).unwrap();
+        assert_eq!(buy.midpoint, dec!(0.50));
+        assert_eq!(buy.absolute, dec!(0.02));
+        assert_eq!(buy.bps, dec!(400));
+
+        let sell = slippage(&book(), Side::SELL, dec!(300)).unwrap();
+        // 100 @ 0.48 + 200 @ 0.47 = 142 for 300 shares
+        assert_eq!(sell.absolute, dec!(0.50) - dec!(142) / dec!(300));
+        assert!(sell.bps > dec!(0));
+
+        assert_eq!(slippage(&empty_book(), Side::BUY, dec!(1)), None);
+    }
+
+    #[test]
+    fn test_max_size_within_ticks() {
+        let book = book();
+
+        assert_eq!(max_size_within_ticks(&book, Side::BUY, 0), dec!(50));
+        assert_eq!(max_size_within_ticks(&book, Side::BUY, 1), dec!(200));
+        assert_eq!(max_size_within_ticks(&book, Side::BUY, 3), dec!(200));
+        assert_eq!(max_size_within_ticks(&book, Side::BUY, 4), dec!(600));
+        assert_eq!(max_size_within_ticks(&book, Side::SELL, 1), dec!(300));
+        assert_eq!(max_size_within_ticks(&book, Side::SELL, 3), dec!(600));
+        assert_eq!(max_size_within_ticks(&empty_book(), Side::SELL, 3), dec!(0));
+    }
+
+    #[test]
+    fn test_imbalance() {
+        let book = book();
+
+        assert_eq!(imbalance(&book, Some(1)), Some(dec!(50) / dec!(150)));
+        assert_eq!(imbalance(&book, None), Some(dec!(0)));
+        assert_eq!(imbalance(&empty_book(), None), None);
+
+        let only_asks = OrderBookSnapshot {
+            bids: vec![],
+            ..book.clone()
+        };
+        assert_eq!(imbalance(&only_asks, None), Some(dec!(-1)));
+    }
+
+    #[test]
+    fn test_microprice() {
+        // (0.48 * 50 + 0.52 * 100) / 150, pulled towards the thinner ask
+        assert_eq!(microprice(&book()), Some((dec!(24) + dec!(52)) / dec!(150)));
+        assert!(microprice(&book()).unwrap() > midpoint(&book()).unwrap());
+        assert_eq!(microprice(&empty_book()), None);
+    }
+}
@@ -0,0 +1,290 @@
+//! Combined books for binary markets.
+//!
+//! The two outcome tokens of a binary market are complementary: a bid for NO
+//! at `p` can be matched against a bid for YES at `1 - p` by minting a full
+//! set, so it is effectively an ask for YES at `1 - p`, and vice versa. The
+//! true liquidity for one outcome is therefore its own book plus the mirrored
+//! book of the other outcome.
+
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+use crate::public::orderbook::models::{OrderBookSnapshot, PriceLevel};
+use crate::shared::ApiError;
+
+/// Where a level of a [`CombinedBook`] comes from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum LevelSource {
+    /// Resting on the book of the outcome itself
+    Native,
+    /// Mirrored from the opposite side of the complementary outcome
+    Mirrored,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CombinedLevel {
+    pub price: Decimal,
+    pub size: Decimal,
+    pub source: LevelSource,
+}
+
+/// The book of one outcome merged with the mirrored book of its complement.
+///
+/// Levels are ordered best first. Native and mirrored liquidity at the same
+/// price are kept as separate levels, with the native level first.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CombinedBook {
+    pub market: String,
+    pub asset_id: String,
+    pub complement_asset_id: String,
+    /// The older timestamp of the two books
+    pub timestamp: DateTime<Utc>,
+    pub bids: Vec<CombinedLevel>,
+    pub asks: Vec<CombinedLevel>,
+    pub tick_size: Decimal,
+    pub min_order_size: Decimal,
+    pub neg_risk: bool,
+}
+
+impl CombinedBook {
+    pub fn best_bid(&self) -> Option<CombinedLevel> {
+        self.bids.first().copied()
+    }
+
+    pub fn best_ask(&self) -> Option<CombinedLevel> {
+        self.asks.first().copied()
+    }
+
+    /// Aggregate native and mirrored levels into an [`OrderBookSnapshot`] of
+    /// the outcome, so the functions in
+    /// [`analytics`](crate::public::orderbook::analytics) price against the
+    /// combined liquidity. The snapshot has no hash since it is not a server state.
+    pub fn to_snapshot(&self) -> OrderBookSnapshot {
+        OrderBookSnapshot {
+            market: self.market.clone(),
+            asset_id: self.asset_id.clone(),
+            timestamp: self.timestamp,
+            hash: String::new(),
+            bids: aggregate(&self.bids),
+            asks: aggregate(&self.asks),
+            min_order_size: self.min_order_size,
+            tick_size: self.tick_size,
+            neg_risk: self.neg_risk,
+        }
+    }
+}
+
+/// Combine the book of an outcome with the book of its complement
+/// # Arguments
+/// * `book` - The book of the outcome to build the combined book for
+/// * `complement` - The book of the other outcome of the same market
+/// # Returns
+/// * `Result<CombinedBook, ApiError>` - The combined book, or an error if the books belong to different markets
+pub fn combine(
+    book: &OrderBookSnapshot,
+    complement: &OrderBookSnapshot,
+) -> Result<CombinedBook, ApiError> {
+    if book.market != complement.market {
+        return Err(ApiError::Unexpected(format!(
+            "Books belong to different markets ({} and {})",
+            book.market, complement.market
+        )));
+    }
+    if book.asset_id == complement.asset_id {
+        return Err(ApiError::Unexpected(format!(
+            "Cannot combine the book of {} with itself",
+            book.asset_id
+        )));
+    }
+
+    let mut bids = merge(&book.bids, &complement.asks);
+    let mut asks = merge(&book.asks, &complement.bids);
+    bids.sort_by(|a, b| {
+        b.price
+            .cmp(&a.price)
+            .then(a.source.cmp_native_first(&b.source))
+    });
+    asks.sort_by(|a, b| {
+        a.price
+            .cmp(&b.price)
+            .then(a.source.cmp_native_first(&b.source))
+    });
+
+    Ok(CombinedBook {
+        market: book.market.clone(),
+        asset_id: book.asset_id.clone(),
+        complement_asset_id: complement.asset_id.clone(),
+        timestamp: book.timestamp.min(complement.timestamp),
+        bids,
+        asks,
+        tick_size: book.tick_size.max(complement.tick_size),
+        min_order_size: book.min_order_size,
+        neg_risk: book.neg_risk,
+    })
+}
+
+impl LevelSource {
+    fn cmp_native_first(&self, other: &Self) -> std::cmp::Ordering {
+        (*self == LevelSource::Mirrored).cmp(&(*other == LevelSource::Mirrored))
+    }
+}
+
+fn merge(native: &[PriceLevel], opposite: &[PriceLevel]) -> Vec<CombinedLevel> {
+    let native = native.iter().map(|level| CombinedLevel {
+        price: level.price,
+        size: level.size,
+        source: LevelSource::Native,
+    });
+    let mirrored = opposite.iter().map(|level| CombinedLevel {
+        price: Decimal::ONE - level.price,
+        size: level.size,
+        source: LevelSource::Mirrored,
+    });
+    native
+        .chain(mirrored)
+        .filter(|level| !level.size.is_zero())
+        .collect()
+}
+
+fn aggregate(levels: &[CombinedLevel]) -> Vec<PriceLevel> {
+    let mut aggregated: Vec<PriceLevel> = vec![];
+    for level in levels {
+        match aggregated.last_mut() {
+            Some(last) if last.price == level.price => last.size += level.size,
+            _ => aggregated.push(PriceLevel {
+                price: level.price,
+                size: level.size,
+            }),
+        }
+    }
+    aggregated
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::public::orderbook::analytics;
+    use crate::shared::Side;
+    use chrono::DateTime;
+    use rust_decimal::dec;
+
+    fn level(price: Decimal, size: Decimal) -> PriceLevel {
+        PriceLevel { price, size }
+    }
+
+    fn snapshot(asset_id: &str, bids: Vec<PriceLevel>, asks: Vec<PriceLevel>) -> OrderBookSnapshot {
+        OrderBookSnapshot {
+            market: String::from("0xmarket"),
+            asset_id: asset_id.to_string(),
+            timestamp: DateTime::from_timestamp_millis(0).unwrap(),
+            hash: String::new(),
+            bids,
+            asks,
+            min_order_size: dec!(5),
+            tick_size: dec!(0.01),
+            neg_risk: false,
+        }
+    }
+
+    fn yes() -> OrderBookSnapshot {
+        snapshot(
+            "yes",
+            vec![level(dec!(0.40), dec!(100)), level(dec!(0.38), dec!(50))],
+            vec![level(dec!(0.45), dec!(80))],
+        )
+    }
+
+    fn no() -> OrderBookSnapshot {
+        snapshot(
+            "no",
+            // Mirrors to YES asks at 0.43 and 0.45
+            vec![level(dec!(0.57), dec!(30)), level(dec!(0.55), dec!(20))],
+            // Mirrors to a YES bid at 0.39
+            vec![level(dec!(0.61), dec!(70))],
+        )
+    }
+
+    fn native(price: Decimal, size: Decimal) -> CombinedLevel {
+        CombinedLevel {
+            price,
+            size,
+            source: LevelSource::Native,
+        }
+    }
+
+    fn mirrored(price: Decimal, size: Decimal) -> CombinedLevel {
+        CombinedLevel {
+            price,
+            size,
+            source: LevelSource::Mirrored,
+        }
+    }
+
+    #[test]
+    fn test_combine_mirrors_complement() {
+        let combined = combine(&yes(), &no()).unwrap();
+
+        assert_eq!(combined.asset_id, "yes");
+        assert_eq!(combined.complement_asset_id, "no");
+        assert_eq!(
+            combined.bids,
+            vec![
+                native(dec!(0.40), dec!(100)),
+                mirrored(dec!(0.39), dec!(70)),
+                native(dec!(0.38), dec!(50)),
+            ]
+        );
+        assert_eq!(
+            combined.asks,
+            vec![
+                mirrored(dec!(0.43), dec!(30)),
+                native(dec!(0.45), dec!(80)),
+                mirrored(dec!(0.45), dec!(20)),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_combine_for_other_outcome() {
+        let combined = combine(&no(), &yes()).unwrap();
+
+        assert_eq!(combined.best_bid(), Some(native(dec!(0.57), dec!(30))));
+        assert_eq!(combined.best_ask(), Some(mirrored(dec!(0.60), dec!(100))));
+        assert_eq!(
+            combined.bids,
+            vec![
+                native(dec!(0.57), dec!(30)),
+                native(dec!(0.55), dec!(20)),
+                mirrored(dec!(0.55), dec!(80)),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_to_snapshot_aggregates_levels() {
+        let combined = combine(&yes(), &no()).unwrap();
+        let snapshot = combined.to_snapshot();
+
+        assert_eq!(
+            snapshot.asks,
+            vec![level(dec!(0.43), dec!(30)), level(dec!(0.45), dec!(100))]
+        );
+        assert_eq!(snapshot.bids.len(), 3);
+        assert_eq!(snapshot.asset_id, "yes");
+
+        // Buying 50 YES takes the mirrored 0.43 level before the native book
+        let fill = analytics::vwap_for_size(&snapshot, Side::BUY, dec!(50)).unwrap();
+        assert_eq!(fill.notional, dec!(30) * dec!(0.43) + dec!(20) * dec!(0.45));
+        assert!(fill.vwap < dec!(0.45));
+    }
+
+    #[test]
+    fn test_combine_rejects_unrelated_books() {
+        let mut other = no();
+        other.market = String::from("0xother");
+
+        assert!(combine(&yes(), &other).is_err());
+        assert!(combine(&yes(), &yes()).is_err());
+    }
+}
@@ -0,0 +1,765 @@
+//! Locally maintained order books.
+//!
+//! A [`LocalOrderBook`] is seeded from an [`OrderbookSummary`] snapshot and
+//! then kept up to date with the deltas pushed on the market WebSocket channel.
+//! [`BookManager`] holds many books keyed by asset id, re-fetches a snapshot
+//! whenever a book falls out of sequence, and can be cloned into other tasks
+//! for cheap best bid/ask reads.
+
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use std::collections::{BTreeMap, HashMap};
+use std::sync::{Arc, RwLock};
+
+use crate::public::orderbook::OrderBook;
+use crate::public::orderbook::models;
+use crate::public::orderbook::models::{
+    OrderBookSnapshot, OrderSummary, OrderbookSummary, PriceLevel, parse_decimal,
+    parse_timestamp_millis,
+};
+use crate::public::websocket::{
+    BookMessage, MarketEvent, PriceChange, TickSizeChangeMessage, WsEvent,
+};
+use crate::shared::{ApiError, Side, TokenId};
+
+/// Reasons a delta could not be applied to a [`LocalOrderBook`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SequenceError {
+    /// The update is older than the current state of the book
+    StaleTimestamp {
+        book: DateTime<Utc>,
+        update: DateTime<Utc>,
+    },
+    /// A snapshot for the same instant as the book carries a different hash
+    HashMismatch { expected: String, actual: String },
+    /// After applying the update the local top of book differs from the one
+    /// reported by the server
+    TopOfBookMismatch {
+        side: Side,
+        expected: Option<Decimal>,
+        actual: Option<Decimal>,
+    },
+    /// The update could not be parsed
+    Malformed(String),
+}
+
+/// An order book for a single asset with bids and asks keyed by price
+#[derive(Debug, Clone)]
+pub struct LocalOrderBook {
+    asset_id: String,
+    market: String,
+    bids: BTreeMap<Decimal, Decimal>,
+    asks: BTreeMap<Decimal, Decimal>,
+    hash: String,
+    timestamp: DateTime<Utc>,
+    tick_size: Decimal,
+    min_order_size: Decimal,
+    neg_risk: bool,
+}
+
+impl LocalOrderBook {
+    /// Seed a book from a REST snapshot
+    pub fn from_summary(summary: &OrderbookSummary) -> Result<Self, ApiError> {
+        Ok(Self::from_snapshot(&OrderBookSnapshot::try_from(summary)?))
+    }
+
+    /// Seed a book from a parsed snapshot
+    pub fn from_snapshot(snapshot: &OrderBookSnapshot) -> Self {
+        Self {
+            asset_id: snapshot.asset_id.clone(),
+            market: snapshot.market.clone(),
+            bids: to_map(&snapshot.bids),
+            asks: to_map(&snapshot.asks),
+            hash: snapshot.hash.clone(),
+            timestamp: snapshot.timestamp,
+            tick_size: snapshot.tick_size,
+            min_order_size: snapshot.min_order_size,
+            neg_risk: snapshot.neg_risk,
+        }
+    }
+
+    /// The current state of the book as a [`OrderBookSnapshot`]
+    pub fn snapshot(&self) -> OrderBookSnapshot {
+        OrderBookSnapshot {
+            market: self.market.clone(),
+            asset_id: self.asset_id.clone(),
+            timestamp: self.timestamp,
+            hash: self.hash.clone(),
+            bids: self.bids().collect(),
+            asks: self.asks().collect(),
+            min_order_size: self.min_order_size,
+            tick_size: self.tick_size,
+            neg_risk: self.neg_risk,
+        }
+    }
+
+    pub fn asset_id(&self) -> &str {
+        &self.asset_id
+    }
+
+    pub fn market(&self) -> &str {
+        &self.market
+    }
+
+    /// Hash of the last snapshot or delta applied to the book
+    pub fn hash(&self) -> &str {
+        &self.hash
+    }
+
+    /// Timestamp of the last snapshot or delta applied to the book
+    pub fn timestamp(&self) -> DateTime<Utc> {
+        self.timestamp
+    }
+
+    /// Bid levels from best (highest) to worst
+    pub fn bids(&self) -> impl Iterator<Item = PriceLevel> + '_ {
+        self.bids
+            .iter()
+            .rev()
+            .map(|(&price, &size)| PriceLevel { price, size })
+    }
+
+    /// Ask levels from best (lowest) to worst
+    pub fn asks(&self) -> impl Iterator<Item = PriceLevel> + '_ {
+        self.asks
+            .iter()
+            .map(|(&price, &size)| PriceLevel { price, size })
+    }
+
+    pub fn tick_size(&self) -> Decimal {
+        self.tick_size
+    }
+
+    pub fn best_bid(&self) -> Option<PriceLevel> {
+        self.bids().next()
+    }
+
+    pub fn best_ask(&self) -> Option<PriceLevel> {
+        self.asks().next()
+    }
+
+    /// Replace the book with a newer `book` message
+    pub fn apply_snapshot(&mut self, message: &BookMessage) -> Result<(), SequenceError> {
+        let update = parse_timestamp_millis(&message.timestamp)
+            .map_err(|error| SequenceError::Malformed(format!("{error:?}")))?;
+        if update < self.timestamp {
+            return Err(SequenceError::StaleTimestamp {
+                book: self.timestamp,
+                update,
+            });
+        }
+        if update == self.timestamp && message.hash != self.hash {
+            return Err(SequenceError::HashMismatch {
+                expected: self.hash.clone(),
+                actual: message.hash.clone(),
+            });
+        }
+        let (Ok(bids), Ok(asks)) = (parse_levels(&message.bids), parse_levels(&message.asks))
+        else {
+            return Err(SequenceError::Malformed(format!(
+                "Invalid levels in snapshot {}",
+                message.hash
+            )));
+        };
+        self.bids = bids;
+        self.asks = asks;
+        self.hash = message.hash.clone();
+        self.timestamp = update;
+        Ok(())
+    }
+
+    /// Apply a `tick_size_change` message
+    pub fn apply_tick_size_change(
+        &mut self,
+        message: &TickSizeChangeMessage,
+    ) -> Result<(), SequenceError> {
+        self.tick_size = parse_decimal(&message.new_tick_size)
+            .map_err(|error| SequenceError::Malformed(format!("{error:?}")))?;
+        Ok(())
+    }
+
+    /// Apply a single level update stamped with `timestamp`
+    pub fn apply_price_change(
+        &mut self,
+        change: &PriceChange,
+        timestamp: DateTime<Utc>,
+    ) -> Result<(), SequenceError> {
+        if timestamp < self.timestamp {
+            return Err(SequenceError::StaleTimestamp {
+                book: self.timestamp,
+                update: timestamp,
+            });
+        }
+
+        let (Ok(price), Ok(size)) = (parse_decimal(&change.price), parse_decimal(&change.size))
+        else {
+            return Err(SequenceError::Malformed(format!(
+                "Invalid level {} @ {}",
+                change.size, change.price
+            )));
+        };
+        let previous = set_level(self.levels_mut(change.side), price, size);
+
+        // Only commit the change once the top of book matches the server's,
+        // so a book that failed to apply a delta keeps its last valid state
+        let verified = self
+            .verify_top(Side::BUY, change.best_bid.as_deref())
+            .and_then(|()| self.verify_top(Side::SELL, change.best_ask.as_deref()));
+        if let Err(error) = verified {
+            set_level(
+                self.levels_mut(change.side),
+                price,
+                previous.unwrap_or_default(),
+            );
+            return Err(error);
+        }
+
+        self.timestamp = timestamp;
+        if let Some(hash) = &change.hash {
+            self.hash = hash.clone();
+        }
+        Ok(())
+    }
+
+    fn levels_mut(&mut self, side: Side) -> &mut BTreeMap<Decimal, Decimal> {
+        match side {
+            Side::BUY => &mut self.bids,
+            Side::SELL => &mut self.asks,
+        }
+    }
+
+    /// Compare the local top of book with the one reported alongside a delta
+    fn verify_top(&self, side: Side, reported: Option<&str>) -> Result<(), SequenceError> {
+        let Some(reported) = reported else {
+            return Ok(());
+        };
+        // The server reports an empty side as "0"
+        let expected = parse_decimal(reported)
+            .ok()
+            .filter(|price| !price.is_zero());
+        let actual = match side {
+            Side::BUY => self.best_bid(),
+            Side::SELL => self.best_ask(),
+        }
+        .map(|level| level.price);
+
+        if expected == actual {
+            Ok(())
+        } else {
+            Err(SequenceError::TopOfBookMismatch {
+                side,
+                expected,
+                actual,
+            })
+        }
+    }
+}
+
+/// A thread safe collection of [`LocalOrderBook`]s keyed by asset id.
+///
+/// Cloning a manager is cheap and every clone shares the same books, so one
+/// task can feed it events while others read the top of book.
+pub struct BookManager<C> {
+    client: Arc<C>,
+    books: Arc<RwLock<HashMap<String, LocalOrderBook>>>,
+}
+
+impl<C> Clone for BookManager<C> {
+    fn clone(&self) -> Self {
+        Self {
+            client: Arc::clone(&self.client),
+            books: Arc::clone(&self.books),
+        }
+    }
+}
+
+impl<C: OrderBook + Send + Sync> BookManager<C> {
+    /// Create a manager that re-fetches snapshots through `client`
+    pub fn new(client: C) -> Self {
+        Self {
+            client: Arc::new(client),
+            books: Arc::new(RwLock::new(HashMap::new())),
+        }
+    }
+
+    /// Start tracking the given assets by fetching a snapshot for each
+    pub async fn track(&self, asset_ids: Vec<String>) -> Result<(), ApiError> {
+        if asset_ids.is_empty() {
+            return Ok(());
+        }
+        let tokens = asset_ids
+            .into_iter()
+            .map(|token_id| TokenId { token_id })
+            .collect();
+        let summaries = self.client.post_orderbook_summaries(tokens).await?;
+        for summary in summaries {
+            self.insert(LocalOrderBook::from_summary(&summary)?);
+        }
+        Ok(())
+    }
+
+    /// Stop tracking an asset
+    pub fn untrack(&self, asset_id: &str) -> Option<LocalOrderBook> {
+        self.write().remove(asset_id)
+    }
+
+    /// Replace the book of an asset with a fresh REST snapshot
+    pub async fn resnapshot(&self, asset_id: &str) -> Result<(), ApiError> {
+        let summary = self
+            .client
+            .get_orderbook_summary(TokenId {
+                token_id: asset_id.to_string(),
+            })
+            .await?;
+        self.insert(LocalOrderBook::from_summary(&summary)?);
+        Ok(())
+    }
+
+    /// Apply an event from a [`MarketSocket`](crate::public::websocket::MarketSocket).
+    ///
+    /// Market updates are applied to the tracked books, and a snapshot is
+    /// re-fetched for every book that is out of sequence or affected by a gap.
+    /// Updates for untracked assets are ignored.
+    pub async fn handle(&self, event: &WsEvent) -> Result<(), ApiError> {
+        match event {
+            WsEvent::Market(event) => self.apply(event).await,
+            WsEvent::Gap { asset_ids, .. } => {
+                for asset_id in asset_ids {
+                    if self.contains(asset_id) {
+                        self.resnapshot(asset_id).await?;
+                    }
+                }
+                Ok(())
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Apply a market channel message to the tracked books
+    pub async fn apply(&self, event: &MarketEvent) -> Result<(), ApiError> {
+        let out_of_sequence = match event {
+            MarketEvent::Book(message) => {
+                let mut books = self.write();
+                match books.get_mut(&message.asset_id) {
+                    Some(book) => match book.apply_snapshot(message) {
+                        Ok(()) => vec![],
+                        Err(SequenceError::StaleTimestamp { .. }) => vec![],
+                        Err(_) => vec![message.asset_id.clone()],
+                    },
+                    None => vec![],
+                }
+            }
+            MarketEvent::PriceChange(message) => {
+                let timestamp = parse_timestamp_millis(&message.timestamp)?;
+                let mut books = self.write();
+                let mut out_of_sequence: Vec<String> = vec![];
+                for change in &message.price_changes {
+                    if out_of_sequence.contains(&change.asset_id) {
+                        continue;
+                    }
+                    let Some(book) = books.get_mut(&change.asset_id) else {
+                        continue;
+                    };
+                    match book.apply_price_change(change, timestamp) {
+                        // A delta older than the book is already part of it
+                        Ok(()) | Err(SequenceError::StaleTimestamp { .. }) => {}
+                        Err(_) => out_of_sequence.push(change.asset_id.clone()),
+                    }
+                }
+                out_of_sequence
+            }
+            MarketEvent::TickSizeChange(message) => {
+                let mut books = self.write();
+                match books.get_mut(&message.asset_id) {
+                    Some(book) => match book.apply_tick_size_change(message) {
+                        Ok(()) => vec![],
+                        Err(_) => vec![message.asset_id.clone()],
+                    },
+                    None => vec![],
+                }
+            }
+            _ => vec![],
+        };
+
+        for asset_id in out_of_sequence {
+            self.resnapshot(&asset_id).await?;
+        }
+        Ok(())
+    }
+
+    pub fn contains(&self, asset_id: &str) -> bool {
+        self.read().contains_key(asset_id)
+    }
+
+    /// Ids of all tracked assets
+    pub fn asset_ids(&self) -> Vec<String> {
+        self.read().keys().cloned().collect()
+    }
+
+    pub fn best_bid(&self, asset_id: &str) -> Option<PriceLevel> {
+        self.read().get(asset_id)?.best_bid()
+    }
+
+    pub fn best_ask(&self, asset_id: &str) -> Option<PriceLevel> {
+        self.read().get(asset_id)?.best_ask()
+    }
+
+    /// Best bid and best ask of an asset read under a single lock
+    pub fn top_of_book(&self, asset_id: &str) -> Option<(Option<PriceLevel>, Option<PriceLevel>)> {
+        let books = self.read();
+        let book = books.get(asset_id)?;
+        Some((book.best_bid(), book.best_ask()))
+    }
+
+    /// A copy of the full book of an asset
+    pub fn book(&self, asset_id: &str) -> Option<LocalOrderBook> {
+        self.read().get(asset_id).cloned()
+    }
+
+    fn insert(&self, book: LocalOrderBook) {
+        self.write().insert(book.asset_id.clone(), book);
+    }
+
+    fn read(&self) -> std::sync::RwLockReadGuard<'_, HashMap<String, LocalOrderBook>> {
+        self.books
+            .read()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    fn write(&self) -> std::sync::RwLockWriteGuard<'_, HashMap<String, LocalOrderBook>> {
+        self.books
+            .write()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+/// Set the size of a level, removing it when `size` is zero
+/// # Returns
+/// * `Option<Decimal>` - The previous size of the level
+fn set_level(
+    levels: &mut BTreeMap<Decimal, Decimal>,
+    price: Decimal,
+    size: Decimal,
+) -> Option<Decimal> {
+    if size.is_zero() {
+        levels.remove(&price)
+    } else {
+        levels.insert(price, size)
+    }
+}
+
+fn to_map(levels: &[PriceLevel]) -> BTreeMap<Decimal, Decimal> {
+    levels
+        .iter()
+        .filter(|level| !level.size.is_zero())
+        .map(|level| (level.price, level.size))
+        .collect()
+}
+
+fn parse_levels(levels: &[OrderSummary]) -> Result<BTreeMap<Decimal, Decimal>, ApiError> {
+    Ok(to_map(&models::parse_levels(levels)?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::public::websocket::PriceChangeMessage;
+    use crate::shared::client::AsyncHttpClient;
+    use async_trait::async_trait;
+    use rust_decimal::dec;
+    use std::sync::Mutex;
+
+    /// Serves snapshots from memory and records which assets were fetched
+    struct MockBooks {
+        http: AsyncHttpClient,
+        snapshots: Mutex<HashMap<String, OrderbookSummary>>,
+        fetched: Mutex<Vec<String>>,
+    }
+
+    impl MockBooks {
+        fn new(snapshots: Vec<OrderbookSummary>) -> Self {
+            Self {
+                http: AsyncHttpClient::new(String::new(), None),
+                snapshots: Mutex::new(
+                    snapshots
+                        .into_iter()
+                        .map(|summary| (summary.asset_id.clone(), summary))
+                        .collect(),
+                ),
+                fetched: Mutex::new(vec![]),
+            }
+        }
+    }
+
+    #[async_trait]
+    impl OrderBook for MockBooks {
+        fn get_clob_client(&self) -> &AsyncHttpClient {
+            &self.http
+        }
+
+        async fn get_orderbook_summary(&self, data: TokenId) -> Result<OrderbookSummary, ApiError> {
+            self.fetched.lock().unwrap().push(data.token_id.clone());
+            self.snapshots
+                .lock()
+                .unwrap()
+                .get(&data.token_id)
+                .cloned()
+                .ok_or_else(|| ApiError::Unexpected("unknown asset".to_string()))
+        }
+
+        async fn post_orderbook_summaries(
+            &self,
+            data: Vec<TokenId>,
+        ) -> Result<Vec<OrderbookSummary>, ApiError> {
+            let mut summaries = vec![];
+            for token in data {
+                summaries.push(self.get_orderbook_summary(token).await?);
+            }
+            Ok(summaries)
+        }
+    }
+
+    fn level(price: &str, size: &str) -> OrderSummary {
+        OrderSummary {
+            price: price.to_string(),
+            size: size.to_string(),
+        }
+    }
+
+    fn summary(asset_id: &str, timestamp: &str, hash: &str) -> OrderbookSummary {
+        OrderbookSummary {
+            market: "0xmarket".to_string(),
+            asset_id: asset_id.to_string(),
+            timestamp: timestamp.to_string(),
+            hash: hash.to_string(),
+            // The API lists bids ascending and asks descending
+            bids: vec![level("0.45", "100"), level("0.48", "50")],
+            asks: vec![level("0.55", "80"), level("0.52", "20")],
+            min_order_size: "5".to_string(),
+            tick_size: "0.01".to_string(),
+            neg_risk: false,
+        }
+    }
+
+    fn change(asset_id: &str, side: Side, price: &str, size: &str) -> PriceChange {
+        PriceChange {
+            asset_id: asset_id.to_string(),
+            price: price.to_string(),
+            size: size.to_string(),
+            side,
+            hash: Some("0xnext".to_string()),
+            best_bid: None,
+            best_ask: None,
+        }
+    }
+
+    fn ts(millis: i64) -> DateTime<Utc> {
+        DateTime::from_timestamp_millis(millis).unwrap()
+    }
+
+    #[test]
+    fn test_seed_from_summary() {
+        let book = LocalOrderBook::from_summary(&summary("1", "1000", "0xa")).unwrap();
+
+        assert_eq!(
+            book.best_bid(),
+            Some(PriceLevel {
+                price: dec!(0.48),
+                size: dec!(50)
+            })
+        );
+        assert_eq!(
+            book.best_ask(),
+            Some(PriceLevel {
+                price: dec!(0.52),
+                size: dec!(20)
+            })
+        );
+        assert_eq!(
+            book.bids().map(|level| level.price).collect::<Vec<_>>(),
+            vec![dec!(0.48), dec!(0.45)]
+        );
+        assert_eq!(
+            book.asks().map(|level| level.price).collect::<Vec<_>>(),
+            vec![dec!(0.52), dec!(0.55)]
+        );
+        assert_eq!(book.hash(), "0xa");
+        assert_eq!(book.timestamp(), ts(1000));
+    }
+
+    #[test]
+    fn test_seed_rejects_invalid_levels() {
+        let mut invalid = summary("1", "1000", "0xa");
+        invalid.bids.push(level("abc", "1"));
+
+        assert!(matches!(
+            LocalOrderBook::from_summary(&invalid),
+            Err(ApiError::Decode(_))
+        ));
+    }
+
+    #[test]
+    fn test_apply_price_change() {
+        let mut book = LocalOrderBook::from_summary(&summary("1", "1000", "0xa")).unwrap();
+
+        book.apply_price_change(&change("1", Side::BUY, "0.49", "10"), ts(1001))
+            .unwrap();
+        assert_eq!(book.best_bid().unwrap().price, dec!(0.49));
+
+        book.apply_price_change(&change("1", Side::SELL, "0.52", "0"), ts(1002))
+            .unwrap();
+        assert_eq!(book.best_ask().unwrap().price, dec!(0.55));
+        assert_eq!(book.hash(), "0xnext");
+        assert_eq!(book.timestamp(), ts(1002));
+    }
+
+    #[test]
+    fn test_stale_price_change_is_rejected() {
+        let mut book = LocalOrderBook::from_summary(&summary("1", "1000", "0xa")).unwrap();
+
+        let result = book.apply_price_change(&change("1", Side::BUY, "0.49", "10"), ts(999));
+
+        assert_eq!(
+            result,
+            Err(SequenceError::StaleTimestamp {
+                book: ts(1000),
+                update: ts(999)
+            })
+        );
+        assert_eq!(book.best_bid().unwrap().price, dec!(0.48));
+    }
+
+    #[test]
+    fn test_top_of_book_mismatch_is_detected() {
+        let mut book = LocalOrderBook::from_summary(&summary("1", "1000", "0xa")).unwrap();
+        let mut update = change("1", Side::BUY, "0.46", "10");
+        update.best_bid = Some("0.49".to_string());
+
+        let result = book.apply_price_change(&update, ts(1001));
+
+        assert_eq!(
+            result,
+            Err(SequenceError::TopOfBookMismatch {
+                side: Side::BUY,
+                expected: Some(dec!(0.49)),
+                actual: Some(dec!(0.48)),
+            })
+        );
+        // The rejected delta left the book untouched
+        assert_eq!(
+            book.bids().map(|level| level.price).collect::<Vec<_>>(),
+            vec![dec!(0.48), dec!(0.45)]
+        );
+        assert_eq!(book.hash(), "0xa");
+        assert_eq!(book.timestamp(), ts(1000));
+
+        let mut removal = change("1", Side::SELL, "0.52", "0");
+        removal.best_ask = Some("0.52".to_string());
+        assert!(book.apply_price_change(&removal, ts(1001)).is_err());
+        assert_eq!(
+            book.best_ask(),
+            Some(PriceLevel {
+                price: dec!(0.52),
+                size: dec!(20)
+            })
+        );
+    }
+
+    #[test]
+    fn test_conflicting_snapshot_hash_is_detected() {
+        let mut book = LocalOrderBook::from_summary(&summary("1", "1000", "0xa")).unwrap();
+        let message = BookMessage {
+            asset_id: "1".to_string(),
+            market: "0xmarket".to_string(),
+            bids: vec![],
+            asks: vec![],
+            timestamp: "1000".to_string(),
+            hash: "0xb".to_string(),
+        };
+
+        assert_eq!(
+            book.apply_snapshot(&message),
+            Err(SequenceError::HashMismatch {
+                expected: "0xa".to_string(),
+                actual: "0xb".to_string()
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn test_manager_resnapshots_out_of_sequence_books() {
+        let manager = BookManager::new(MockBooks::new(vec![
+            summary("1", "1000", "0xa"),
+            summary("2", "1000", "0xb"),
+        ]));
+        manager
+            .track(vec!["1".to_string(), "2".to_string()])
+            .await
+            .unwrap();
+
+        let mut diverged = change("1", Side::BUY, "0.47", "5");
+        diverged.best_bid = Some("0.47".to_string());
+        let event = MarketEvent::PriceChange(PriceChangeMessage {
+            market: "0xmarket".to_string(),
+            price_changes: vec![diverged, change("2", Side::BUY, "0.50", "5")],
+            timestamp: "1001".to_string(),
+        });
+        manager.apply(&event).await.unwrap();
+
+        // Book 1 was replaced by a fresh snapshot, book 2 kept the delta
+        assert_eq!(manager.best_bid("1").unwrap().price, dec!(0.48));
+        assert_eq!(manager.best_bid("2").unwrap().price, dec!(0.50));
+        assert_eq!(
+            *manager.client.fetched.lock().unwrap(),
+            vec!["1".to_string(), "2".to_string(), "1".to_string()]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_manager_ignores_stale_price_changes() {
+        let manager = BookManager::new(MockBooks::new(vec![summary("1", "1000", "0xa")]));
+        manager.track(vec!["1".to_string()]).await.unwrap();
+
+        let event = MarketEvent::PriceChange(PriceChangeMessage {
+            market: "0xmarket".to_string(),
+            price_changes: vec![change("1", Side::BUY, "0.49", "10")],
+            timestamp: "999".to_string(),
+        });
+        manager.apply(&event).await.unwrap();
+
+        assert_eq!(manager.best_bid("1").unwrap().price, dec!(0.48));
+        assert_eq!(manager.client.fetched.lock().unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_manager_resnapshots_on_gap() {
+        let manager = BookManager::new(MockBooks::new(vec![summary("1", "1000", "0xa")]));
+        manager.track(vec!["1".to_string()]).await.unwrap();
+        let reader = manager.clone();
+
+        manager
+            .handle(&WsEvent::Gap {
+                asset_ids: vec!["1".to_string(), "untracked".to_string()],
+                from: None,
+                to: Utc::now(),
+            })
+            .await
+            .unwrap();
+
+        assert_eq!(manager.client.fetched.lock().unwrap().len(), 2);
+        assert_eq!(
+            reader.top_of_book("1"),
+            Some((
+                Some(PriceLevel {
+                    price: dec!(0.48),
+                    size: dec!(50)
+                }),
+                Some(PriceLevel {
+                    price: dec!(0.52),
+                    size: dec!(20)
+                })
+            ))
+        );
+        assert!(reader.top_of_book("untracked").is_none());
+    }
+}
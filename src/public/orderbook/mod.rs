@@ -1,10 +1,17 @@
 // use reqwest;
 
 use crate::shared::{ApiError, QueryParams, TokenId, client::AsyncHttpClient};
+pub mod analytics;
+pub mod complement;
+pub mod local;
 pub mod models;
 use crate::clob_client::orders::models::NegRiskResponse;
 use async_trait::async_trait;
-pub use models::{Order, OrderbookRequestDTO, OrderbookSummary};
+pub use complement::{CombinedBook, CombinedLevel, LevelSource};
+pub use local::{BookManager, LocalOrderBook, SequenceError};
+pub use models::{
+    OrderBookSnapshot, OrderSummary, OrderbookRequestDTO, OrderbookSummary, PriceLevel,
+};
 
 #[async_trait]
 pub trait OrderBook {
@@ -23,6 +30,46 @@ pub trait OrderBook {
         Ok(orderbook)
     }
 
+    /// Get the parsed orderbook for a given token id
+    /// # Arguments
+    /// * `data` - The token id to get the orderbook for
+    /// # Returns
+    /// * `Result<OrderBookSnapshot, ApiError>` - The orderbook with decimal levels, best first
+    async fn get_orderbook_snapshot(&self, data: TokenId) -> Result<OrderBookSnapshot, ApiError> {
+        let summary = self.get_orderbook_summary(data).await?;
+        OrderBookSnapshot::try_from(summary)
+    }
+
+    /// Get the combined book of an outcome of a binary market.
+    /// See [`complement::combine`] for how the two books are merged
+    /// # Arguments
+    /// * `data` - The token id of the outcome
+    /// * `complement` - The token id of the other outcome of the same market
+    /// # Returns
+    /// * `Result<CombinedBook, ApiError>` - The book of `data` merged with the mirrored book of `complement`
+    async fn get_combined_orderbook(
+        &self,
+        data: TokenId,
+        complement: TokenId,
+    ) -> Result<CombinedBook, ApiError> {
+        let books = self
+            .post_orderbook_snapshots(vec![data.clone(), complement])
+            .await?;
+        let book = books
+            .iter()
+            .find(|book| book.asset_id == data.token_id)
+            .ok_or_else(|| {
+                ApiError::Unexpected(format!("No book returned for {}", data.token_id))
+            })?;
+        let complement = books
+            .iter()
+            .find(|other| other.asset_id != data.token_id)
+            .ok_or_else(|| {
+                ApiError::Unexpected("No book returned for the complement".to_string())
+            })?;
+        complement::combine(book, complement)
+    }
+
     /// Get the neg_risk value for a given token id
     /// # Arguments
     /// * `data` - The token id to get the neg_risk value for
@@ -51,6 +98,22 @@ pub trait OrderBook {
         let orderbook: Vec<OrderbookSummary> = response.json().await?;
         Ok(orderbook)
     }
+
+    /// Get the parsed orderbooks for a given list of token ids
+    /// # Arguments
+    /// * `data` - The list of token ids to get the orderbooks for
+    /// # Returns
+    /// * `Result<Vec<OrderBookSnapshot>, ApiError>` - The orderbooks with decimal levels, best first
+    async fn post_orderbook_snapshots(
+        &self,
+        data: Vec<TokenId>,
+    ) -> Result<Vec<OrderBookSnapshot>, ApiError> {
+        let summaries = self.post_orderbook_summaries(data).await?;
+        summaries
+            .into_iter()
+            .map(OrderBookSnapshot::try_from)
+            .collect()
+    }
 }
 
 #[cfg(test)]
@@ -58,6 +121,73 @@ mod tests {
     use super::*;
     use crate::public::PubClient;
 
+    fn wire_summary() -> OrderbookSummary {
+        let level = |price: &str, size: &str| OrderSummary {
+            price: price.to_string(),
+            size: size.to_string(),
+        };
+        OrderbookSummary {
+            market: String::from("0xmarket"),
+            asset_id: String::from("1"),
+            timestamp: String::from("1700000000123"),
+            hash: String::from("0xhash"),
+            bids: vec![
+                level("0.01", "1000"),
+                level("0.450", "20.5"),
+                level("0.48", "7"),
+            ],
+            asks: vec![
+                level("0.99", "1000"),
+                level("0.55", "3"),
+                level("0.52", "12"),
+            ],
+            min_order_size: String::from("5"),
+            tick_size: String::from("0.01"),
+            neg_risk: true,
+        }
+    }
+
+    #[test]
+    fn test_snapshot_sorts_levels_best_first() {
+        let snapshot = OrderBookSnapshot::try_from(wire_summary()).unwrap();
+
+        let bids: Vec<String> = snapshot.bids.iter().map(|l| l.price.to_string()).collect();
+        let asks: Vec<String> = snapshot.asks.iter().map(|l| l.price.to_string()).collect();
+        assert_eq!(bids, vec!["0.48", "0.450", "0.01"]);
+        assert_eq!(asks, vec!["0.52", "0.55", "0.99"]);
+        assert_eq!(snapshot.best_bid().unwrap().size.to_string(), "7");
+        assert_eq!(snapshot.tick_size.to_string(), "0.01");
+        assert_eq!(snapshot.timestamp.timestamp_millis(), 1700000000123);
+    }
+
+    #[test]
+    fn test_snapshot_round_trips_to_wire_format() {
+        let summary = wire_summary();
+        let round_trip = OrderbookSummary::from(OrderBookSnapshot::try_from(&summary).unwrap());
+
+        assert_eq!(
+            serde_json::to_value(&round_trip).unwrap(),
+            serde_json::to_value(&summary).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_snapshot_rejects_invalid_values() {
+        let mut summary = wire_summary();
+        summary.tick_size = String::from("tick");
+        assert!(matches!(
+            OrderBookSnapshot::try_from(&summary),
+            Err(ApiError::Decode(_))
+        ));
+
+        let mut summary = wire_summary();
+        summary.timestamp = String::from("yesterday");
+        assert!(matches!(
+            OrderBookSnapshot::try_from(&summary),
+            Err(ApiError::Decode(_))
+        ));
+    }
+
     #[tokio::test]
     async fn test_get_orderbook_summary() {
         let client = PubClient::new();
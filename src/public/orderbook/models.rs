@@ -1,9 +1,12 @@
-use crate::shared::TokenId;
+use crate::shared::{ApiError, TokenId};
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
+use std::str::FromStr;
 
-/// Represents a single order (bid or ask)
+/// Represents a single aggregated level (bid or ask) as sent by the API
 #[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct Order {
+pub struct OrderSummary {
     pub price: String,
     pub size: String,
 }
@@ -15,8 +18,8 @@ pub struct OrderbookSummary {
     pub asset_id: String,
     pub timestamp: String,
     pub hash: String,
-    pub bids: Vec<Order>,
-    pub asks: Vec<Order>,
+    pub bids: Vec<OrderSummary>,
+    pub asks: Vec<OrderSummary>,
     pub min_order_size: String,
     pub tick_size: String,
     pub neg_risk: bool,
@@ -27,3 +30,123 @@ pub struct OrderbookSummary {
 pub struct OrderbookRequestDTO {
     pub token_ids: Vec<TokenId>,
 }
+
+/// A single aggregated price level of a book
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PriceLevel {
+    pub price: Decimal,
+    pub size: Decimal,
+}
+
+impl TryFrom<&OrderSummary> for PriceLevel {
+    type Error = ApiError;
+
+    fn try_from(level: &OrderSummary) -> Result<Self, Self::Error> {
+        Ok(Self {
+            price: parse_decimal(&level.price)?,
+            size: parse_decimal(&level.size)?,
+        })
+    }
+}
+
+impl From<PriceLevel> for OrderSummary {
+    fn from(level: PriceLevel) -> Self {
+        Self {
+            price: level.price.to_string(),
+            size: level.size.to_string(),
+        }
+    }
+}
+
+/// A parsed [`OrderbookSummary`] with decimal prices and sizes.
+///
+/// Levels are ordered best first: bids by descending price and asks by
+/// ascending price. The API sends both sides in the opposite order.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OrderBookSnapshot {
+    pub market: String,
+    pub asset_id: String,
+    pub timestamp: DateTime<Utc>,
+    pub hash: String,
+    pub bids: Vec<PriceLevel>,
+    pub asks: Vec<PriceLevel>,
+    pub min_order_size: Decimal,
+    pub tick_size: Decimal,
+    pub neg_risk: bool,
+}
+
+impl OrderBookSnapshot {
+    pub fn best_bid(&self) -> Option<PriceLevel> {
+        self.bids.first().copied()
+    }
+
+    pub fn best_ask(&self) -> Option<PriceLevel> {
+        self.asks.first().copied()
+    }
+}
+
+impl TryFrom<&OrderbookSummary> for OrderBookSnapshot {
+    type Error = ApiError;
+
+    fn try_from(summary: &OrderbookSummary) -> Result<Self, Self::Error> {
+        let mut bids = parse_levels(&summary.bids)?;
+        let mut asks = parse_levels(&summary.asks)?;
+        bids.sort_by_key(|level| std::cmp::Reverse(level.price));
+        asks.sort_by_key(|level| level.price);
+
+        Ok(Self {
+            market: summary.market.clone(),
+            asset_id: summary.asset_id.clone(),
+            timestamp: parse_timestamp_millis(&summary.timestamp)?,
+            hash: summary.hash.clone(),
+            bids,
+            asks,
+            min_order_size: parse_decimal(&summary.min_order_size)?,
+            tick_size: parse_decimal(&summary.tick_size)?,
+            neg_risk: summary.neg_risk,
+        })
+    }
+}
+
+impl TryFrom<OrderbookSummary> for OrderBookSnapshot {
+    type Error = ApiError;
+
+    fn try_from(summary: OrderbookSummary) -> Result<Self, Self::Error> {
+        Self::try_from(&summary)
+    }
+}
+
+/// Converts back to the wire format, restoring the API's level order
+impl From<OrderBookSnapshot> for OrderbookSummary {
+    fn from(snapshot: OrderBookSnapshot) -> Self {
+        Self {
+            market: snapshot.market,
+            asset_id: snapshot.asset_id,
+            timestamp: snapshot.timestamp.timestamp_millis().to_string(),
+            hash: snapshot.hash,
+            bids: snapshot.bids.into_iter().rev().map(Into::into).collect(),
+            asks: snapshot.asks.into_iter().rev().map(Into::into).collect(),
+            min_order_size: snapshot.min_order_size.to_string(),
+            tick_size: snapshot.tick_size.to_string(),
+            neg_risk: snapshot.neg_risk,
+        }
+    }
+}
+
+pub(crate) fn parse_decimal(value: &str) -> Result<Decimal, ApiError> {
+    Decimal::from_str(value)
+        .map_err(|error| ApiError::Decode(format!("Invalid decimal {value:?}: {error}")))
+}
+
+/// Parse a millisecond timestamp as sent by the CLOB
+pub(crate) fn parse_timestamp_millis(value: &str) -> Result<DateTime<Utc>, ApiError> {
+    value
+        .parse::<i64>()
+        .ok()
+        .and_then(DateTime::from_timestamp_millis)
+        .ok_or_else(|| ApiError::Decode(format!("Invalid timestamp {value:?}")))
+}
+
+pub(crate) fn parse_levels(levels: &[OrderSummary]) -> Result<Vec<PriceLevel>, ApiError> {
+    levels.iter().map(PriceLevel::try_from).collect()
+}
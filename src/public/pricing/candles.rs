@@ -0,0 +1,152 @@
+use chrono::{DateTime, TimeDelta, Utc};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+use super::models::{HistoryItem, PricesHistory};
+use crate::shared::ApiError;
+
+/// The open, high, low and close price of one time bucket
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Candle {
+    /// The start of the bucket, a multiple of the bucket size since the Unix epoch
+    pub start: DateTime<Utc>,
+    pub open: Decimal,
+    pub high: Decimal,
+    pub low: Decimal,
+    pub close: Decimal,
+    /// The number of points in the bucket
+    pub points: usize,
+}
+
+impl Candle {
+    fn new(start: DateTime<Utc>, price: Decimal) -> Self {
+        Self {
+            start,
+            open: price,
+            high: price,
+            low: price,
+            close: price,
+            points: 1,
+        }
+    }
+
+    fn push(&mut self, price: Decimal) {
+        self.high = self.high.max(price);
+        self.low = self.low.min(price);
+        self.close = price;
+        self.points += 1;
+    }
+}
+
+/// Resample a series of points into candles of `bucket` length.
+///
+/// Points are ordered by time first, so the input does not need to be sorted.
+/// Buckets without any point are left out rather than filled forward.
+/// # Returns
+/// * `Result<Vec<Candle>, ApiError>` - The candles in time order, or an [`ApiError`] if `bucket` is shorter than a millisecond
+pub fn resample(points: &[HistoryItem], bucket: TimeDelta) -> Result<Vec<Candle>, ApiError> {
+    let size = bucket.num_milliseconds();
+    if size <= 0 {
+        return Err(ApiError::Unexpected(format!(
+            "Candle bucket must be at least one millisecond, got {bucket}"
+        )));
+    }
+
+    let mut sorted: Vec<&HistoryItem> = points.iter().collect();
+    sorted.sort_by_key(|point| point.timestamp);
+
+    let mut candles: Vec<Candle> = Vec::new();
+    for point in sorted {
+        let millis = point.timestamp.timestamp_millis();
+        let start = DateTime::from_timestamp_millis(millis - millis.rem_euclid(size))
+            .expect("bucket start is within range of the point");
+        match candles.last_mut() {
+            Some(candle) if candle.start == start => candle.push(point.price),
+            _ => candles.push(Candle::new(start, point.price)),
+        }
+    }
+    Ok(candles)
+}
+
+impl PricesHistory {
+    /// Resample the history into candles of `bucket` length. See [`resample`]
+    pub fn candles(&self, bucket: TimeDelta) -> Result<Vec<Candle>, ApiError> {
+        resample(&self.history, bucket)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal::dec;
+
+    fn point(timestamp: i64, price: Decimal) -> HistoryItem {
+        HistoryItem {
+            timestamp: DateTime::from_timestamp(timestamp, 0).unwrap(),
+            price,
+        }
+    }
+
+    #[test]
+    fn test_resample_into_candles() {
+        let history = PricesHistory {
+            history: vec![
+                point(3_600, dec!(0.50)),
+                point(3_660, dec!(0.55)),
+                point(4_000, dec!(0.45)),
+                point(7_199, dec!(0.48)),
+                point(10_800, dec!(0.60)),
+            ],
+        };
+
+        let candles = history.candles(TimeDelta::hours(1)).unwrap();
+
+        assert_eq!(candles.len(), 2);
+        assert_eq!(candles[0].start.timestamp(), 3_600);
+        assert_eq!(
+            (
+                candles[0].open,
+                candles[0].high,
+                candles[0].low,
+                candles[0].close
+            ),
+            (dec!(0.50), dec!(0.55), dec!(0.45), dec!(0.48))
+        );
+        assert_eq!(candles[0].points, 4);
+        // The bucket starting at 7200 has no point and is skipped
+        assert_eq!(candles[1].start.timestamp(), 10_800);
+        assert_eq!(candles[1].open, dec!(0.60));
+        assert_eq!(candles[1].points, 1);
+    }
+
+    #[test]
+    fn test_resample_sorts_points() {
+        let points = vec![
+            point(120, dec!(0.3)),
+            point(0, dec!(0.1)),
+            point(60, dec!(0.2)),
+        ];
+
+        let candles = resample(&points, TimeDelta::minutes(5)).unwrap();
+
+        assert_eq!(candles.len(), 1);
+        assert_eq!(candles[0].open, dec!(0.1));
+        assert_eq!(candles[0].close, dec!(0.3));
+    }
+
+    #[test]
+    fn test_resample_rejects_empty_bucket() {
+        let points = [point(0, dec!(0.5))];
+
+        for bucket in [
+            TimeDelta::zero(),
+            TimeDelta::microseconds(10),
+            TimeDelta::minutes(-1),
+        ] {
+            assert!(matches!(
+                resample(&points, bucket),
+                Err(ApiError::Unexpected(_))
+            ));
+        }
+    }
+}
@@ -1,12 +1,37 @@
 use crate::shared::{ApiError, QueryParams, TokenId, client::AsyncHttpClient};
 use async_trait::async_trait;
+use serde::{Serialize, de::DeserializeOwned};
 
+pub mod candles;
 pub mod models;
+pub use candles::Candle;
 use models::{
-    BidAskSpreads, MarketPrice, MarketPriceDTO, MarketPriceSet, MidpointPrice, PriceHistoryDTO,
-    PricesHistory,
+    BidAskSpreads, LastTradePrice, MarketPrice, MarketPriceDTO, MarketPriceSet, MidpointPrice,
+    Midpoints, PriceHistoryDTO, PricesHistory, TokenLastTradePrice,
 };
 
+/// The most entries the CLOB accepts in the body of a batch request
+pub const MAX_BATCH_SIZE: usize = 500;
+
+/// Post `data` to `path` in chunks of at most [`MAX_BATCH_SIZE`] entries,
+/// returning one response per chunk
+async fn post_in_chunks<T, R>(
+    client: &AsyncHttpClient,
+    path: &str,
+    data: &[T],
+) -> Result<Vec<R>, ApiError>
+where
+    T: Serialize + Sync,
+    R: DeserializeOwned,
+{
+    let mut responses = Vec::with_capacity(data.len().div_ceil(MAX_BATCH_SIZE));
+    for chunk in data.chunks(MAX_BATCH_SIZE) {
+        let response = client.post(Some(path), Some(chunk), None, None).await?;
+        responses.push(response.json().await?);
+    }
+    Ok(responses)
+}
+
 #[async_trait]
 pub trait Pricing {
     fn get_clob_client(&self) -> &AsyncHttpClient;
@@ -24,7 +49,8 @@ pub trait Pricing {
         Ok(price)
     }
 
-    /// Post the market prices for a given list of token ids and sides
+    /// Post the market prices for a given list of token ids and sides.
+    /// Lists longer than [`MAX_BATCH_SIZE`] are sent in several requests
     /// # Arguments
     /// * `data` - The list of token ids and sides to post the market prices for
     /// # Returns
@@ -34,8 +60,13 @@ pub trait Pricing {
         data: Vec<MarketPriceDTO>,
     ) -> Result<MarketPriceSet, ApiError> {
         let client = self.get_clob_client();
-        let response = client.post(Some("/prices"), Some(data), None, None).await?;
-        let prices: MarketPriceSet = response.json().await?;
+        let chunks: Vec<MarketPriceSet> = post_in_chunks(client, "/prices", &data).await?;
+        let mut prices = MarketPriceSet::new();
+        for chunk in chunks {
+            for (token_id, sides) in chunk {
+                prices.entry(token_id).or_default().extend(sides);
+            }
+        }
         Ok(prices)
     }
 
@@ -52,12 +83,57 @@ pub trait Pricing {
         Ok(price)
     }
 
-    /// Get the price history for a given market
+    /// Get the midpoint prices for a given list of token ids.
+    /// Lists longer than [`MAX_BATCH_SIZE`] are sent in several requests
+    /// # Arguments
+    /// * `data` - The list of token ids to get the midpoint prices for
+    /// # Returns
+    /// * `Result<Midpoints, ApiError>` - A map of token ids to their midpoint price
+    async fn post_midpoints(&self, data: Vec<TokenId>) -> Result<Midpoints, ApiError> {
+        let client = self.get_clob_client();
+        let chunks: Vec<Midpoints> = post_in_chunks(client, "/midpoints", &data).await?;
+        Ok(chunks.into_iter().flatten().collect())
+    }
+
+    /// Get the price and side of the last trade of a given token id
+    /// # Arguments
+    /// * `data` - The token id to get the last trade price for
+    /// # Returns
+    /// * `Result<LastTradePrice, ApiError>` - The last trade price for the given token id
+    async fn get_last_trade_price(&self, data: TokenId) -> Result<LastTradePrice, ApiError> {
+        let client = self.get_clob_client();
+        let query = data.as_query_params();
+        let response = client
+            .get(Some("/last-trade-price"), Some(query), None)
+            .await?;
+        let price: LastTradePrice = response.json().await?;
+        Ok(price)
+    }
+
+    /// Get the last trade prices for a given list of token ids.
+    /// Lists longer than [`MAX_BATCH_SIZE`] are sent in several requests
+    /// # Arguments
+    /// * `data` - The list of token ids to get the last trade prices for
+    /// # Returns
+    /// * `Result<Vec<TokenLastTradePrice>, ApiError>` - The last trade price of each token id
+    async fn post_last_trade_prices(
+        &self,
+        data: Vec<TokenId>,
+    ) -> Result<Vec<TokenLastTradePrice>, ApiError> {
+        let client = self.get_clob_client();
+        let chunks: Vec<Vec<TokenLastTradePrice>> =
+            post_in_chunks(client, "/last-trades-prices", &data).await?;
+        Ok(chunks.into_iter().flatten().collect())
+    }
+
+    /// Get the price history for a given market.
+    /// Use [`PricesHistory::candles`] to resample it into OHLC candles
     /// # Arguments
-    /// * `data` - The price history query parameters
+    /// * `data` - The price history query parameters. See [`PriceHistoryDTO`] for available options.
     /// # Returns
-    /// * `Result<Vec<MarketPrice>, ApiError>` - The price history for the given market
+    /// * `Result<PricesHistory, ApiError>` - The price history for the given market, or an [`ApiError`] if the window is invalid or the request fails
     async fn get_price_history(&self, data: PriceHistoryDTO) -> Result<PricesHistory, ApiError> {
+        data.validate()?;
         let client = self.get_clob_client();
         let query = data.as_query_params();
         let response = client
@@ -67,24 +143,17 @@ pub trait Pricing {
         Ok(prices)
     }
 
-    /// Get bid-ask spreads for a list of token IDs and sides
+    /// Get bid-ask spreads for a list of token IDs.
+    /// Lists longer than [`MAX_BATCH_SIZE`] are sent in several requests
     /// # Arguments
-    /// * `data` - The list of token ids and sides to get spreads for
+    /// * `data` - The list of token ids to get spreads for
     /// # Returns
     /// * `Result<BidAskSpreads, ApiError>` - A map of token IDs to their spread values
-    async fn post_bid_ask_spreads(
-        &self,
-        data: Vec<MarketPriceDTO>,
-    ) -> Result<BidAskSpreads, ApiError> {
+    async fn post_bid_ask_spreads(&self, data: Vec<TokenId>) -> Result<BidAskSpreads, ApiError> {
         let client = self.get_clob_client();
-        let response = client
-            .post(Some("/spreads"), Some(data), None, None)
-            .await?;
-        let spreads: BidAskSpreads = response.json().await?;
-        Ok(spreads)
+        let chunks: Vec<BidAskSpreads> = post_in_chunks(client, "/spreads", &data).await?;
+        Ok(chunks.into_iter().flatten().collect())
     }
-
-    // async fn get_prices_history(&self, data: )
 }
 
 #[cfg(test)]
@@ -92,7 +161,13 @@ mod tests {
     use super::*;
     use crate::public::PubClient;
     use crate::shared::Side;
-    use chrono::Utc;
+    use crate::shared::query::pairs;
+    use chrono::{TimeDelta, TimeZone, Utc};
+    use models::PriceInterval;
+    use rust_decimal::dec;
+    use std::sync::{Arc, Mutex};
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::{TcpListener, TcpStream};
 
     #[tokio::test]
     async fn test_get_market_price() {
@@ -164,14 +239,15 @@ mod tests {
     async fn test_get_price_history() {
         let client = PubClient::new();
 
-        let start_ts = Utc::now().timestamp();
-        let data = PriceHistoryDTO {
-            market: "85229865481166262443616698813899475047082678584551624516576861283095641108073"
+        let end = Utc::now();
+        let data = PriceHistoryDTO::window(
+            "85229865481166262443616698813899475047082678584551624516576861283095641108073"
                 .to_string(),
-            start_ts: start_ts as u128,
-            end_ts: (start_ts + 10) as u128,
-            ..Default::default()
-        };
+            end - TimeDelta::days(1),
+            end,
+        )
+        .unwrap()
+        .with_fidelity(60);
 
         let price_history = client.get_price_history(data).await;
         dbg!(price_history.as_ref().err());
@@ -182,22 +258,196 @@ mod tests {
     async fn test_post_bid_ask_spreads() {
         let client = PubClient::new();
 
-        let data = vec![
-            MarketPriceDTO {
-                token_id:
-                    "85229865481166262443616698813899475047082678584551624516576861283095641108073"
-                        .to_string(),
-                side: Side::BUY,
-            },
-            MarketPriceDTO {
-                token_id:
-                    "85229865481166262443616698813899475047082678584551624516576861283095641108073"
-                        .to_string(),
-                side: Side::SELL,
-            },
-        ];
+        let data = vec![TokenId {
+            token_id:
+                "85229865481166262443616698813899475047082678584551624516576861283095641108073"
+                    .to_string(),
+        }];
 
         let bid_ask_spread = client.post_bid_ask_spreads(data).await;
         assert!(bid_ask_spread.is_ok());
     }
+
+    #[tokio::test]
+    async fn test_get_last_trade_price() {
+        let client = PubClient::new();
+
+        let data = TokenId {
+            token_id:
+                "85229865481166262443616698813899475047082678584551624516576861283095641108073"
+                    .to_string(),
+        };
+
+        let last_trade_price = client.get_last_trade_price(data).await;
+        if let Err(e) = &last_trade_price {
+            eprintln!("Last trade price error: {:?}", e);
+        }
+        assert!(last_trade_price.is_ok());
+    }
+
+    #[test]
+    fn test_price_history_query() {
+        let start = Utc.with_ymd_and_hms(2025, 1, 1, 0, 0, 0).unwrap();
+        let window = PriceHistoryDTO::window(String::from("1"), start, start + TimeDelta::hours(1))
+            .unwrap()
+            .with_fidelity(5);
+        assert_eq!(
+            window.as_query_params(),
+            pairs(&[
+                ("market", "1"),
+                ("startTs", "1735689600"),
+                ("endTs", "1735693200"),
+                ("fidelity", "5"),
+            ])
+        );
+
+        let interval = PriceHistoryDTO::interval(String::from("1"), PriceInterval::Day1);
+        assert_eq!(
+            interval.as_query_params(),
+            pairs(&[("market", "1"), ("interval", "1d")])
+        );
+    }
+
+    #[test]
+    fn test_price_history_validation() {
+        let start = Utc.with_ymd_and_hms(2025, 1, 1, 0, 0, 0).unwrap();
+        assert!(PriceHistoryDTO::window(String::from("1"), start, start).is_err());
+        assert!(
+            PriceHistoryDTO::interval(String::from("1"), PriceInterval::Max)
+                .with_fidelity(0)
+                .validate()
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_price_history_decodes_points() {
+        let history: PricesHistory =
+            serde_json::from_str(r#"{"history": [{"t": 1735689600, "p": 0.535}]}"#).unwrap();
+        assert_eq!(history.history[0].timestamp.timestamp(), 1735689600);
+        assert_eq!(history.history[0].price, dec!(0.535));
+    }
+
+    #[test]
+    fn test_batch_responses_decode_to_decimals() {
+        let midpoints: Midpoints = serde_json::from_str(r#"{"1": "0.455", "2": "0.545"}"#).unwrap();
+        assert_eq!(midpoints["1"], dec!(0.455));
+        assert_eq!(midpoints["2"], dec!(0.545));
+
+        let spreads: BidAskSpreads = serde_json::from_str(r#"{"1": "0.01"}"#).unwrap();
+        assert_eq!(spreads["1"], dec!(0.01));
+
+        let last_trades: Vec<TokenLastTradePrice> = serde_json::from_str(
+            r#"[{"token_id": "1", "price": "0.46", "side": "BUY"}, {"token_id": "2", "price": "0.5"}]"#,
+        )
+        .unwrap();
+        assert_eq!(last_trades[0].price, dec!(0.46));
+        assert_eq!(last_trades[0].side, Some(Side::BUY));
+        assert_eq!(last_trades[1].side, None);
+
+        let last_trade: LastTradePrice =
+            serde_json::from_str(r#"{"price": "0.54", "side": "SELL"}"#).unwrap();
+        assert_eq!(last_trade.price, dec!(0.54));
+    }
+
+    #[test]
+    fn test_last_trade_without_side_decodes_to_none() {
+        let last_trade: LastTradePrice =
+            serde_json::from_str(r#"{"price": "0", "side": ""}"#).unwrap();
+        assert_eq!(last_trade.side, None);
+
+        let last_trades: Vec<TokenLastTradePrice> = serde_json::from_str(
+            r#"[{"token_id": "1", "price": "0", "side": ""}, {"token_id": "2", "price": "0.5", "side": null}]"#,
+        )
+        .unwrap();
+        assert_eq!(last_trades[0].side, None);
+        assert_eq!(last_trades[1].side, None);
+
+        assert!(
+            serde_json::from_str::<LastTradePrice>(r#"{"price": "0.5", "side": "HOLD"}"#).is_err()
+        );
+    }
+
+    /// Serves the batch last trade prices endpoint on a local port, answering
+    /// each token of a batch in order and recording the size of every batch
+    async fn mock_last_trades_server() -> (String, Arc<Mutex<Vec<usize>>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let batches = Arc::new(Mutex::new(vec![]));
+        let recorded = Arc::clone(&batches);
+        tokio::spawn(async move {
+            loop {
+                let (mut stream, _) = listener.accept().await.unwrap();
+                let tokens: Vec<TokenId> =
+                    serde_json::from_slice(&read_request_body(&mut stream).await).unwrap();
+                recorded.lock().unwrap().push(tokens.len());
+                let trades: Vec<serde_json::Value> = tokens
+                    .iter()
+                    .map(|token| serde_json::json!({"token_id": token.token_id, "price": "0.5"}))
+                    .collect();
+                let body = serde_json::to_string(&trades).unwrap();
+                let response = format!(
+                    "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                    body.len()
+                );
+                stream.write_all(response.as_bytes()).await.unwrap();
+            }
+        });
+        (url, batches)
+    }
+
+    async fn read_request_body(stream: &mut TcpStream) -> Vec<u8> {
+        let mut request = vec![];
+        let mut buffer = [0u8; 4096];
+        loop {
+            let read = stream.read(&mut buffer).await.unwrap();
+            assert!(read > 0, "connection closed mid-request");
+            request.extend_from_slice(&buffer[..read]);
+            let text = String::from_utf8_lossy(&request);
+            if let Some(end) = text.find("\r\n\r\n") {
+                let length: usize = text[..end]
+                    .lines()
+                    .find_map(|line| {
+                        let (name, value) = line.split_once(':')?;
+                        name.eq_ignore_ascii_case("content-length")
+                            .then(|| value.trim().parse().unwrap())
+                    })
+                    .unwrap_or(0);
+                if request.len() >= end + 4 + length {
+                    return request[end + 4..end + 4 + length].to_vec();
+                }
+            }
+        }
+    }
+
+    struct MockPricing {
+        http: AsyncHttpClient,
+    }
+
+    impl Pricing for MockPricing {
+        fn get_clob_client(&self) -> &AsyncHttpClient {
+            &self.http
+        }
+    }
+
+    #[tokio::test]
+    async fn test_batches_are_chunked_and_joined_in_order() {
+        let (url, batches) = mock_last_trades_server().await;
+        let client = MockPricing {
+            http: AsyncHttpClient::new(url, None),
+        };
+        let tokens: Vec<TokenId> = (0..1_200)
+            .map(|index| TokenId::from(index.to_string()))
+            .collect();
+
+        let trades = client.post_last_trade_prices(tokens.clone()).await.unwrap();
+
+        assert_eq!(
+            *batches.lock().unwrap(),
+            vec![MAX_BATCH_SIZE, MAX_BATCH_SIZE, 200]
+        );
+        let token_ids: Vec<String> = trades.into_iter().map(|trade| trade.token_id).collect();
+        let expected: Vec<String> = tokens.into_iter().map(|token| token.token_id).collect();
+        assert_eq!(token_ids, expected);
+    }
 }
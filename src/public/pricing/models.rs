@@ -1,13 +1,16 @@
-use std::collections::HashMap;
+use std::{collections::HashMap, fmt};
 
-use crate::shared::{QueryParams, Side};
-use serde::{Deserialize, Serialize, Serializer};
+use crate::shared::{ApiError, QueryParams, Side, query::QueryBuilder};
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use serde::de::IntoDeserializer;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
 /// Represent a Set of Market Prices where each key is the token id
 /// and their values are a hashmap of the buy and sell position at that moment
 pub type MarketPriceSet = HashMap<String, HashMap<String, String>>;
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, QueryParams)]
 pub struct MarketPriceDTO {
     pub token_id: String,
     pub side: Side,
@@ -23,23 +26,58 @@ pub struct PricesHistory {
     pub history: Vec<HistoryItem>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+/// One point of a price history
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct HistoryItem {
-    pub t: u128,
-    pub p: f64,
+    #[serde(rename = "t", with = "chrono::serde::ts_seconds")]
+    pub timestamp: DateTime<Utc>,
+    #[serde(rename = "p")]
+    pub price: Decimal,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MidpointPrice {
-    pub mid: String,
+    pub mid: Decimal,
 }
 
+/// Represents midpoint prices for multiple token IDs
+/// Maps token ID (String) to midpoint price
+pub type Midpoints = HashMap<String, Decimal>;
+
 /// Represents bid-ask spreads for multiple token IDs
-/// Maps token ID (String) to spread value (String)
-/// This is a type alias for clarity - the API returns a JSON object
-pub type BidAskSpreads = HashMap<String, String>;
+/// Maps token ID (String) to spread value
+pub type BidAskSpreads = HashMap<String, Decimal>;
 
-#[derive(Debug, Clone)]
+/// The price and side of the last trade of a token
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LastTradePrice {
+    pub price: Decimal,
+    #[serde(default, deserialize_with = "empty_side_as_none")]
+    pub side: Option<Side>,
+}
+
+/// The last trade of a token, as returned by the batch endpoint
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TokenLastTradePrice {
+    pub token_id: String,
+    pub price: Decimal,
+    #[serde(default, deserialize_with = "empty_side_as_none")]
+    pub side: Option<Side>,
+}
+
+/// Decode a missing, null or empty side as `None`. The API sends `""` for a
+/// token that has not traded yet
+fn empty_side_as_none<'de, D>(deserializer: D) -> Result<Option<Side>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    match Option::<String>::deserialize(deserializer)?.as_deref() {
+        None | Some("") => Ok(None),
+        Some(side) => Side::deserialize(side.into_deserializer()).map(Some),
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum PriceInterval {
     Minute1,
     Hour1,
@@ -61,6 +99,12 @@ impl PriceInterval {
     }
 }
 
+impl fmt::Display for PriceInterval {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
 impl Serialize for PriceInterval {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
@@ -88,41 +132,84 @@ impl<'de> Deserialize<'de> for PriceInterval {
     }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
-#[serde(rename_all = "camelCase")]
+/// The time range of a price history. The API takes either an explicit
+/// window or an interval ending now, never both
+#[derive(Debug, Clone, PartialEq)]
+pub enum PriceHistoryRange {
+    Window {
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    },
+    Interval(PriceInterval),
+}
+
+#[derive(Debug, Clone)]
 pub struct PriceHistoryDTO {
+    /// The token id to get the price history of
     pub market: String,
-    pub start_ts: u128,
-    pub end_ts: u128,
-    pub interval: Option<PriceInterval>,
-    pub fidelity: Option<u128>,
-}
-
-impl QueryParams for MarketPriceDTO {
-    fn as_query_params(&self) -> HashMap<String, String> {
-        HashMap::from([
-            ("token_id".to_string(), self.token_id.clone()),
-            ("side".to_string(), self.side.as_str().to_string()),
-        ])
-    }
+    pub range: PriceHistoryRange,
+    /// The resolution of the points in minutes
+    pub fidelity: Option<u32>,
 }
 
-impl QueryParams for PriceHistoryDTO {
-    fn as_query_params(&self) -> HashMap<String, String> {
-        let mut params = HashMap::new();
-
-        params.insert("market".to_string(), self.market.clone());
-        params.insert("startTs".to_string(), self.start_ts.to_string());
-        params.insert("endTs".to_string(), self.end_ts.to_string());
+impl PriceHistoryDTO {
+    /// The price history of `market` between `start` and `end`
+    pub fn window(
+        market: String,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    ) -> Result<Self, ApiError> {
+        let dto = Self {
+            market,
+            range: PriceHistoryRange::Window { start, end },
+            fidelity: None,
+        };
+        dto.validate()?;
+        Ok(dto)
+    }
 
-        if let Some(interval) = &self.interval {
-            params.insert("interval".to_string(), interval.as_str().to_string());
+    /// The price history of `market` over the `interval` ending now
+    pub fn interval(market: String, interval: PriceInterval) -> Self {
+        Self {
+            market,
+            range: PriceHistoryRange::Interval(interval),
+            fidelity: None,
         }
+    }
+
+    /// Set the resolution of the points in minutes
+    pub fn with_fidelity(mut self, minutes: u32) -> Self {
+        self.fidelity = Some(minutes);
+        self
+    }
 
-        if let Some(fidelity) = self.fidelity {
-            params.insert("fidelity".to_string(), fidelity.to_string());
+    /// Check that the window is not empty and the fidelity is not zero
+    pub fn validate(&self) -> Result<(), ApiError> {
+        if let PriceHistoryRange::Window { start, end } = &self.range
+            && start >= end
+        {
+            return Err(ApiError::Unexpected(format!(
+                "Price history window must start before it ends, got {start} to {end}"
+            )));
+        }
+        if self.fidelity == Some(0) {
+            return Err(ApiError::Unexpected(
+                "Price history fidelity must be at least one minute".to_string(),
+            ));
         }
+        Ok(())
+    }
+}
 
-        params
+impl QueryParams for PriceHistoryDTO {
+    fn as_query_params(&self) -> Vec<(String, String)> {
+        let query = QueryBuilder::new().param("market", &self.market);
+        let query = match &self.range {
+            PriceHistoryRange::Window { start, end } => query
+                .param("startTs", start.timestamp())
+                .param("endTs", end.timestamp()),
+            PriceHistoryRange::Interval(interval) => query.param("interval", interval),
+        };
+        query.opt("fidelity", self.fidelity).build()
     }
 }
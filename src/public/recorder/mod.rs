@@ -0,0 +1,481 @@
+//! Recording market data to disk and replaying it.
+//!
+//! A [`Recorder`] subscribes to the market channel for a set of tokens and
+//! writes every book, price change, trade and tick size change it receives,
+//! stamped with the time it arrived, to gzip compressed newline-delimited JSON
+//! files that rotate by size and age. It can also fetch full books through the
+//! public client at a fixed interval.
+//!
+//! [`replay`] reads the files back as a [`Stream`](futures::Stream) of the same
+//! [`MarketEvent`]s, unpaced, at the recorded pace or accelerated, e.g. to feed
+//! a [`BookManager`](crate::public::orderbook::BookManager) in a backtest.
+//!
+//! # Key Types
+//!
+//! - [`Recorder`]: Records the market channel in a background task.
+//! - [`RotatingWriter`]: Writes events to rotating `.ndjson.gz` files.
+//! - [`replay`]: Reads the files back, see [`ReplaySpeed`].
+
+pub mod replay;
+pub mod writer;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use std::time::Duration;
+use tokio::sync::mpsc::{self, UnboundedReceiver};
+use tokio::sync::oneshot;
+use tokio::task::JoinHandle;
+use tokio::time::MissedTickBehavior;
+
+pub use replay::{ReplaySpeed, replay};
+pub use writer::{RECORDING_EXTENSION, RotatingWriter, Rotation, recording_files};
+
+use crate::public::PubClient;
+use crate::public::orderbook::OrderBook;
+use crate::public::websocket::{MarketEvent, MarketSocket, WsConfig, WsEvent};
+use crate::shared::{ApiError, TokenId};
+
+/// A market channel event and when it was received
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecordedEvent {
+    pub recorded_at: DateTime<Utc>,
+    pub event: MarketEvent,
+}
+
+impl RecordedEvent {
+    /// Stamp an event with the current time
+    pub fn now(event: MarketEvent) -> Self {
+        Self {
+            recorded_at: Utc::now(),
+            event,
+        }
+    }
+}
+
+/// Settings of a [`Recorder`]
+/// # Fields
+/// * `asset_ids` - The token ids to record
+/// * `directory` - Where the files are written
+/// * `prefix` - The start of every file name, to keep several recordings in one directory
+/// * `rotation` - When to start a new file. See [`Rotation`]
+/// * `flush_interval` - How often the open file is flushed, which bounds what a crash loses
+/// * `snapshot_interval` - Also fetch the full books of `asset_ids` over HTTP this often
+/// * `ws` - The market channel connection. See [`WsConfig`]
+#[derive(Debug, Clone)]
+pub struct RecorderConfig {
+    pub asset_ids: Vec<String>,
+    pub directory: PathBuf,
+    pub prefix: String,
+    pub rotation: Rotation,
+    pub flush_interval: Duration,
+    pub snapshot_interval: Option<Duration>,
+    pub ws: WsConfig,
+}
+
+impl RecorderConfig {
+    /// Record `asset_ids` into `directory` with the default settings
+    pub fn new(asset_ids: Vec<String>, directory: impl Into<PathBuf>) -> Self {
+        Self {
+            asset_ids,
+            directory: directory.into(),
+            prefix: "market".to_string(),
+            rotation: Rotation::default(),
+            flush_interval: Duration::from_secs(1),
+            snapshot_interval: None,
+            ws: WsConfig::default(),
+        }
+    }
+}
+
+/// What a [`Recorder`] wrote
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RecordingSummary {
+    pub events: u64,
+    /// Every file written, oldest first
+    pub files: Vec<PathBuf>,
+    /// Book fetches that failed and were skipped
+    pub snapshot_errors: u64,
+}
+
+/// Handle to a recording running in a background task.
+///
+/// The recording runs until [`Recorder::stop`] is called or its event source
+/// ends. Dropping the handle aborts it, the events already received are
+/// still written and the last file finished in the background.
+#[derive(Debug)]
+pub struct Recorder {
+    stop: Option<oneshot::Sender<()>>,
+    task: JoinHandle<Result<RecordingSummary, ApiError>>,
+}
+
+impl Recorder {
+    /// Connect to the market channel and record `config.asset_ids`
+    /// # Arguments
+    /// * `config` - The recording settings. See [`RecorderConfig`]
+    /// # Returns
+    /// * `Result<Recorder, ApiError>` - The running recorder, or an [`ApiError`] if the directory cannot be created
+    pub fn start(config: RecorderConfig) -> Result<Self, ApiError> {
+        let writer = RotatingWriter::new(&config.directory, &config.prefix, config.rotation)?;
+        let (socket, events) = MarketSocket::connect(config.ws.clone(), config.asset_ids.clone());
+        Ok(Self::spawn(config, writer, events, Some(socket)))
+    }
+
+    /// Record the market events of an existing connection, or of any other
+    /// source of [`WsEvent`]s. `config.ws` is not used
+    pub fn record(
+        config: RecorderConfig,
+        events: UnboundedReceiver<WsEvent>,
+    ) -> Result<Self, ApiError> {
+        let writer = RotatingWriter::new(&config.directory, &config.prefix, config.rotation)?;
+        Ok(Self::spawn(config, writer, events, None))
+    }
+
+    /// Stop recording, finish the last file and wait for the task to end
+    /// # Returns
+    /// * `Result<RecordingSummary, ApiError>` - What was written, or an [`ApiError`] if a file could not be written
+    pub async fn stop(mut self) -> Result<RecordingSummary, ApiError> {
+        if let Some(stop) = self.stop.take() {
+            let _ = stop.send(());
+        }
+        (&mut self.task)
+            .await
+            .map_err(|error| ApiError::Unexpected(format!("Recorder task failed: {error}")))?
+    }
+
+    /// Whether the recording has ended on its own, after its event source
+    /// closed or a write failed
+    pub fn is_finished(&self) -> bool {
+        self.task.is_finished()
+    }
+
+    fn spawn(
+        config: RecorderConfig,
+        writer: RotatingWriter,
+        events: UnboundedReceiver<WsEvent>,
+        socket: Option<MarketSocket>,
+    ) -> Self {
+        let (stop, stopped) = oneshot::channel();
+        let task = tokio::spawn(run(config, writer, events, socket, stopped));
+        Self {
+            stop: Some(stop),
+            task,
+        }
+    }
+}
+
+impl Drop for Recorder {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+/// What the recording task asks of the writer thread
+enum WriterCommand {
+    Write(RecordedEvent),
+    Flush,
+}
+
+/// Own the writer on a blocking thread, so file I/O and compression never
+/// stall the runtime. Ends on the first failed write, or once every command
+/// is handled and the recording task has hung up
+fn write_all(
+    mut writer: RotatingWriter,
+    mut commands: UnboundedReceiver<WriterCommand>,
+) -> Result<Vec<PathBuf>, ApiError> {
+    while let Some(command) = commands.blocking_recv() {
+        match command {
+            WriterCommand::Write(event) => writer.write(&event)?,
+            WriterCommand::Flush => writer.flush()?,
+        }
+    }
+    writer.finish()
+}
+
+async fn run(
+    config: RecorderConfig,
+    writer: RotatingWriter,
+    mut events: UnboundedReceiver<WsEvent>,
+    socket: Option<MarketSocket>,
+    mut stopped: oneshot::Receiver<()>,
+) -> Result<RecordingSummary, ApiError> {
+    let client = PubClient::new();
+    let token_ids: Vec<TokenId> = config
+        .asset_ids
+        .iter()
+        .map(|asset_id| TokenId::from(asset_id.clone()))
+        .collect();
+    let mut flush = tokio::time::interval(config.flush_interval);
+    flush.set_missed_tick_behavior(MissedTickBehavior::Delay);
+    let mut snapshots = config.snapshot_interval.map(|period| {
+        let mut snapshots = tokio::time::interval(period);
+        snapshots.set_missed_tick_behavior(MissedTickBehavior::Delay);
+        snapshots
+    });
+    let mut summary = RecordingSummary {
+        events: 0,
+        files: vec![],
+        snapshot_errors: 0,
+    };
+    let (commands, received) = mpsc::unbounded_channel();
+    let writing = tokio::task::spawn_blocking(move || write_all(writer, received));
+
+    loop {
+        let queued = tokio::select! {
+            _ = &mut stopped => break,
+            event = events.recv() => match event {
+                Some(WsEvent::Market(MarketEvent::Unknown)) => true,
+                Some(WsEvent::Market(event)) => {
+                    summary.events += 1;
+                    commands.send(WriterCommand::Write(RecordedEvent::now(event))).is_ok()
+                }
+                Some(_) => true,
+                None => break,
+            },
+            _ = flush.tick() => commands.send(WriterCommand::Flush).is_ok(),
+            _ = async {
+                match snapshots.as_mut() {
+                    Some(snapshots) => snapshots.tick().await,
+                    None => std::future::pending().await,
+                }
+            } => match client.post_orderbook_summaries(token_ids.clone()).await {
+                Ok(books) => books.into_iter().all(|book| {
+                    summary.events += 1;
+                    let event = RecordedEvent::now(MarketEvent::Book(book.into()));
+                    commands.send(WriterCommand::Write(event)).is_ok()
+                }),
+                Err(_) => {
+                    summary.snapshot_errors += 1;
+                    true
+                }
+            },
+        };
+        // The writer stopped on an error, which joining it returns
+        if !queued {
+            break;
+        }
+    }
+
+    if let Some(socket) = socket {
+        socket.close().await;
+    }
+    drop(commands);
+    summary.files = writing
+        .await
+        .map_err(|error| ApiError::Unexpected(format!("Recording writer failed: {error}")))??;
+    Ok(summary)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::public::websocket::{BookMessage, LastTradePriceMessage};
+    use crate::shared::Side;
+    use chrono::TimeDelta;
+    use futures::StreamExt;
+    use std::io::Write;
+    use tokio::sync::mpsc;
+
+    /// A fresh directory under the system temp directory
+    fn temp_dir(name: &str) -> PathBuf {
+        let directory = std::env::temp_dir().join(format!(
+            "poly-rc-{name}-{}",
+            Utc::now().timestamp_nanos_opt().unwrap()
+        ));
+        std::fs::create_dir_all(&directory).unwrap();
+        directory
+    }
+
+    fn trade(asset_id: &str, price: &str) -> MarketEvent {
+        MarketEvent::LastTradePrice(LastTradePriceMessage {
+            asset_id: asset_id.to_string(),
+            market: "0xmarket".to_string(),
+            price: price.to_string(),
+            size: "10".to_string(),
+            side: Side::BUY,
+            fee_rate_bps: None,
+            timestamp: "1700000000000".to_string(),
+        })
+    }
+
+    fn book(asset_id: &str) -> MarketEvent {
+        MarketEvent::Book(BookMessage {
+            asset_id: asset_id.to_string(),
+            market: "0xmarket".to_string(),
+            bids: vec![],
+            asks: vec![],
+            timestamp: "1700000000000".to_string(),
+            hash: "0xhash".to_string(),
+        })
+    }
+
+    fn recorded(at: DateTime<Utc>, event: MarketEvent) -> RecordedEvent {
+        RecordedEvent {
+            recorded_at: at,
+            event,
+        }
+    }
+
+    fn price_of(event: &RecordedEvent) -> String {
+        match &event.event {
+            MarketEvent::LastTradePrice(trade) => trade.price.clone(),
+            other => panic!("expected a trade, got {other:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_rotating_writer_round_trips_through_replay() {
+        let directory = temp_dir("rotation");
+        let start = Utc::now();
+        let rotation = Rotation {
+            max_bytes: u64::MAX,
+            max_age: TimeDelta::minutes(1),
+        };
+        let mut writer = RotatingWriter::new(&directory, "test", rotation).unwrap();
+        for (offset, price) in [
+            (0, "0.1"),
+            (30, "0.2"),
+            (60, "0.3"),
+            (90, "0.4"),
+            (150, "0.5"),
+        ] {
+            writer
+                .write(&recorded(
+                    start + TimeDelta::seconds(offset),
+                    trade("1", price),
+                ))
+                .unwrap();
+        }
+        let files = writer.finish().unwrap();
+
+        // A new file at 60s and another at 150s
+        assert_eq!(files.len(), 3);
+        assert_eq!(recording_files(&directory, "test").unwrap(), files);
+        let events: Vec<RecordedEvent> = replay(files, ReplaySpeed::Unpaced)
+            .map(Result::unwrap)
+            .collect()
+            .await;
+        let prices: Vec<String> = events.iter().map(price_of).collect();
+        assert_eq!(prices, vec!["0.1", "0.2", "0.3", "0.4", "0.5"]);
+        assert_eq!(events[2].recorded_at, start + TimeDelta::seconds(60));
+
+        std::fs::remove_dir_all(directory).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_writer_rotates_by_size() {
+        let directory = temp_dir("size");
+        let rotation = Rotation {
+            max_bytes: 1,
+            max_age: TimeDelta::hours(1),
+        };
+        let mut writer = RotatingWriter::new(&directory, "test", rotation).unwrap();
+        let at = Utc::now();
+        for _ in 0..3 {
+            writer.write(&recorded(at, book("1"))).unwrap();
+        }
+        let files = writer.finish().unwrap();
+
+        // Files opened in the same instant still sort in order
+        assert_eq!(files.len(), 3);
+        let mut sorted = files.clone();
+        sorted.sort();
+        assert_eq!(sorted, files);
+
+        std::fs::remove_dir_all(directory).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_replay_skips_bad_lines_and_reads_unfinished_files() {
+        let directory = temp_dir("unfinished");
+        let mut writer = RotatingWriter::new(&directory, "test", Rotation::default()).unwrap();
+        writer
+            .write(&recorded(Utc::now(), trade("1", "0.1")))
+            .unwrap();
+        writer
+            .write(&recorded(Utc::now(), trade("1", "0.2")))
+            .unwrap();
+        writer.flush().unwrap();
+        let files = writer.files().to_vec();
+        // Leaves the gzip stream without its trailer, as after a crash
+        std::mem::forget(writer);
+
+        let mut corrupt = flate2::write::GzEncoder::new(
+            std::fs::File::create(directory.join("test-zzz.ndjson.gz")).unwrap(),
+            flate2::Compression::default(),
+        );
+        corrupt.write_all(b"not json\n").unwrap();
+        corrupt.finish().unwrap();
+
+        let all = recording_files(&directory, "test").unwrap();
+        assert_eq!(all[0], files[0]);
+        let events: Vec<Result<RecordedEvent, ApiError>> =
+            replay(all, ReplaySpeed::Unpaced).collect().await;
+
+        assert_eq!(events.len(), 3);
+        assert_eq!(price_of(events[0].as_ref().unwrap()), "0.1");
+        assert_eq!(price_of(events[1].as_ref().unwrap()), "0.2");
+        assert!(matches!(events[2], Err(ApiError::Decode(_))));
+
+        std::fs::remove_dir_all(directory).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_replay_paces_accelerated_events() {
+        let directory = temp_dir("pace");
+        let start = Utc::now();
+        let mut writer = RotatingWriter::new(&directory, "test", Rotation::default()).unwrap();
+        writer.write(&recorded(start, trade("1", "0.1"))).unwrap();
+        writer
+            .write(&recorded(
+                start + TimeDelta::milliseconds(500),
+                trade("1", "0.2"),
+            ))
+            .unwrap();
+        let files = writer.finish().unwrap();
+
+        let started = std::time::Instant::now();
+        let events: Vec<_> = replay(files, ReplaySpeed::Accelerated(10.0))
+            .collect()
+            .await;
+        let elapsed = started.elapsed();
+
+        assert_eq!(events.len(), 2);
+        assert!(elapsed >= Duration::from_millis(50), "{elapsed:?}");
+        assert!(elapsed < Duration::from_millis(500), "{elapsed:?}");
+
+        std::fs::remove_dir_all(directory).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_recorder_writes_market_events() {
+        let directory = temp_dir("recorder");
+        let (sender, events) = mpsc::unbounded_channel();
+        let recorder = Recorder::record(
+            RecorderConfig::new(vec!["1".to_string()], &directory),
+            events,
+        )
+        .unwrap();
+
+        sender.send(WsEvent::Connected).unwrap();
+        sender.send(WsEvent::Market(book("1"))).unwrap();
+        sender.send(WsEvent::Market(trade("1", "0.5"))).unwrap();
+        sender.send(WsEvent::Market(MarketEvent::Unknown)).unwrap();
+        drop(sender);
+        while !recorder.is_finished() {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        let summary = recorder.stop().await.unwrap();
+
+        assert_eq!(summary.events, 2);
+        assert_eq!(summary.files.len(), 1);
+        let events: Vec<RecordedEvent> = replay(summary.files, ReplaySpeed::Unpaced)
+            .map(Result::unwrap)
+            .collect()
+            .await;
+        assert!(matches!(events[0].event, MarketEvent::Book(_)));
+        assert_eq!(price_of(&events[1]), "0.5");
+
+        std::fs::remove_dir_all(directory).unwrap();
+    }
+}
@@ -0,0 +1,195 @@
+//! Reading recordings back as a [`Stream`] of [`RecordedEvent`]s.
+
+use chrono::{DateTime, Utc};
+use flate2::read::MultiGzDecoder;
+use futures::Stream;
+use std::collections::VecDeque;
+use std::fs::File;
+use std::io::{BufRead, BufReader, ErrorKind};
+use std::path::PathBuf;
+use std::time::Duration;
+use tokio::sync::mpsc;
+use tokio::time::Instant;
+
+use crate::public::recorder::RecordedEvent;
+use crate::shared::ApiError;
+
+/// How fast a recording is replayed
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ReplaySpeed {
+    /// Every event as soon as it is read
+    Unpaced,
+    /// With the gaps between events as they were recorded
+    Original,
+    /// With the recorded gaps divided by the factor, e.g. `10.0` for ten times faster
+    Accelerated(f64),
+}
+
+/// The longest delay between two replayed events, about 30 years. A slower
+/// replay would never deliver the event anyway, and a longer delay could
+/// overflow an [`Instant`]
+const MAX_DELAY: Duration = Duration::from_secs(86_400 * 365 * 30);
+
+/// How many events the file reader reads ahead of the stream
+const READ_AHEAD: usize = 1024;
+
+impl ReplaySpeed {
+    /// How long after the first event an event recorded `elapsed` later is replayed
+    fn scale(self, elapsed: chrono::TimeDelta) -> Option<Duration> {
+        let elapsed = elapsed.to_std().unwrap_or_default();
+        let delay = match self {
+            ReplaySpeed::Unpaced => return None,
+            ReplaySpeed::Original => elapsed,
+            ReplaySpeed::Accelerated(factor) if factor > 0.0 => {
+                Duration::try_from_secs_f64(elapsed.as_secs_f64() / factor).unwrap_or(MAX_DELAY)
+            }
+            ReplaySpeed::Accelerated(_) => return None,
+        };
+        Some(delay.min(MAX_DELAY))
+    }
+}
+
+type Lines = std::io::Lines<BufReader<MultiGzDecoder<File>>>;
+
+/// Reads the lines of the recording files one after the other
+struct LineReader {
+    files: VecDeque<PathBuf>,
+    lines: Option<(PathBuf, Lines)>,
+}
+
+impl LineReader {
+    /// The next line of the recording, `None` once every file is read
+    fn next_line(&mut self) -> Option<Result<String, ApiError>> {
+        loop {
+            if self.lines.is_none() {
+                let path = self.files.pop_front()?;
+                match File::open(&path) {
+                    Ok(file) => {
+                        let reader = BufReader::new(MultiGzDecoder::new(file));
+                        self.lines = Some((path, reader.lines()));
+                    }
+                    Err(error) => {
+                        return Some(Err(ApiError::Unexpected(format!(
+                            "Could not open {}: {error}",
+                            path.display()
+                        ))));
+                    }
+                }
+            }
+
+            let (path, lines) = self.lines.as_mut()?;
+            match lines.next() {
+                Some(Ok(line)) if line.trim().is_empty() => continue,
+                Some(Ok(line)) => return Some(Ok(line)),
+                // The file of a recorder that did not finish ends mid-stream
+                Some(Err(error)) if error.kind() == ErrorKind::UnexpectedEof => self.lines = None,
+                Some(Err(error)) => {
+                    let error =
+                        ApiError::Unexpected(format!("Could not read {}: {error}", path.display()));
+                    self.lines = None;
+                    return Some(Err(error));
+                }
+                None => self.lines = None,
+            }
+        }
+    }
+
+    /// Send every event of the files until they are read or the stream is
+    /// dropped. Blocks, so it runs on a blocking thread
+    fn send_all(mut self, events: mpsc::Sender<Result<RecordedEvent, ApiError>>) {
+        while let Some(line) = self.next_line() {
+            let event = line.and_then(|line| {
+                serde_json::from_str::<RecordedEvent>(&line).map_err(ApiError::from)
+            });
+            if events.blocking_send(event).is_err() {
+                return;
+            }
+        }
+    }
+}
+
+struct ReplayState {
+    /// The files to read, until the reader is started on the first poll
+    files: Option<Vec<PathBuf>>,
+    events: Option<mpsc::Receiver<Result<RecordedEvent, ApiError>>>,
+    speed: ReplaySpeed,
+    /// The recording time of the first event and when it was replayed
+    start: Option<(DateTime<Utc>, Instant)>,
+}
+
+impl ReplayState {
+    /// Wait until `event` is due
+    async fn pace(&mut self, event: &RecordedEvent) {
+        let Some((first, started)) = self.start else {
+            self.start = Some((event.recorded_at, Instant::now()));
+            return;
+        };
+        if let Some(delay) = self.speed.scale(event.recorded_at - first) {
+            tokio::time::sleep_until(started + delay).await;
+        }
+    }
+}
+
+/// Replay recording files as a stream of the events they hold.
+///
+/// Files are read in the given order, see
+/// [`recording_files`](super::recording_files), on a blocking thread that
+/// reads a little ahead of the stream. A line that is not a
+/// [`RecordedEvent`] or a file that cannot be read yields an error and the
+/// replay carries on with the next line or file. A file cut short because its
+/// recorder stopped without finishing ends at its last complete event.
+/// # Arguments
+/// * `files` - The recording files, oldest first
+/// * `speed` - How to pace the events, see [`ReplaySpeed`]
+/// # Returns
+/// * `impl Stream<Item = Result<RecordedEvent, ApiError>>` - The recorded events, in recording order
+pub fn replay(
+    files: Vec<PathBuf>,
+    speed: ReplaySpeed,
+) -> impl Stream<Item = Result<RecordedEvent, ApiError>> {
+    let state = ReplayState {
+        files: Some(files),
+        events: None,
+        speed,
+        start: None,
+    };
+    futures::stream::unfold(state, |mut state| async move {
+        if let Some(files) = state.files.take() {
+            let (sender, receiver) = mpsc::channel(READ_AHEAD);
+            let reader = LineReader {
+                files: files.into(),
+                lines: None,
+            };
+            tokio::task::spawn_blocking(move || reader.send_all(sender));
+            state.events = Some(receiver);
+        }
+        let event = state.events.as_mut()?.recv().await?;
+        if let Ok(event) = &event {
+            state.pace(event).await;
+        }
+        Some((event, state))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeDelta;
+
+    #[test]
+    fn test_scale_clamps_huge_delays() {
+        let second = TimeDelta::seconds(1);
+
+        assert_eq!(
+            ReplaySpeed::Accelerated(10.0).scale(second),
+            Some(Duration::from_millis(100))
+        );
+        assert_eq!(
+            ReplaySpeed::Accelerated(1e-300).scale(second),
+            Some(MAX_DELAY)
+        );
+        assert_eq!(ReplaySpeed::Original.scale(TimeDelta::MAX), Some(MAX_DELAY));
+        assert_eq!(ReplaySpeed::Accelerated(f64::NAN).scale(second), None);
+        assert_eq!(ReplaySpeed::Unpaced.scale(second), None);
+    }
+}
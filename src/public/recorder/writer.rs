@@ -0,0 +1,175 @@
+//! Gzip compressed, rotating newline-delimited JSON files.
+
+use chrono::{DateTime, TimeDelta, Utc};
+use flate2::Compression;
+use flate2::write::GzEncoder;
+use std::fs::{self, File};
+use std::io::{BufWriter, Write};
+use std::path::{Path, PathBuf};
+
+use crate::public::recorder::RecordedEvent;
+use crate::shared::ApiError;
+
+/// The extension of recording files
+pub const RECORDING_EXTENSION: &str = "ndjson.gz";
+
+/// When a [`RotatingWriter`] starts a new file
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Rotation {
+    /// Rotate once this many bytes of JSON have been written to a file
+    pub max_bytes: u64,
+    /// Rotate once the first event of a file is this old
+    pub max_age: TimeDelta,
+}
+
+impl Default for Rotation {
+    fn default() -> Self {
+        Self {
+            max_bytes: 256 * 1024 * 1024,
+            max_age: TimeDelta::hours(1),
+        }
+    }
+}
+
+struct OpenFile {
+    path: PathBuf,
+    encoder: GzEncoder<BufWriter<File>>,
+    opened_at: DateTime<Utc>,
+    bytes: u64,
+}
+
+/// Writes [`RecordedEvent`]s as one JSON object per line to gzip files named
+/// `<prefix>-<time of the first event>-<counter>.ndjson.gz`, so the files of a
+/// directory sort in recording order.
+///
+/// A file only becomes a complete gzip stream once it is rotated or the writer
+/// is finished. [`flush`](Self::flush) makes everything written so far
+/// readable, so a crash loses at most the events since the last flush.
+pub struct RotatingWriter {
+    directory: PathBuf,
+    prefix: String,
+    rotation: Rotation,
+    current: Option<OpenFile>,
+    files: Vec<PathBuf>,
+}
+
+impl RotatingWriter {
+    /// Write into `directory`, creating it if needed. No file is created
+    /// before the first event
+    pub fn new(
+        directory: impl Into<PathBuf>,
+        prefix: &str,
+        rotation: Rotation,
+    ) -> Result<Self, ApiError> {
+        let directory = directory.into();
+        fs::create_dir_all(&directory)?;
+        Ok(Self {
+            directory,
+            prefix: prefix.to_string(),
+            rotation,
+            current: None,
+            files: vec![],
+        })
+    }
+
+    /// Append an event, rotating first if the current file is full or too old
+    pub fn write(&mut self, event: &RecordedEvent) -> Result<(), ApiError> {
+        let mut line = serde_json::to_vec(event)?;
+        line.push(b'\n');
+
+        if let Some(current) = &self.current
+            && (current.bytes >= self.rotation.max_bytes
+                || event.recorded_at - current.opened_at >= self.rotation.max_age)
+        {
+            self.close_current()?;
+        }
+        if self.current.is_none() {
+            self.current = Some(self.open(event.recorded_at)?);
+        }
+        let current = self.current.as_mut().unwrap();
+        current.encoder.write_all(&line)?;
+        current.bytes += line.len() as u64;
+        Ok(())
+    }
+
+    /// Make every event written so far readable from the current file
+    pub fn flush(&mut self) -> Result<(), ApiError> {
+        if let Some(current) = &mut self.current {
+            current.encoder.flush()?;
+        }
+        Ok(())
+    }
+
+    /// Complete the current file
+    /// # Returns
+    /// * `Result<Vec<PathBuf>, ApiError>` - Every file written, oldest first
+    pub fn finish(mut self) -> Result<Vec<PathBuf>, ApiError> {
+        self.close_current()?;
+        Ok(self.files)
+    }
+
+    /// The files written so far, oldest first, including the open one
+    pub fn files(&self) -> &[PathBuf] {
+        &self.files
+    }
+
+    fn open(&mut self, at: DateTime<Utc>) -> Result<OpenFile, ApiError> {
+        let stamp = at.format("%Y%m%dT%H%M%S%.6fZ");
+        // The counter orders files opened in the same microsecond
+        let mut counter = 0;
+        let mut path;
+        loop {
+            path = self.directory.join(format!(
+                "{}-{stamp}-{counter:04}.{RECORDING_EXTENSION}",
+                self.prefix
+            ));
+            if !path.exists() {
+                break;
+            }
+            counter += 1;
+        }
+
+        let file = File::create(&path)?;
+        self.files.push(path.clone());
+        Ok(OpenFile {
+            path,
+            encoder: GzEncoder::new(BufWriter::new(file), Compression::default()),
+            opened_at: at,
+            bytes: 0,
+        })
+    }
+
+    fn close_current(&mut self) -> Result<(), ApiError> {
+        if let Some(current) = self.current.take() {
+            current
+                .encoder
+                .finish()?
+                .into_inner()
+                .map_err(|error| {
+                    ApiError::Unexpected(format!(
+                        "Could not write {}: {}",
+                        current.path.display(),
+                        error.error()
+                    ))
+                })?
+                .sync_all()?;
+        }
+        Ok(())
+    }
+}
+
+/// The recording files of `directory` whose name starts with `prefix`, oldest first
+pub fn recording_files(directory: &Path, prefix: &str) -> Result<Vec<PathBuf>, ApiError> {
+    let mut files: Vec<PathBuf> = fs::read_dir(directory)?
+        .map(|entry| entry.map(|entry| entry.path()))
+        .collect::<Result<_, _>>()?;
+    files.retain(|path| {
+        path.file_name()
+            .and_then(|name| name.to_str())
+            .is_some_and(|name| {
+                name.starts_with(&format!("{prefix}-")) && name.ends_with(RECORDING_EXTENSION)
+            })
+    });
+    files.sort();
+    Ok(files)
+}
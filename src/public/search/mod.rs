@@ -0,0 +1,101 @@
+pub mod models;
+
+use async_trait::async_trait;
+use models::{SearchDTO, SearchResults};
+
+use crate::shared::{ApiError, QueryParams, client::AsyncHttpClient};
+
+#[async_trait]
+pub trait Search {
+    fn get_gamma_client(&self) -> &AsyncHttpClient;
+
+    /// Search events, tags and profiles
+    /// # Arguments
+    /// * `data` - The search phrase and filters. See [`SearchDTO`] for available options.
+    /// # Returns
+    /// * `Result<[`SearchResults`], [`ApiError`]>` - The results grouped by type, or an [`ApiError`] if the request fails
+    async fn search(&self, data: SearchDTO) -> Result<SearchResults, ApiError> {
+        let client = self.get_gamma_client();
+        let query = data.as_query_params();
+        let response = client
+            .get(Some("/public-search"), Some(query), None)
+            .await?;
+        let results: SearchResults = response.json().await?;
+        Ok(results)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::public::PubClient;
+    use crate::shared::query::pairs;
+    use models::EventsStatus;
+
+    #[tokio::test]
+    async fn test_search() {
+        let client = PubClient::new();
+
+        let results = client
+            .search(SearchDTO {
+                q: String::from("bitcoin"),
+                search_tags: Some(true),
+                search_profiles: Some(true),
+                limit_per_type: Some(5),
+                ..Default::default()
+            })
+            .await;
+
+        if let Err(e) = &results {
+            eprintln!("Search error: {:?}", e);
+        }
+        assert!(results.is_ok());
+    }
+
+    #[test]
+    fn test_search_dto_query_params() {
+        let dto = SearchDTO {
+            q: String::from("fed rates"),
+            search_profiles: Some(false),
+            page: Some(2),
+            events_status: Some(EventsStatus::Active),
+            events_tag: Some(vec![String::from("economy"), String::from("fed")]),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            dto.as_query_params(),
+            pairs(&[
+                ("q", "fed rates"),
+                ("search_profiles", "false"),
+                ("page", "2"),
+                ("events_status", "active"),
+                ("events_tag", "economy"),
+                ("events_tag", "fed"),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_deserialize_search_results() {
+        let body = r#"{
+            "events": [{
+                "id": "2909",
+                "title": "Fed decision",
+                "markets": [{"id": "1", "slug": "fed-cut", "question": "Cut?", "clobTokenIds": "[\"1\", \"2\"]"}]
+            }],
+            "tags": [{"id": "7", "label": "Fed", "slug": "fed", "event_count": 3}],
+            "profiles": null,
+            "pagination": {"hasMore": true, "totalResults": 40}
+        }"#;
+
+        let results: SearchResults = serde_json::from_str(body).unwrap();
+
+        assert_eq!(results.events.len(), 1);
+        assert_eq!(results.markets().next().unwrap().slug, "fed-cut");
+        assert_eq!(results.tags[0].slug.as_deref(), Some("fed"));
+        assert_eq!(results.tags[0].event_count, Some(3));
+        assert!(results.profiles.is_empty());
+        assert!(results.pagination.unwrap().has_more);
+    }
+}
@@ -0,0 +1,114 @@
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+use serde_with::{DefaultOnNull, serde_as};
+
+use crate::public::{events::models::EventInfo, markets::models::Market};
+use crate::shared::QueryParams;
+
+/// Filter on the status of the events returned by a search
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum EventsStatus {
+    Active,
+    Closed,
+    All,
+}
+
+impl fmt::Display for EventsStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl EventsStatus {
+    pub fn as_str(&self) -> &str {
+        match self {
+            EventsStatus::Active => "active",
+            EventsStatus::Closed => "closed",
+            EventsStatus::All => "all",
+        }
+    }
+}
+
+/// Query parameters for the Gamma `/public-search` endpoint
+/// # Fields
+/// * `q` - The search phrase
+/// * `search_tags` / `search_profiles` - Also search tags and profiles. Events are always searched
+/// * `limit_per_type` / `page` - Pagination, applied to every result type
+/// * `sort` / `ascending` - Field to sort the events by and the sort direction
+/// * `events_status` - Only return events with the status
+/// * `events_tag` - Only return events with the tags
+/// * `exclude_tag_id` - Leave out events with any of the tags
+/// * `keep_closed_markets` - Keep the closed markets of the returned events
+#[derive(Debug, Clone, Serialize, Deserialize, Default, QueryParams)]
+pub struct SearchDTO {
+    pub q: String,
+    pub search_tags: Option<bool>,
+    pub search_profiles: Option<bool>,
+    pub limit_per_type: Option<u32>,
+    pub page: Option<u32>,
+    pub sort: Option<String>,
+    pub ascending: Option<bool>,
+    pub events_status: Option<EventsStatus>,
+    #[query(repeat)]
+    pub events_tag: Option<Vec<String>>,
+    #[query(repeat)]
+    pub exclude_tag_id: Option<Vec<i64>>,
+    pub keep_closed_markets: Option<bool>,
+}
+
+/// A tag matching a search
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct SearchTag {
+    pub id: String,
+    pub label: Option<String>,
+    pub slug: Option<String>,
+    #[serde(alias = "event_count")]
+    pub event_count: Option<i64>,
+}
+
+/// A user profile matching a search
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct SearchProfile {
+    pub id: Option<String>,
+    pub name: Option<String>,
+    pub pseudonym: Option<String>,
+    pub bio: Option<String>,
+    pub profile_image: Option<String>,
+    pub proxy_wallet: Option<String>,
+    pub display_username_public: Option<bool>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct SearchPagination {
+    pub has_more: bool,
+    pub total_results: Option<u64>,
+}
+
+/// The results of a search, grouped by type
+#[serde_as]
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct SearchResults {
+    #[serde(default)]
+    #[serde_as(as = "DefaultOnNull")]
+    pub events: Vec<EventInfo>,
+    #[serde(default)]
+    #[serde_as(as = "DefaultOnNull")]
+    pub tags: Vec<SearchTag>,
+    #[serde(default)]
+    #[serde_as(as = "DefaultOnNull")]
+    pub profiles: Vec<SearchProfile>,
+    pub pagination: Option<SearchPagination>,
+}
+
+impl SearchResults {
+    /// The markets of the matching events
+    pub fn markets(&self) -> impl Iterator<Item = &Market> {
+        self.events
+            .iter()
+            .flat_map(|event| event.markets.iter().flatten())
+    }
+}
@@ -0,0 +1,162 @@
+pub mod models;
+
+use async_trait::async_trait;
+use futures::stream::BoxStream;
+use models::{SeriesDTO, SeriesInfo};
+
+use crate::shared::{ApiError, PageConfig, QueryParams, client::AsyncHttpClient, pagination};
+
+#[async_trait]
+pub trait Series {
+    fn get_gamma_client(&self) -> &AsyncHttpClient;
+
+    /// Get the series
+    /// # Arguments
+    /// * `data` - The query parameters for the series. See [`SeriesDTO`] for available options.
+    /// # Returns
+    /// * `Result<Vec<[`SeriesInfo`]>, [`ApiError`]>` - A vector of [`SeriesInfo`] objects, or an [`ApiError`] if the request fails
+    async fn get_series(&self, data: SeriesDTO) -> Result<Vec<SeriesInfo>, ApiError> {
+        let client = self.get_gamma_client();
+        let query = data.as_query_params();
+        let response = client.get(Some("/series"), Some(query), None).await?;
+        let series: Vec<SeriesInfo> = response.json().await?;
+        Ok(series)
+    }
+
+    /// Stream all the series matching the filters, one page at a time
+    /// # Arguments
+    /// * `data` - The query parameters. `limit` is replaced by the page size and `offset` is the offset of the first item
+    /// * `config` - The page size, concurrency and item cap. See [`PageConfig`]
+    /// # Returns
+    /// * `BoxStream<Result<[`SeriesInfo`], [`ApiError`]>>` - The series, ending after the first [`ApiError`]
+    fn stream_series(
+        &self,
+        data: SeriesDTO,
+        config: PageConfig,
+    ) -> BoxStream<'_, Result<SeriesInfo, ApiError>>
+    where
+        Self: Sync,
+    {
+        let start = data.offset.unwrap_or(0);
+        pagination::paginate(config, start, move |offset, limit| {
+            self.get_series(SeriesDTO {
+                limit: Some(limit),
+                offset: Some(offset),
+                ..data.clone()
+            })
+        })
+    }
+
+    /// Get all the series matching the filters
+    /// # Arguments
+    /// * `data` - The query parameters. See [`SeriesDTO`] for available options.
+    /// # Returns
+    /// * `Result<Vec<[`SeriesInfo`]>, [`ApiError`]>` - All the series, or an [`ApiError`] if a request fails
+    async fn collect_all_series(&self, data: SeriesDTO) -> Result<Vec<SeriesInfo>, ApiError> {
+        pagination::collect_all(self.stream_series(data, PageConfig::default())).await
+    }
+
+    /// Get a series by id
+    /// # Arguments
+    /// * `id` - The id of the series
+    /// # Returns
+    /// * `Result<[`SeriesInfo`], [`ApiError`]>` - The [`SeriesInfo`] object, or an [`ApiError`] if the request fails
+    async fn get_series_by_id(&self, id: String) -> Result<SeriesInfo, ApiError> {
+        let path = format!("{}{}", "/series/", id);
+        let client = self.get_gamma_client();
+        let response = client.get(Some(path.as_str()), None, None).await?;
+        let series: SeriesInfo = response.json().await?;
+        Ok(series)
+    }
+
+    /// Get a series by slug
+    /// # Arguments
+    /// * `slug` - The slug of the series
+    /// # Returns
+    /// * `Result<[`SeriesInfo`], [`ApiError`]>` - The [`SeriesInfo`] object, or an [`ApiError`] if the request fails or no series has the slug
+    async fn get_series_by_slug(&self, slug: String) -> Result<SeriesInfo, ApiError> {
+        let series = self
+            .get_series(SeriesDTO {
+                slug: Some(vec![slug.clone()]),
+                limit: Some(1),
+                ..Default::default()
+            })
+            .await?;
+        series
+            .into_iter()
+            .next()
+            .ok_or_else(|| ApiError::Unexpected(format!("No series with slug {slug}")))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::public::PubClient;
+    use crate::public::events::models::EventInfo;
+    use crate::shared::query::pairs;
+
+    #[tokio::test]
+    async fn test_get_series() {
+        let client = PubClient::new();
+
+        let series = client
+            .get_series(SeriesDTO {
+                limit: Some(5),
+                closed: Some(false),
+                ..Default::default()
+            })
+            .await;
+
+        if let Err(e) = &series {
+            eprintln!("Get series error: {:?}", e);
+        }
+        assert!(series.is_ok());
+    }
+
+    #[test]
+    fn test_series_dto_query_params() {
+        let dto = SeriesDTO {
+            limit: Some(20),
+            slug: Some(vec![String::from("btc-up-or-down-daily")]),
+            categories_labels: Some(vec![String::from("Crypto"), String::from("Sports")]),
+            closed: Some(false),
+            recurrence: Some(String::from("daily")),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            dto.as_query_params(),
+            pairs(&[
+                ("limit", "20"),
+                ("slug", "btc-up-or-down-daily"),
+                ("categories_labels", "Crypto"),
+                ("categories_labels", "Sports"),
+                ("closed", "false"),
+                ("recurrence", "daily"),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_current_event() {
+        let event = |id: &str, end_date: &str, closed: bool| EventInfo {
+            id: id.to_string(),
+            end_date: Some(end_date.to_string()),
+            closed: Some(closed),
+            ..Default::default()
+        };
+        let series = SeriesInfo {
+            id: String::from("1"),
+            events: Some(vec![
+                event("old", "2025-01-01T00:00:00Z", true),
+                event("next", "2025-01-03T00:00:00Z", false),
+                event("current", "2025-01-02T00:00:00Z", false),
+            ]),
+            ..Default::default()
+        };
+
+        assert_eq!(series.current_event().unwrap().id, "current");
+        assert!(SeriesInfo::default().current_event().is_none());
+    }
+}
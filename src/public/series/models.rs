@@ -0,0 +1,91 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::public::events::models::EventInfo;
+use crate::shared::QueryParams;
+
+/// Query parameters for the Gamma `/series` endpoint
+/// # Fields
+/// * `limit` / `offset` - Pagination
+/// * `order` / `ascending` - Fields to sort by and the sort direction
+/// * `slug` - Only return the listed series
+/// * `categories_ids` / `categories_labels` - Only return series in the categories
+/// * `closed` - Filter on whether the series is closed
+/// * `recurrence` - Filter on the recurrence, e.g. `daily` or `weekly`
+/// * `include_chat` - Include the chat data
+#[derive(Debug, Clone, Serialize, Deserialize, Default, QueryParams)]
+pub struct SeriesDTO {
+    pub limit: Option<u32>,
+    pub offset: Option<u32>,
+    #[query(comma)]
+    pub order: Option<Vec<String>>,
+    pub ascending: Option<bool>,
+    #[query(repeat)]
+    pub slug: Option<Vec<String>>,
+    #[query(repeat)]
+    pub categories_ids: Option<Vec<i64>>,
+    #[query(repeat)]
+    pub categories_labels: Option<Vec<String>>,
+    pub closed: Option<bool>,
+    pub recurrence: Option<String>,
+    pub include_chat: Option<bool>,
+}
+
+/// A Gamma series, a group of recurring events such as a daily or weekly market
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct SeriesInfo {
+    pub id: String,
+    pub ticker: Option<String>,
+    pub slug: Option<String>,
+    pub title: Option<String>,
+    pub subtitle: Option<String>,
+    pub series_type: Option<String>,
+    pub recurrence: Option<String>,
+    pub description: Option<String>,
+    pub image: Option<String>,
+    pub icon: Option<String>,
+    pub layout: Option<String>,
+
+    pub active: Option<bool>,
+    pub closed: Option<bool>,
+    pub archived: Option<bool>,
+    pub new: Option<bool>,
+    pub featured: Option<bool>,
+    pub restricted: Option<bool>,
+    pub comments_enabled: Option<bool>,
+
+    pub start_date: Option<String>,
+    pub published_at: Option<String>,
+    pub created_at: Option<String>,
+    pub updated_at: Option<String>,
+
+    pub volume: Option<f64>,
+    pub volume24hr: Option<f64>,
+    pub liquidity: Option<f64>,
+    pub competitive: Option<String>,
+    pub comment_count: Option<i64>,
+
+    pub events: Option<Vec<EventInfo>>,
+}
+
+impl SeriesInfo {
+    /// The open event of the series that ends first, i.e. the event currently
+    /// trading for a recurring series.
+    ///
+    /// The events of a series are returned without their markets, fetch the
+    /// event by id to get its `clob_token_ids`.
+    pub fn current_event(&self) -> Option<&EventInfo> {
+        self.events
+            .iter()
+            .flatten()
+            .filter(|event| event.closed != Some(true))
+            .filter_map(|event| {
+                let end_date = event.end_date.as_deref()?;
+                let end_date = DateTime::parse_from_rfc3339(end_date).ok()?;
+                Some((end_date.with_timezone(&Utc), event))
+            })
+            .min_by_key(|(end_date, _)| *end_date)
+            .map(|(_, event)| event)
+    }
+}
@@ -1,4 +1,5 @@
-use crate::shared::{ApiError, client::AsyncHttpClient};
+use crate::shared::{ApiError, PageConfig, client::AsyncHttpClient, pagination};
+use futures::stream::BoxStream;
 pub mod models;
 use crate::shared::QueryParams;
 use async_trait::async_trait;
@@ -21,6 +22,42 @@ pub trait Sports {
         Ok(sports_data)
     }
 
+    /// Stream all the sports teams matching the filters, one page at a time
+    /// # Arguments
+    /// * `data` - The query parameters. `limit` is replaced by the page size and `offset` is the offset of the first item
+    /// * `config` - The page size, concurrency and item cap. See [`PageConfig`]
+    /// # Returns
+    /// * `BoxStream<Result<[`SportsTeam`], [`ApiError`]>>` - The sports teams, ending after the first [`ApiError`]
+    fn stream_sports_teams(
+        &self,
+        data: SportsTeamsDTO,
+        config: PageConfig,
+    ) -> BoxStream<'_, Result<SportsTeam, ApiError>>
+    where
+        Self: Sync,
+    {
+        let start = data.offset.unwrap_or(0);
+        pagination::paginate(config, start, move |offset, limit| {
+            self.get_sports_teams(SportsTeamsDTO {
+                limit: Some(limit),
+                offset: Some(offset),
+                ..data.clone()
+            })
+        })
+    }
+
+    /// Get all the sports teams matching the filters
+    /// # Arguments
+    /// * `data` - The query parameters. See [`SportsTeamsDTO`] for available options.
+    /// # Returns
+    /// * `Result<Vec<[`SportsTeam`]>, [`ApiError`]>` - All the sports teams, or an [`ApiError`] if a request fails
+    async fn collect_all_sports_teams(
+        &self,
+        data: SportsTeamsDTO,
+    ) -> Result<Vec<SportsTeam>, ApiError> {
+        pagination::collect_all(self.stream_sports_teams(data, PageConfig::default())).await
+    }
+
     /// Get the sports metadata
     /// # Arguments
     /// * `data` - The query parameters for the sports metadata. See [SportsMetadataDTO] for available options.
@@ -115,4 +152,28 @@ mod tests {
 
         assert!(sport_market_types.is_ok());
     }
+
+    #[test]
+    fn test_sports_teams_dto_repeats_keys() {
+        let dto = SportsTeamsDTO {
+            limit: Some(2),
+            order: Some(vec![String::from("name"), String::from("id")]),
+            league: Some(vec![String::from("nba"), String::from("nfl")]),
+            abbreviation: Some(vec![String::from("lal")]),
+            ..Default::default()
+        };
+
+        let params = dto.as_query_params();
+
+        assert_eq!(
+            params,
+            vec![
+                (String::from("limit"), String::from("2")),
+                (String::from("order"), String::from("name,id")),
+                (String::from("league"), String::from("nba")),
+                (String::from("league"), String::from("nfl")),
+                (String::from("abbreviation"), String::from("lal")),
+            ]
+        );
+    }
 }
@@ -1,62 +1,23 @@
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
 
 use crate::shared::QueryParams;
 
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, Serialize, Deserialize, Default, QueryParams)]
 pub struct SportsTeamsDTO {
-    pub limit: Option<i16>,
-    pub offset: Option<i16>,
+    pub limit: Option<u32>,
+    pub offset: Option<u32>,
+    #[query(comma)]
     pub order: Option<Vec<String>>,
     pub ascending: Option<bool>,
+    #[query(repeat)]
     pub league: Option<Vec<String>>,
+    #[query(repeat)]
     pub name: Option<Vec<String>>,
+    #[query(repeat)]
     pub abbreviation: Option<Vec<String>>,
 }
 
-impl QueryParams for SportsTeamsDTO {
-    fn as_query_params(&self) -> HashMap<String, String> {
-        let mut query = HashMap::new();
-        if let Some(limit) = self.limit {
-            query.insert("limit".to_string(), limit.to_string());
-        }
-        if let Some(offset) = self.offset {
-            query.insert("offset".to_string(), offset.to_string());
-        }
-        if let Some(order) = &self.order {
-            let mut order_str = String::new();
-            for (i, order) in order.iter().enumerate() {
-                order_str.push_str(order);
-                if i < order.len() - 1 {
-                    order_str.push(',');
-                }
-            }
-            query.insert("order".to_string(), order_str);
-        }
-        if let Some(ascending) = self.ascending {
-            query.insert("ascending".to_string(), ascending.to_string());
-        }
-
-        if let Some(league) = &self.league {
-            for leag in league {
-                query.insert("league".to_string(), leag.to_string());
-            }
-        }
-        if let Some(name) = &self.name {
-            for name in name {
-                query.insert("name".to_string(), name.to_string());
-            }
-        }
-        if let Some(abbreviation) = &self.abbreviation {
-            for abbreviation in abbreviation {
-                query.insert("abbreviation".to_string(), abbreviation.to_string());
-            }
-        }
-        query
-    }
-}
-
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct SportsTeam {
@@ -1,5 +1,6 @@
-use crate::shared::{ApiError, QueryParams, client::AsyncHttpClient};
+use crate::shared::{ApiError, PageConfig, QueryParams, client::AsyncHttpClient, pagination};
 use async_trait::async_trait;
+use futures::stream::BoxStream;
 pub mod models;
 
 use models::{TagDTO, TagIdDTO, TagItem, TagRelationshipDTO, TagRelationshipItem, TagSlugDTO};
@@ -20,6 +21,39 @@ pub trait Tags {
         Ok(tags)
     }
 
+    /// Stream all the tags matching the filters, one page at a time
+    /// # Arguments
+    /// * `data` - The query parameters. `limit` is replaced by the page size and `offset` is the offset of the first item
+    /// * `config` - The page size, concurrency and item cap. See [`PageConfig`]
+    /// # Returns
+    /// * `BoxStream<Result<[`TagItem`], [`ApiError`]>>` - The tags, ending after the first [`ApiError`]
+    fn stream_tags(
+        &self,
+        data: TagDTO,
+        config: PageConfig,
+    ) -> BoxStream<'_, Result<TagItem, ApiError>>
+    where
+        Self: Sync,
+    {
+        let start = data.offset.unwrap_or(0);
+        pagination::paginate(config, start, move |offset, limit| {
+            self.get_tags(TagDTO {
+                limit: Some(limit),
+                offset: Some(offset),
+                ..data.clone()
+            })
+        })
+    }
+
+    /// Get all the tags matching the filters
+    /// # Arguments
+    /// * `data` - The query parameters. See [`TagDTO`] for available options.
+    /// # Returns
+    /// * `Result<Vec<[`TagItem`]>, [`ApiError`]>` - All the tags, or an [`ApiError`] if a request fails
+    async fn collect_all_tags(&self, data: TagDTO) -> Result<Vec<TagItem>, ApiError> {
+        pagination::collect_all(self.stream_tags(data, PageConfig::default())).await
+    }
+
     /// Get the tag by id
     /// # Arguments
     /// * `id` - The id of the tag
@@ -158,6 +192,7 @@ mod tests {
     use super::*;
     use crate::public::PubClient;
     use models::TagStatus;
+    use std::collections::HashMap;
 
     #[tokio::test]
     async fn test_get_tags() {
@@ -370,7 +405,7 @@ mod tests {
             is_carousel: Some(false),
         };
 
-        let params = dto.as_query_params();
+        let params: HashMap<String, String> = dto.as_query_params().into_iter().collect();
 
         assert_eq!(params.get("limit"), Some(&"10".to_string()));
         assert_eq!(params.get("offset"), Some(&"5".to_string()));
@@ -391,7 +426,7 @@ mod tests {
             is_carousel: None,
         };
 
-        let params = dto.as_query_params();
+        let params: HashMap<String, String> = dto.as_query_params().into_iter().collect();
 
         assert!(params.is_empty());
     }
@@ -407,7 +442,7 @@ mod tests {
             is_carousel: None,
         };
 
-        let params = dto.as_query_params();
+        let params: HashMap<String, String> = dto.as_query_params().into_iter().collect();
 
         assert_eq!(params.get("order"), Some(&"name".to_string()));
         assert_eq!(params.len(), 1);
@@ -419,7 +454,7 @@ mod tests {
             include_template: Some(true),
         };
 
-        let params = dto.as_query_params();
+        let params: HashMap<String, String> = dto.as_query_params().into_iter().collect();
 
         assert_eq!(params.get("include_template"), Some(&"true".to_string()));
         assert_eq!(params.len(), 1);
@@ -431,7 +466,7 @@ mod tests {
             include_template: None,
         };
 
-        let params = dto.as_query_params();
+        let params: HashMap<String, String> = dto.as_query_params().into_iter().collect();
 
         assert!(params.is_empty());
     }
@@ -443,7 +478,7 @@ mod tests {
             status: Some(TagStatus::Active),
         };
 
-        let params = dto.as_query_params();
+        let params: HashMap<String, String> = dto.as_query_params().into_iter().collect();
 
         assert_eq!(params.get("omit_empty"), Some(&"true".to_string()));
         assert_eq!(params.get("status"), Some(&"active".to_string()));
@@ -457,7 +492,7 @@ mod tests {
             status: Some(TagStatus::All),
         };
 
-        let params = dto.as_query_params();
+        let params: HashMap<String, String> = dto.as_query_params().into_iter().collect();
 
         assert_eq!(params.get("status"), Some(&"all".to_string()));
         assert_eq!(params.len(), 1);
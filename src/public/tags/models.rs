@@ -1,53 +1,21 @@
-use std::{collections::HashMap, fmt};
+use std::fmt;
 
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
 use crate::shared::QueryParams;
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, Default, QueryParams)]
 pub struct TagDTO {
-    pub limit: Option<i16>,
-    pub offset: Option<i16>,
+    pub limit: Option<u32>,
+    pub offset: Option<u32>,
+    #[query(comma)]
     pub order: Option<Vec<String>>,
     pub ascending: Option<bool>,
     pub include_template: Option<bool>,
     pub is_carousel: Option<bool>,
 }
 
-impl QueryParams for TagDTO {
-    fn as_query_params(&self) -> HashMap<String, String> {
-        let mut query = HashMap::new();
-        if let Some(limit) = self.limit {
-            query.insert("limit".to_string(), limit.to_string());
-        }
-        if let Some(offset) = self.offset {
-            query.insert("offset".to_string(), offset.to_string());
-        }
-        if let Some(order) = &self.order {
-            let mut order_str = String::new();
-            let order_len = order.len();
-            for (i, order_item) in order.iter().enumerate() {
-                order_str.push_str(order_item);
-                if i < order_len - 1 {
-                    order_str.push(',');
-                }
-            }
-            query.insert("order".to_string(), order_str);
-        }
-        if let Some(ascending) = self.ascending {
-            query.insert("ascending".to_string(), ascending.to_string());
-        }
-        if let Some(include_template) = self.include_template {
-            query.insert("include_template".to_string(), include_template.to_string());
-        }
-        if let Some(is_carousel) = self.is_carousel {
-            query.insert("is_carousel".to_string(), is_carousel.to_string());
-        }
-        query
-    }
-}
-
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct TagItem {
@@ -64,21 +32,11 @@ pub struct TagItem {
     pub is_carousel: Option<bool>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, QueryParams)]
 pub struct TagIdDTO {
     pub include_template: Option<bool>,
 }
 
-impl QueryParams for TagIdDTO {
-    fn as_query_params(&self) -> HashMap<String, String> {
-        let mut query = HashMap::new();
-        if let Some(include_template) = self.include_template {
-            query.insert("include_template".to_string(), include_template.to_string());
-        }
-        query
-    }
-}
-
 pub type TagSlugDTO = TagIdDTO;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -104,25 +62,12 @@ impl TagStatus {
     }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, QueryParams)]
 pub struct TagRelationshipDTO {
     pub omit_empty: Option<bool>,
     pub status: Option<TagStatus>,
 }
 
-impl QueryParams for TagRelationshipDTO {
-    fn as_query_params(&self) -> HashMap<String, String> {
-        let mut query = HashMap::new();
-        if let Some(omit_empty) = self.omit_empty {
-            query.insert("omit_empty".to_string(), omit_empty.to_string());
-        }
-        if let Some(status) = &self.status {
-            query.insert("status".to_string(), status.to_string());
-        }
-        query
-    }
-}
-
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct TagRelationshipItem {
@@ -0,0 +1,113 @@
+pub mod models;
+
+use async_trait::async_trait;
+use models::{LeaderboardDTO, LeaderboardEntry, PublicProfile};
+
+use crate::shared::{ApiError, QueryParams, client::AsyncHttpClient};
+
+/// Discover traders: the public leaderboard and user profiles
+#[async_trait]
+pub trait Traders {
+    fn get_data_client(&self) -> &AsyncHttpClient;
+
+    fn get_gamma_client(&self) -> &AsyncHttpClient;
+
+    /// Get the trader leaderboard
+    /// # Arguments
+    /// * `data` - The ranking, the window and the query parameters. See [`LeaderboardDTO`] for available options.
+    /// # Returns
+    /// * `Result<Vec<[`LeaderboardEntry`]>, [`ApiError`]>` - The traders, best first, or an [`ApiError`] if the request fails
+    async fn get_leaderboard(
+        &self,
+        data: LeaderboardDTO,
+    ) -> Result<Vec<LeaderboardEntry>, ApiError> {
+        let client = self.get_data_client();
+        let query = data.as_query_params();
+        let response = client
+            .get(Some("/v1/leaderboard"), Some(query), None)
+            .await?;
+        let leaderboard: Vec<LeaderboardEntry> = response.json().await?;
+        Ok(leaderboard)
+    }
+
+    /// Get the public profile of a user
+    /// # Arguments
+    /// * `address` - The wallet address of the user
+    /// # Returns
+    /// * `Result<[`PublicProfile`], [`ApiError`]>` - The [`PublicProfile`] object, or an [`ApiError`] if the request fails
+    async fn get_public_profile(&self, address: String) -> Result<PublicProfile, ApiError> {
+        let client = self.get_gamma_client();
+        let query = vec![(String::from("address"), address)];
+        let response = client
+            .get(Some("/public-profile"), Some(query), None)
+            .await?;
+        let profile: PublicProfile = response.json().await?;
+        Ok(profile)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::public::PubClient;
+    use models::{LeaderboardRanking, LeaderboardWindow};
+
+    #[tokio::test]
+    async fn test_get_leaderboard() {
+        let client = PubClient::new();
+
+        let leaderboard = client
+            .get_leaderboard(LeaderboardDTO {
+                limit: Some(10),
+                ..LeaderboardDTO::new(LeaderboardRanking::Profit, LeaderboardWindow::Week)
+            })
+            .await;
+
+        if let Err(e) = &leaderboard {
+            eprintln!("Get leaderboard error: {:?}", e);
+        }
+        assert!(leaderboard.is_ok());
+    }
+
+    #[test]
+    fn test_leaderboard_dto_query_params() {
+        let dto = LeaderboardDTO {
+            limit: Some(25),
+            ..LeaderboardDTO::new(LeaderboardRanking::Volume, LeaderboardWindow::All)
+        };
+
+        assert_eq!(
+            dto.as_query_params(),
+            vec![
+                (String::from("orderBy"), String::from("VOL")),
+                (String::from("timePeriod"), String::from("ALL")),
+                (String::from("limit"), String::from("25")),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_deserialize_leaderboard_and_profile() {
+        let body = r#"[
+            {"rank": "1", "proxyWallet": "0xaaa", "userName": "whale", "vol": 1000000.5, "pnl": 25000, "verifiedBadge": true},
+            {"rank": 2, "proxyWallet": "0xbbb", "vol": 900000, "pnl": -100.25}
+        ]"#;
+        let leaderboard: Vec<LeaderboardEntry> = serde_json::from_str(body).unwrap();
+
+        assert_eq!(leaderboard[0].rank, 1);
+        assert_eq!(leaderboard[0].user_name.as_deref(), Some("whale"));
+        assert_eq!(leaderboard[1].rank, 2);
+        assert_eq!(leaderboard[1].pnl, -100.25);
+
+        let body = r#"{
+            "proxyWallet": "0xaaa",
+            "pseudonym": "Giant-Whale",
+            "bio": "Macro",
+            "createdAt": "2024-03-01T10:00:00.000Z"
+        }"#;
+        let profile: PublicProfile = serde_json::from_str(body).unwrap();
+
+        assert_eq!(profile.pseudonym.as_deref(), Some("Giant-Whale"));
+        assert!(profile.created_at.is_some());
+    }
+}
@@ -0,0 +1,111 @@
+use std::fmt;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_with::{DisplayFromStr, PickFirst, serde_as};
+
+use crate::shared::QueryParams;
+
+/// The time window a leaderboard is computed over
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum LeaderboardWindow {
+    Day,
+    Week,
+    Month,
+    All,
+}
+
+impl fmt::Display for LeaderboardWindow {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let value = match self {
+            LeaderboardWindow::Day => "DAY",
+            LeaderboardWindow::Week => "WEEK",
+            LeaderboardWindow::Month => "MONTH",
+            LeaderboardWindow::All => "ALL",
+        };
+        write!(f, "{value}")
+    }
+}
+
+/// What the traders of a leaderboard are ranked by
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum LeaderboardRanking {
+    Volume,
+    Profit,
+}
+
+impl fmt::Display for LeaderboardRanking {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let value = match self {
+            LeaderboardRanking::Volume => "VOL",
+            LeaderboardRanking::Profit => "PNL",
+        };
+        write!(f, "{value}")
+    }
+}
+
+/// Query parameters for the trader leaderboard
+/// # Fields
+/// * `order_by` - Rank by volume or by profit
+/// * `time_period` - The window the volume or profit is computed over
+/// * `category` - Only count markets in the category, e.g. `POLITICS`. All markets by default
+/// * `limit` / `offset` - Pagination
+/// * `user` - Only return the entry of this wallet
+#[derive(Debug, Clone, Serialize, Deserialize, QueryParams)]
+pub struct LeaderboardDTO {
+    #[query(rename = "orderBy")]
+    pub order_by: LeaderboardRanking,
+    #[query(rename = "timePeriod")]
+    pub time_period: LeaderboardWindow,
+    pub category: Option<String>,
+    pub limit: Option<u32>,
+    pub offset: Option<u32>,
+    pub user: Option<String>,
+}
+
+impl LeaderboardDTO {
+    /// The leaderboard ranked by `order_by` over `time_period`
+    pub fn new(order_by: LeaderboardRanking, time_period: LeaderboardWindow) -> Self {
+        Self {
+            order_by,
+            time_period,
+            category: None,
+            limit: None,
+            offset: None,
+            user: None,
+        }
+    }
+}
+
+/// A trader of the leaderboard
+#[serde_as]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LeaderboardEntry {
+    #[serde_as(as = "PickFirst<(_, DisplayFromStr)>")]
+    pub rank: u32,
+    pub proxy_wallet: String,
+    pub user_name: Option<String>,
+    /// Traded volume over the window, in USDC
+    pub vol: f64,
+    /// Profit over the window, in USDC
+    pub pnl: f64,
+    pub profile_image: Option<String>,
+    pub x_username: Option<String>,
+    pub verified_badge: Option<bool>,
+}
+
+/// The public profile of a user
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PublicProfile {
+    pub proxy_wallet: Option<String>,
+    pub name: Option<String>,
+    pub pseudonym: Option<String>,
+    pub bio: Option<String>,
+    pub profile_image: Option<String>,
+    pub display_username_public: Option<bool>,
+    pub x_username: Option<String>,
+    pub verified_badge: Option<bool>,
+    pub created_at: Option<DateTime<Utc>>,
+}
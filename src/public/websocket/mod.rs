@@ -0,0 +1,584 @@
+//! A supervised connection to the CLOB market WebSocket channel.
+//!
+//! [`MarketSocket`] owns a background task that keeps the connection alive:
+//!
+//! - a `PING` is sent every [`WsConfig::ping_interval`] and the connection is
+//!   dropped when nothing has been received for [`WsConfig::stale_timeout`]
+//! - lost connections are re-established with exponential [`Backoff`]
+//! - the active subscription set is replayed on every reconnect, followed by
+//!   [`WsEvent::Reconnected`] and a [`WsEvent::Gap`] for the affected assets
+//! - assets can be added or removed at any time without reconnecting
+
+pub mod models;
+
+use chrono::{DateTime, Utc};
+use futures::{SinkExt, StreamExt};
+use std::collections::BTreeSet;
+use tokio::sync::mpsc::{self, UnboundedReceiver, UnboundedSender};
+use tokio::task::JoinHandle;
+use tokio::time::{Instant, MissedTickBehavior};
+use tokio_tungstenite::{MaybeTlsStream, WebSocketStream, connect_async, tungstenite::Message};
+
+use models::SubscriptionRequest;
+pub use models::{
+    Backoff, BookMessage, LastTradePriceMessage, MarketEvent, PriceChange, PriceChangeMessage,
+    TickSizeChangeMessage, UnparsedEvent, WsConfig, WsEvent,
+};
+
+use crate::shared::ApiError;
+
+type Socket = WebSocketStream<MaybeTlsStream<tokio::net::TcpStream>>;
+
+#[derive(Debug)]
+enum Command {
+    Subscribe(Vec<String>),
+    Unsubscribe(Vec<String>),
+    Close,
+}
+
+/// Handle to a supervised market channel connection.
+///
+/// Events are delivered on the receiver returned by [`MarketSocket::connect`].
+/// Dropping the handle closes the connection.
+#[derive(Debug)]
+pub struct MarketSocket {
+    commands: UnboundedSender<Command>,
+    task: JoinHandle<()>,
+}
+
+impl MarketSocket {
+    /// Start a supervised connection subscribed to `asset_ids`
+    /// # Arguments
+    /// * `config` - The connection settings. See [`WsConfig`]
+    /// * `asset_ids` - The token ids to subscribe to initially
+    /// # Returns
+    /// * `(MarketSocket, UnboundedReceiver<WsEvent>)` - The handle and the stream of [`WsEvent`]s
+    pub fn connect(config: WsConfig, asset_ids: Vec<String>) -> (Self, UnboundedReceiver<WsEvent>) {
+        let (commands, command_rx) = mpsc::unbounded_channel();
+        let (events, event_rx) = mpsc::unbounded_channel();
+        let supervisor = Supervisor {
+            config,
+            subscriptions: asset_ids.into_iter().collect(),
+            commands: command_rx,
+            events,
+            last_message_at: None,
+        };
+        let task = tokio::spawn(supervisor.run());
+        (Self { commands, task }, event_rx)
+    }
+
+    /// Add assets to the subscription set of the live connection
+    pub fn subscribe(&self, asset_ids: Vec<String>) -> Result<(), ApiError> {
+        self.send(Command::Subscribe(asset_ids))
+    }
+
+    /// Remove assets from the subscription set of the live connection
+    pub fn unsubscribe(&self, asset_ids: Vec<String>) -> Result<(), ApiError> {
+        self.send(Command::Unsubscribe(asset_ids))
+    }
+
+    /// Close the connection and wait for the supervisor to stop
+    pub async fn close(mut self) {
+        let _ = self.commands.send(Command::Close);
+        let _ = (&mut self.task).await;
+    }
+
+    /// Whether the supervisor has stopped, either after [`MarketSocket::close`]
+    /// or after exhausting [`WsConfig::max_reconnect_attempts`]
+    pub fn is_finished(&self) -> bool {
+        self.task.is_finished()
+    }
+
+    fn send(&self, command: Command) -> Result<(), ApiError> {
+        self.commands
+            .send(command)
+            .map_err(|_| ApiError::Unexpected("WebSocket supervisor has stopped".to_string()))
+    }
+}
+
+impl Drop for MarketSocket {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+/// Why a session ended
+enum SessionEnd {
+    /// The connection was lost and should be re-established
+    Lost(String),
+    /// The handle asked to close or the event receiver was dropped
+    Shutdown,
+}
+
+struct Supervisor {
+    config: WsConfig,
+    subscriptions: BTreeSet<String>,
+    commands: UnboundedReceiver<Command>,
+    events: UnboundedSender<WsEvent>,
+    last_message_at: Option<DateTime<Utc>>,
+}
+
+impl Supervisor {
+    async fn run(mut self) {
+        let mut connected_before = false;
+        let mut attempts: u32 = 0;
+
+        loop {
+            match connect_async(self.config.url.as_str()).await {
+                Ok((socket, _)) => {
+                    let reconnect_attempts = attempts;
+                    attempts = 0;
+                    match self
+                        .session(socket, connected_before, reconnect_attempts)
+                        .await
+                    {
+                        SessionEnd::Shutdown => return,
+                        SessionEnd::Lost(reason) => {
+                            if !self.emit(WsEvent::Disconnected { reason }) {
+                                return;
+                            }
+                        }
+                    }
+                    connected_before = true;
+                }
+                Err(error) => {
+                    if let Some(max) = self.config.max_reconnect_attempts
+                        && attempts >= max
+                    {
+                        let _ = self.emit(WsEvent::Disconnected {
+                            reason: format!("Giving up after {attempts} attempts: {error}"),
+                        });
+                        return;
+                    }
+                }
+            }
+
+            let delay = self.config.backoff.delay(attempts);
+            attempts = attempts.saturating_add(1);
+            if !self.wait(delay).await {
+                return;
+            }
+        }
+    }
+
+    /// Drive a single connection until it is lost or shut down
+    async fn session(&mut self, socket: Socket, reconnected: bool, attempts: u32) -> SessionEnd {
+        let (mut sink, mut stream) = socket.split();
+        let mut initialized = false;
+
+        if !self.subscriptions.is_empty() {
+            let request = SubscriptionRequest::initial(self.subscriptions.clone());
+            if let Err(error) = send_json(&mut sink, &request).await {
+                return SessionEnd::Lost(error);
+            }
+            initialized = true;
+        }
+
+        let connected = if reconnected {
+            self.emit(WsEvent::Reconnected { attempts })
+                && self.emit(WsEvent::Gap {
+                    asset_ids: self.subscriptions.iter().cloned().collect(),
+                    from: self.last_message_at,
+                    to: Utc::now(),
+                })
+        } else {
+            self.emit(WsEvent::Connected)
+        };
+        if !connected {
+            return SessionEnd::Shutdown;
+        }
+
+        let mut ping = tokio::time::interval(self.config.ping_interval);
+        ping.set_missed_tick_behavior(MissedTickBehavior::Delay);
+        ping.tick().await;
+        let mut last_activity = Instant::now();
+
+        loop {
+            tokio::select! {
+                _ = ping.tick() => {
+                    if last_activity.elapsed() >= self.config.stale_timeout {
+                        return SessionEnd::Lost("Connection is stale".to_string());
+                    }
+                    if let Err(error) = sink.send(Message::text("PING")).await {
+                        return SessionEnd::Lost(error.to_string());
+                    }
+                }
+                message = stream.next() => {
+                    let message = match message {
+                        Some(Ok(message)) => message,
+                        Some(Err(error)) => return SessionEnd::Lost(error.to_string()),
+                        None => return SessionEnd::Lost("Connection closed".to_string()),
+                    };
+                    last_activity = Instant::now();
+                    match message {
+                        Message::Text(text) => {
+                            if text.as_str() == "PONG" {
+                                continue;
+                            }
+                            let received_at = Utc::now();
+                            let previous = self.last_message_at.replace(received_at);
+                            // Frames that fail to parse are not fatal to the connection
+                            let Ok(events) = MarketEvent::parse_frame(text.as_str()) else {
+                                continue;
+                            };
+                            for event in events {
+                                let event = match event {
+                                    Ok(event) => WsEvent::Market(event),
+                                    Err(unparsed) if unparsed.asset_ids.is_empty() => continue,
+                                    // A lost update leaves the books of its assets out of sequence
+                                    Err(unparsed) => WsEvent::Gap {
+                                        asset_ids: unparsed.asset_ids,
+                                        from: previous,
+                                        to: received_at,
+                                    },
+                                };
+                                if !self.emit(event) {
+                                    return SessionEnd::Shutdown;
+                                }
+                            }
+                        }
+                        Message::Close(frame) => {
+                            let reason = frame
+                                .map(|frame| frame.reason.to_string())
+                                .unwrap_or_else(|| "Connection closed".to_string());
+                            return SessionEnd::Lost(reason);
+                        }
+                        _ => {}
+                    }
+                }
+                command = self.commands.recv() => {
+                    let request = match command {
+                        Some(Command::Subscribe(asset_ids)) => {
+                            let added = self.add(asset_ids);
+                            if added.is_empty() {
+                                continue;
+                            }
+                            if initialized {
+                                SubscriptionRequest::subscribe(added)
+                            } else {
+                                initialized = true;
+                                SubscriptionRequest::initial(self.subscriptions.clone())
+                            }
+                        }
+                        Some(Command::Unsubscribe(asset_ids)) => {
+                            let removed = self.remove(asset_ids);
+                            if removed.is_empty() || !initialized {
+                                continue;
+                            }
+                            SubscriptionRequest::unsubscribe(removed)
+                        }
+                        Some(Command::Close) | None => {
+                            let _ = sink.send(Message::Close(None)).await;
+                            return SessionEnd::Shutdown;
+                        }
+                    };
+                    if let Err(error) = send_json(&mut sink, &request).await {
+                        return SessionEnd::Lost(error);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Sleep for `delay` while still applying subscription changes.
+    /// Returns `false` if the supervisor should stop
+    async fn wait(&mut self, delay: std::time::Duration) -> bool {
+        let sleep = tokio::time::sleep(delay);
+        tokio::pin!(sleep);
+        loop {
+            tokio::select! {
+                _ = &mut sleep => return true,
+                command = self.commands.recv() => match command {
+                    Some(Command::Subscribe(asset_ids)) => {
+                        self.add(asset_ids);
+                    }
+                    Some(Command::Unsubscribe(asset_ids)) => {
+                        self.remove(asset_ids);
+                    }
+                    Some(Command::Close) | None => return false,
+                },
+            }
+        }
+    }
+
+    fn add(&mut self, asset_ids: Vec<String>) -> BTreeSet<String> {
+        asset_ids
+            .into_iter()
+            .filter(|asset_id| self.subscriptions.insert(asset_id.clone()))
+            .collect()
+    }
+
+    fn remove(&mut self, asset_ids: Vec<String>) -> BTreeSet<String> {
+        asset_ids
+            .into_iter()
+            .filter(|asset_id| self.subscriptions.remove(asset_id))
+            .collect()
+    }
+
+    /// Returns `false` once nobody is listening for events anymore
+    fn emit(&self, event: WsEvent) -> bool {
+        self.events.send(event).is_ok()
+    }
+}
+
+async fn send_json<S>(sink: &mut S, request: &SubscriptionRequest) -> Result<(), String>
+where
+    S: SinkExt<Message> + Unpin,
+    S::Error: std::fmt::Display,
+{
+    let payload = serde_json::to_string(request).map_err(|error| error.to_string())?;
+    sink.send(Message::text(payload))
+        .await
+        .map_err(|error| error.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::Value;
+    use std::time::Duration;
+    use tokio::net::{TcpListener, TcpStream};
+    use tokio_tungstenite::accept_async;
+
+    fn test_config(url: String) -> WsConfig {
+        WsConfig {
+            url,
+            ping_interval: Duration::from_millis(50),
+            stale_timeout: Duration::from_millis(200),
+            backoff: Backoff {
+                initial: Duration::from_millis(10),
+                max: Duration::from_millis(50),
+                multiplier: 2,
+            },
+            max_reconnect_attempts: None,
+        }
+    }
+
+    async fn listen() -> (TcpListener, String) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("ws://{}", listener.local_addr().unwrap());
+        (listener, url)
+    }
+
+    async fn accept(listener: &TcpListener) -> WebSocketStream<TcpStream> {
+        let (stream, _) = listener.accept().await.unwrap();
+        accept_async(stream).await.unwrap()
+    }
+
+    /// Read the next JSON frame, skipping pings
+    async fn next_json(server: &mut WebSocketStream<TcpStream>) -> Value {
+        loop {
+            match server.next().await.unwrap().unwrap() {
+                Message::Text(text) if text.as_str() == "PING" => continue,
+                Message::Text(text) => return serde_json::from_str(text.as_str()).unwrap(),
+                _ => continue,
+            }
+        }
+    }
+
+    async fn next_event(events: &mut UnboundedReceiver<WsEvent>) -> WsEvent {
+        tokio::time::timeout(Duration::from_secs(5), events.recv())
+            .await
+            .expect("timed out waiting for event")
+            .expect("event channel closed")
+    }
+
+    #[test]
+    fn test_backoff_delay() {
+        let backoff = Backoff {
+            initial: Duration::from_millis(100),
+            max: Duration::from_secs(1),
+            multiplier: 2,
+        };
+
+        assert_eq!(backoff.delay(0), Duration::from_millis(100));
+        assert_eq!(backoff.delay(1), Duration::from_millis(200));
+        assert_eq!(backoff.delay(3), Duration::from_millis(800));
+        assert_eq!(backoff.delay(4), Duration::from_secs(1));
+        assert_eq!(backoff.delay(u32::MAX), Duration::from_secs(1));
+    }
+
+    #[test]
+    fn test_parse_frame() {
+        let book = r#"[{"event_type":"book","asset_id":"1","market":"0xabc","bids":[{"price":"0.48","size":"30"}],"asks":[{"price":"0.52","size":"25"}],"timestamp":"1700000000000","hash":"0x1"}]"#;
+        let events = MarketEvent::parse_frame(book).unwrap();
+        assert_eq!(events.len(), 1);
+        assert!(matches!(&events[0], Ok(MarketEvent::Book(book)) if book.bids[0].price == "0.48"));
+
+        let change = r#"{"event_type":"price_change","market":"0xabc","price_changes":[{"asset_id":"1","price":"0.5","size":"200","side":"BUY","hash":"0x2","best_bid":"0.5","best_ask":"0.52"}],"timestamp":"1700000000001"}"#;
+        let events = MarketEvent::parse_frame(change).unwrap();
+        assert!(
+            matches!(&events[0], Ok(MarketEvent::PriceChange(change)) if change.price_changes[0].size == "200")
+        );
+
+        let unknown = r#"{"event_type":"something_new","asset_id":"1"}"#;
+        let events = MarketEvent::parse_frame(unknown).unwrap();
+        assert!(matches!(events[0], Ok(MarketEvent::Unknown)));
+
+        assert!(MarketEvent::parse_frame("not json").is_err());
+    }
+
+    #[test]
+    fn test_parse_frame_keeps_the_events_around_a_bad_one() {
+        let frame = r#"[
+            {"event_type":"book","asset_id":"1","market":"0xabc","bids":[],"asks":[],"timestamp":"1","hash":"0x1"},
+            {"event_type":"price_change","market":"0xabc","price_changes":[{"asset_id":"2","price":"0.5","side":"BUY"},{"asset_id":"3","price":"0.4","size":"1","side":"SELL"}],"timestamp":"2"},
+            {"event_type":"tick_size_change","asset_id":"1","market":"0xabc","old_tick_size":"0.01","new_tick_size":"0.001","timestamp":"3"}
+        ]"#;
+
+        let events = MarketEvent::parse_frame(frame).unwrap();
+
+        assert_eq!(events.len(), 3);
+        assert!(matches!(events[0], Ok(MarketEvent::Book(_))));
+        match &events[1] {
+            Err(unparsed) => {
+                assert_eq!(unparsed.asset_ids, vec!["2".to_string(), "3".to_string()]);
+                assert!(matches!(unparsed.error, ApiError::Decode(_)));
+            }
+            event => panic!("expected an unparsed event, got {event:?}"),
+        }
+        assert!(matches!(events[2], Ok(MarketEvent::TickSizeChange(_))));
+    }
+
+    #[tokio::test]
+    async fn test_reconnect_replays_subscriptions() {
+        let (listener, url) = listen().await;
+        let (socket, mut events) = MarketSocket::connect(test_config(url), vec!["1".to_string()]);
+
+        let mut server = accept(&listener).await;
+        let request = next_json(&mut server).await;
+        assert_eq!(request["type"], "market");
+        assert_eq!(request["assets_ids"], serde_json::json!(["1"]));
+        assert!(matches!(next_event(&mut events).await, WsEvent::Connected));
+
+        server
+            .send(Message::text(
+                r#"{"event_type":"tick_size_change","asset_id":"1","market":"0xabc","old_tick_size":"0.01","new_tick_size":"0.001","timestamp":"1"}"#,
+            ))
+            .await
+            .unwrap();
+        assert!(matches!(
+            next_event(&mut events).await,
+            WsEvent::Market(MarketEvent::TickSizeChange(_))
+        ));
+
+        socket.subscribe(vec!["2".to_string()]).unwrap();
+        let request = next_json(&mut server).await;
+        assert_eq!(request["operation"], "subscribe");
+        assert_eq!(request["assets_ids"], serde_json::json!(["2"]));
+
+        drop(server);
+        assert!(matches!(
+            next_event(&mut events).await,
+            WsEvent::Disconnected { .. }
+        ));
+
+        let mut server = accept(&listener).await;
+        let request = next_json(&mut server).await;
+        assert_eq!(request["type"], "market");
+        assert_eq!(request["assets_ids"], serde_json::json!(["1", "2"]));
+        assert!(matches!(
+            next_event(&mut events).await,
+            WsEvent::Reconnected { .. }
+        ));
+        match next_event(&mut events).await {
+            WsEvent::Gap {
+                asset_ids, from, ..
+            } => {
+                assert_eq!(asset_ids, vec!["1".to_string(), "2".to_string()]);
+                assert!(from.is_some());
+            }
+            event => panic!("expected a gap, got {event:?}"),
+        }
+
+        socket.close().await;
+    }
+
+    #[tokio::test]
+    async fn test_bad_event_is_reported_as_gap() {
+        let (listener, url) = listen().await;
+        let (socket, mut events) = MarketSocket::connect(test_config(url), vec!["1".to_string()]);
+
+        let mut server = accept(&listener).await;
+        next_json(&mut server).await;
+        assert!(matches!(next_event(&mut events).await, WsEvent::Connected));
+
+        server
+            .send(Message::text(
+                r#"[{"event_type":"book","asset_id":"1","market":"0xabc","timestamp":"1"},{"event_type":"last_trade_price","asset_id":"1","market":"0xabc","price":"0.5","size":"10","side":"BUY","fee_rate_bps":"0","timestamp":"2"}]"#,
+            ))
+            .await
+            .unwrap();
+        match next_event(&mut events).await {
+            WsEvent::Gap { asset_ids, .. } => assert_eq!(asset_ids, vec!["1".to_string()]),
+            event => panic!("expected a gap, got {event:?}"),
+        }
+        assert!(matches!(
+            next_event(&mut events).await,
+            WsEvent::Market(MarketEvent::LastTradePrice(_))
+        ));
+
+        socket.close().await;
+    }
+
+    #[tokio::test]
+    async fn test_unsubscribe_without_reconnect() {
+        let (listener, url) = listen().await;
+        let (socket, mut events) =
+            MarketSocket::connect(test_config(url), vec!["1".to_string(), "2".to_string()]);
+
+        let mut server = accept(&listener).await;
+        next_json(&mut server).await;
+        assert!(matches!(next_event(&mut events).await, WsEvent::Connected));
+
+        socket
+            .unsubscribe(vec!["1".to_string(), "3".to_string()])
+            .unwrap();
+        let request = next_json(&mut server).await;
+        assert_eq!(request["operation"], "unsubscribe");
+        assert_eq!(request["assets_ids"], serde_json::json!(["1"]));
+
+        socket.close().await;
+    }
+
+    #[tokio::test]
+    async fn test_stale_connection_is_dropped() {
+        let (listener, url) = listen().await;
+        let (socket, mut events) = MarketSocket::connect(test_config(url), vec!["1".to_string()]);
+
+        // Never answer PINGs, so the client should give up on this connection
+        let _silent = accept(&listener).await;
+        assert!(matches!(next_event(&mut events).await, WsEvent::Connected));
+        match next_event(&mut events).await {
+            WsEvent::Disconnected { reason } => assert_eq!(reason, "Connection is stale"),
+            event => panic!("expected a disconnect, got {event:?}"),
+        }
+
+        let _server = accept(&listener).await;
+        assert!(matches!(
+            next_event(&mut events).await,
+            WsEvent::Reconnected { .. }
+        ));
+
+        socket.close().await;
+    }
+
+    #[tokio::test]
+    async fn test_gives_up_after_max_attempts() {
+        let (listener, url) = listen().await;
+        drop(listener);
+
+        let config = WsConfig {
+            max_reconnect_attempts: Some(2),
+            ..test_config(url)
+        };
+        let (socket, mut events) = MarketSocket::connect(config, vec![]);
+
+        assert!(matches!(
+            next_event(&mut events).await,
+            WsEvent::Disconnected { .. }
+        ));
+        assert!(events.recv().await.is_none());
+        assert!(socket.is_finished());
+    }
+}
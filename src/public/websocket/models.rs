@@ -0,0 +1,247 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::BTreeSet;
+use std::time::Duration;
+
+use crate::public::orderbook::models::{OrderSummary, OrderbookSummary};
+use crate::shared::constants::WS_MARKET_ENDPOINT;
+use crate::shared::{ApiError, Side};
+
+/// Exponential backoff used between reconnect attempts
+#[derive(Debug, Clone)]
+pub struct Backoff {
+    pub initial: Duration,
+    pub max: Duration,
+    pub multiplier: u32,
+}
+
+impl Default for Backoff {
+    fn default() -> Self {
+        Self {
+            initial: Duration::from_millis(500),
+            max: Duration::from_secs(30),
+            multiplier: 2,
+        }
+    }
+}
+
+impl Backoff {
+    /// Delay to wait before the given (zero based) reconnect attempt
+    pub fn delay(&self, attempt: u32) -> Duration {
+        let factor = self.multiplier.max(1).saturating_pow(attempt);
+        self.initial.saturating_mul(factor).min(self.max)
+    }
+}
+
+/// Configuration of a [`MarketSocket`](super::MarketSocket) connection
+/// # Fields
+/// * `url` - The market channel url
+/// * `ping_interval` - How often a `PING` is sent to the server
+/// * `stale_timeout` - How long the connection may stay silent before it is considered dead
+/// * `backoff` - The backoff applied between reconnect attempts
+/// * `max_reconnect_attempts` - Give up after this many failed attempts in a row (`None` retries forever)
+#[derive(Debug, Clone)]
+pub struct WsConfig {
+    pub url: String,
+    pub ping_interval: Duration,
+    pub stale_timeout: Duration,
+    pub backoff: Backoff,
+    pub max_reconnect_attempts: Option<u32>,
+}
+
+impl Default for WsConfig {
+    fn default() -> Self {
+        Self {
+            url: WS_MARKET_ENDPOINT.to_string(),
+            ping_interval: Duration::from_secs(10),
+            stale_timeout: Duration::from_secs(30),
+            backoff: Backoff::default(),
+            max_reconnect_attempts: None,
+        }
+    }
+}
+
+/// Events emitted by a [`MarketSocket`](super::MarketSocket)
+#[derive(Debug, Clone)]
+pub enum WsEvent {
+    /// The first connection has been established and subscribed
+    Connected,
+    /// A message received on the market channel
+    Market(MarketEvent),
+    /// The connection was lost
+    Disconnected { reason: String },
+    /// The connection was re-established and the active subscriptions replayed
+    Reconnected { attempts: u32 },
+    /// Updates for `asset_ids` may have been missed between `from` and `to`.
+    /// Books for these assets should be re-snapshotted through
+    /// [`OrderBook::get_orderbook_summary`](crate::public::orderbook::OrderBook::get_orderbook_summary)
+    Gap {
+        asset_ids: Vec<String>,
+        from: Option<DateTime<Utc>>,
+        to: DateTime<Utc>,
+    },
+}
+
+/// A message pushed on the market channel
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "event_type", rename_all = "snake_case")]
+pub enum MarketEvent {
+    Book(BookMessage),
+    PriceChange(PriceChangeMessage),
+    TickSizeChange(TickSizeChangeMessage),
+    LastTradePrice(LastTradePriceMessage),
+    #[serde(other)]
+    Unknown,
+}
+
+/// An event of a frame that could not be parsed
+#[derive(Debug)]
+pub struct UnparsedEvent {
+    /// The assets the event was about, as far as they could be read
+    pub asset_ids: Vec<String>,
+    pub error: ApiError,
+}
+
+impl MarketEvent {
+    /// Parse a text frame, which may hold a single event or an array of events.
+    ///
+    /// Every event of an array is parsed on its own, so one that fails does
+    /// not take the other events of the frame with it.
+    /// # Returns
+    /// * `Result<Vec<Result<MarketEvent, UnparsedEvent>>, ApiError>` - Every event of the frame in order, or an [`ApiError`] if the frame is not JSON
+    pub fn parse_frame(text: &str) -> Result<Vec<Result<MarketEvent, UnparsedEvent>>, ApiError> {
+        let values = match serde_json::from_str(text)? {
+            Value::Array(values) => values,
+            value => vec![value],
+        };
+        let mut events = Vec::with_capacity(values.len());
+        for value in &values {
+            events.push(match MarketEvent::deserialize(value) {
+                Ok(event) => Ok(event),
+                Err(error) => Err(UnparsedEvent {
+                    asset_ids: asset_ids_of(value),
+                    error: error.into(),
+                }),
+            });
+        }
+        Ok(events)
+    }
+}
+
+/// The `asset_id`s of an event, including those of its price changes
+fn asset_ids_of(value: &Value) -> Vec<String> {
+    let changes = value["price_changes"].as_array().into_iter().flatten();
+    let asset_ids: BTreeSet<&str> = std::iter::once(value)
+        .chain(changes)
+        .filter_map(|event| event["asset_id"].as_str())
+        .collect();
+    asset_ids.into_iter().map(str::to_string).collect()
+}
+
+/// Full book snapshot, sent on subscribe and after trades
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BookMessage {
+    pub asset_id: String,
+    pub market: String,
+    #[serde(alias = "buys")]
+    pub bids: Vec<OrderSummary>,
+    #[serde(alias = "sells")]
+    pub asks: Vec<OrderSummary>,
+    pub timestamp: String,
+    pub hash: String,
+}
+
+/// A book fetched over HTTP, in the shape the market channel pushes it
+impl From<OrderbookSummary> for BookMessage {
+    fn from(summary: OrderbookSummary) -> Self {
+        Self {
+            asset_id: summary.asset_id,
+            market: summary.market,
+            bids: summary.bids,
+            asks: summary.asks,
+            timestamp: summary.timestamp,
+            hash: summary.hash,
+        }
+    }
+}
+
+/// One or more level updates, sent when orders are placed or cancelled
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PriceChangeMessage {
+    pub market: String,
+    pub price_changes: Vec<PriceChange>,
+    pub timestamp: String,
+}
+
+/// The new aggregate size at a single price level
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PriceChange {
+    pub asset_id: String,
+    pub price: String,
+    pub size: String,
+    pub side: Side,
+    pub hash: Option<String>,
+    pub best_bid: Option<String>,
+    pub best_ask: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TickSizeChangeMessage {
+    pub asset_id: String,
+    pub market: String,
+    pub old_tick_size: String,
+    pub new_tick_size: String,
+    pub timestamp: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LastTradePriceMessage {
+    pub asset_id: String,
+    pub market: String,
+    pub price: String,
+    pub size: String,
+    pub side: Side,
+    pub fee_rate_bps: Option<String>,
+    pub timestamp: String,
+}
+
+/// Subscription request sent to the market channel
+#[derive(Debug, Clone, Serialize)]
+#[serde(untagged)]
+pub(crate) enum SubscriptionRequest {
+    /// Sent once per connection with the full asset set
+    Initial {
+        assets_ids: BTreeSet<String>,
+        #[serde(rename = "type")]
+        channel: &'static str,
+    },
+    /// Sent to change the asset set of a live connection
+    Update {
+        assets_ids: BTreeSet<String>,
+        operation: &'static str,
+    },
+}
+
+impl SubscriptionRequest {
+    pub(crate) fn initial(assets_ids: BTreeSet<String>) -> Self {
+        Self::Initial {
+            assets_ids,
+            channel: "market",
+        }
+    }
+
+    pub(crate) fn subscribe(assets_ids: BTreeSet<String>) -> Self {
+        Self::Update {
+            assets_ids,
+            operation: "subscribe",
+        }
+    }
+
+    pub(crate) fn unsubscribe(assets_ids: BTreeSet<String>) -> Self {
+        Self::Update {
+            assets_ids,
+            operation: "unsubscribe",
+        }
+    }
+}
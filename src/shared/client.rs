@@ -1,6 +1,5 @@
 use reqwest::{Client as ReqwestClient, Error, Response, StatusCode, header::HeaderMap};
 use serde::Serialize;
-use std::collections::HashMap;
 use std::time::Duration;
 
 use crate::shared::{ApiError, HttpError};
@@ -27,7 +26,7 @@ impl AsyncHttpClient {
     pub async fn get(
         &self,
         path: Option<&str>,
-        query: Option<HashMap<String, String>>,
+        query: Option<Vec<(String, String)>>,
         headers: Option<HeaderMap>,
     ) -> Result<Response, ApiError> {
         let url = if let Some(p) = path {
@@ -39,11 +38,7 @@ impl AsyncHttpClient {
         let mut request = self.client.get(&url);
 
         if let Some(query_params) = query {
-            let query_refs: HashMap<&str, &str> = query_params
-                .iter()
-                .map(|(k, v)| (k.as_str(), v.as_str()))
-                .collect();
-            request = request.query(&query_refs);
+            request = request.query(&query_params);
         }
 
         if let Some(h) = headers {
@@ -71,7 +66,7 @@ impl AsyncHttpClient {
         &self,
         path: Option<&str>,
         body: Option<T>,
-        query: Option<HashMap<String, String>>,
+        query: Option<Vec<(String, String)>>,
         headers: Option<HeaderMap>,
     ) -> Result<Response, ApiError> {
         let url = format!("{}{}", self.base_url, path.unwrap_or(""));
@@ -82,11 +77,7 @@ impl AsyncHttpClient {
         }
 
         if let Some(query_params) = query {
-            let query_refs: HashMap<&str, &str> = query_params
-                .iter()
-                .map(|(k, v)| (k.as_str(), v.as_str()))
-                .collect();
-            request = request.query(&query_refs);
+            request = request.query(&query_params);
         }
 
         if let Some(h) = headers {
@@ -102,7 +93,7 @@ impl AsyncHttpClient {
         &self,
         path: Option<&str>,
         body: Option<T>,
-        query: Option<HashMap<String, String>>,
+        query: Option<Vec<(String, String)>>,
         headers: Option<HeaderMap>,
     ) -> Result<Response, ApiError> {
         let url = format!("{}{}", self.base_url, path.unwrap_or(""));
@@ -113,11 +104,7 @@ impl AsyncHttpClient {
         }
 
         if let Some(query_params) = query {
-            let query_refs: HashMap<&str, &str> = query_params
-                .iter()
-                .map(|(k, v)| (k.as_str(), v.as_str()))
-                .collect();
-            request = request.query(&query_refs);
+            request = request.query(&query_params);
         }
 
         if let Some(h) = headers {
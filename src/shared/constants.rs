@@ -6,6 +6,8 @@ use std::io::Chain;
 
 pub const CLOB_ENDPOINT: &str = "https://clob.polymarket.com";
 pub const GAMMA_ENDPOINT: &str = "https://gamma-api.polymarket.com";
+pub const DATA_ENDPOINT: &str = "https://data-api.polymarket.com";
+pub const WS_MARKET_ENDPOINT: &str = "wss://ws-subscriptions-clob.polymarket.com/ws/market";
 
 pub const ORDER_NAME: &str = "Polymarket CTF Exchange";
 pub const ORDER_VERSION: &str = "1";
@@ -1,5 +1,9 @@
 pub mod client;
 pub mod error;
 pub mod models;
+pub mod pagination;
+pub mod query;
 pub use models::{ApiError, HttpError, QueryParams, Side, TokenId};
+pub use pagination::PageConfig;
+pub use poly_rc_derive::QueryParams;
 pub mod constants;
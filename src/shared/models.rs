@@ -1,29 +1,41 @@
+use alloy::contract::Error as ContractError;
+use alloy::providers::PendingTransactionError;
 use alloy::signers::Error as AlloyError;
+use alloy::transports::TransportError;
 use base64::DecodeError;
+use poly_rc_derive::QueryParams;
 use reqwest::header::InvalidHeaderValue;
 use reqwest::{Error as ReqwestError, StatusCode, Url};
 use serde::{Deserialize, Serialize};
 use sha2::digest::InvalidLength;
-use std::{collections::HashMap, fmt};
+use std::fmt;
 
 /// Converts a value into HTTP URL query parameters.
 ///
 /// Types implementing this trait define how their data
 /// is represented as key–value pairs suitable for inclusion
-/// in the query string of an HTTP request.
+/// in the query string of an HTTP request. The pairs are kept in order and
+/// a key may appear several times for multi-valued filters.
+/// See [`QueryBuilder`](crate::shared::query::QueryBuilder).
 pub trait QueryParams {
-    fn as_query_params(&self) -> HashMap<String, String>;
+    fn as_query_params(&self) -> Vec<(String, String)>;
 }
 
 /// Represents a token identifier used across multiple API endpoints
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize, QueryParams)]
 pub struct TokenId {
     pub token_id: String,
 }
 
-impl QueryParams for TokenId {
-    fn as_query_params(&self) -> HashMap<String, String> {
-        HashMap::from([("token_id".to_string(), self.token_id.clone())])
+impl From<String> for TokenId {
+    fn from(token_id: String) -> Self {
+        TokenId { token_id }
+    }
+}
+
+impl From<TokenId> for String {
+    fn from(token_id: TokenId) -> Self {
+        token_id.token_id
     }
 }
 
@@ -32,12 +44,15 @@ impl QueryParams for TokenId {
 /// * `Http(HttpError)` - An HTTP error
 /// * `Decode(String)` - A JSON decoding error
 /// * `Unexpected(String)` - An unexpected error
+/// * `Crypto(String)` - A signing error
+/// * `Contract(String)` - An on-chain call failed or a transaction reverted
 #[derive(Debug, Serialize, Deserialize)]
 pub enum ApiError {
     Http(HttpError),
     Decode(String),
     Unexpected(String),
     Crypto(String),
+    Contract(String),
 }
 
 impl From<HttpError> for ApiError {
@@ -58,6 +73,24 @@ impl From<AlloyError> for ApiError {
     }
 }
 
+impl From<ContractError> for ApiError {
+    fn from(error: ContractError) -> Self {
+        ApiError::Contract(error.to_string())
+    }
+}
+
+impl From<PendingTransactionError> for ApiError {
+    fn from(error: PendingTransactionError) -> Self {
+        ApiError::Contract(error.to_string())
+    }
+}
+
+impl From<TransportError> for ApiError {
+    fn from(error: TransportError) -> Self {
+        ApiError::Contract(error.to_string())
+    }
+}
+
 impl From<InvalidLength> for ApiError {
     fn from(error: InvalidLength) -> Self {
         ApiError::Crypto(error.to_string())
@@ -95,6 +128,12 @@ impl From<serde_json::Error> for ApiError {
     }
 }
 
+impl From<std::io::Error> for ApiError {
+    fn from(error: std::io::Error) -> Self {
+        ApiError::Unexpected(format!("IO error: {error}"))
+    }
+}
+
 impl From<InvalidHeaderValue> for ApiError {
     fn from(error: InvalidHeaderValue) -> Self {
         ApiError::Decode(format!("Invalid header value: {error}"))
@@ -126,7 +165,7 @@ impl From<ReqwestError> for HttpError {
     }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum Side {
     BUY,
     SELL,
@@ -0,0 +1,270 @@
+//! Streams over paginated list endpoints.
+//!
+//! [`paginate`] drives `limit`/`offset` endpoints such as the Gamma lists and
+//! [`paginate_cursor`] drives `next_cursor` endpoints such as the CLOB lists.
+//! Both yield the items one by one as a [`Stream`]; an error ends the stream.
+
+use std::future::{Future, ready};
+use std::sync::{
+    Arc,
+    atomic::{AtomicBool, Ordering},
+};
+
+use futures::stream::{self, BoxStream, Stream, StreamExt, TryStreamExt};
+
+use crate::shared::ApiError;
+
+/// The `next_cursor` value the CLOB returns on the last page
+pub const END_CURSOR: &str = "LTE=";
+
+/// How to walk a paginated endpoint
+/// # Fields
+/// * `page_size` - The number of items requested per page
+/// * `concurrency` - The number of pages requested at the same time. Only used by offset pagination
+/// * `max_items` - Stop after this many items
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PageConfig {
+    pub page_size: u32,
+    pub concurrency: usize,
+    pub max_items: Option<usize>,
+}
+
+impl Default for PageConfig {
+    fn default() -> Self {
+        Self {
+            page_size: 100,
+            concurrency: 1,
+            max_items: None,
+        }
+    }
+}
+
+impl PageConfig {
+    fn limit(&self) -> u32 {
+        let page_size = self.page_size.max(1);
+        match self.max_items {
+            Some(max_items) => page_size.min(u32::try_from(max_items).unwrap_or(u32::MAX).max(1)),
+            None => page_size,
+        }
+    }
+
+    fn max_items(&self) -> usize {
+        self.max_items.unwrap_or(usize::MAX)
+    }
+}
+
+/// Stream the items of a `limit`/`offset` endpoint.
+///
+/// Pages are requested in order, `concurrency` at a time, starting at `start`.
+/// The stream ends after the first page shorter than the page size, after
+/// `max_items` items or after the first error. With a concurrency above one,
+/// up to `concurrency - 1` requests past the last page may be sent.
+/// # Arguments
+/// * `config` - See [`PageConfig`]
+/// * `start` - The offset of the first item
+/// * `fetch` - Fetches the page at `(offset, limit)`
+pub fn paginate<'a, T, F, Fut>(
+    config: PageConfig,
+    start: u32,
+    fetch: F,
+) -> BoxStream<'a, Result<T, ApiError>>
+where
+    T: Send + 'a,
+    F: Fn(u32, u32) -> Fut + Send + 'a,
+    Fut: Future<Output = Result<Vec<T>, ApiError>> + Send + 'a,
+{
+    let limit = config.limit();
+    // Checked before every request so that no page is requested past the end
+    // once the last page has been seen
+    let done = Arc::new(AtomicBool::new(false));
+    let last_seen = done.clone();
+    stream::iter(0u32..)
+        .take_while(move |_| ready(!done.load(Ordering::Relaxed)))
+        .map(move |page| fetch(start.saturating_add(page.saturating_mul(limit)), limit))
+        .buffered(config.concurrency.max(1))
+        .scan(false, move |done, page| {
+            if *done {
+                return ready(None);
+            }
+            match &page {
+                Ok(items) if items.len() >= limit as usize => {}
+                _ => {
+                    *done = true;
+                    last_seen.store(true, Ordering::Relaxed);
+                }
+            }
+            ready(Some(page))
+        })
+        .flat_map(flatten)
+        .take(config.max_items())
+        .boxed()
+}
+
+/// Stream the items of a `next_cursor` endpoint.
+///
+/// The stream ends when the endpoint returns no cursor or [`END_CURSOR`],
+/// after `max_items` items or after the first error.
+/// # Arguments
+/// * `config` - See [`PageConfig`]. `page_size` and `concurrency` are not used since the server picks the page size
+/// * `start` - The cursor of the first page, `None` to start from the beginning
+/// * `fetch` - Fetches the page at the cursor and returns its items and the next cursor
+pub fn paginate_cursor<'a, T, F, Fut>(
+    config: PageConfig,
+    start: Option<String>,
+    fetch: F,
+) -> BoxStream<'a, Result<T, ApiError>>
+where
+    T: Send + 'a,
+    F: Fn(Option<String>) -> Fut + Send + 'a,
+    Fut: Future<Output = Result<(Vec<T>, Option<String>), ApiError>> + Send + 'a,
+{
+    stream::unfold(Some(start), move |cursor| {
+        let page = cursor.map(&fetch);
+        async move {
+            match page?.await {
+                Ok((items, next)) => {
+                    let next = next.filter(|next| !next.is_empty() && next != END_CURSOR);
+                    let state = next.map(Some);
+                    Some((Ok(items), state))
+                }
+                Err(error) => Some((Err(error), None)),
+            }
+        }
+    })
+    .flat_map(flatten)
+    .take(config.max_items())
+    .boxed()
+}
+
+/// Collect a paginated stream, failing on the first error
+pub async fn collect_all<T>(
+    stream: impl Stream<Item = Result<T, ApiError>>,
+) -> Result<Vec<T>, ApiError> {
+    stream.try_collect().await
+}
+
+fn flatten<T>(
+    page: Result<Vec<T>, ApiError>,
+) -> stream::Iter<std::vec::IntoIter<Result<T, ApiError>>> {
+    let items = match page {
+        Ok(items) => items.into_iter().map(Ok).collect(),
+        Err(error) => vec![Err(error)],
+    };
+    stream::iter(items)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::AtomicUsize;
+
+    /// A list endpoint over `0..total`
+    fn fetch(
+        total: u32,
+        calls: Arc<AtomicUsize>,
+    ) -> impl Fn(u32, u32) -> std::future::Ready<Result<Vec<u32>, ApiError>> {
+        move |offset, limit| {
+            calls.fetch_add(1, Ordering::SeqCst);
+            ready(Ok((offset..total.min(offset + limit)).collect()))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_paginate_walks_all_pages() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let config = PageConfig {
+            page_size: 10,
+            ..Default::default()
+        };
+
+        let items = collect_all(paginate(config, 0, fetch(25, calls.clone())))
+            .await
+            .unwrap();
+
+        assert_eq!(items, (0..25).collect::<Vec<_>>());
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn test_paginate_concurrently_keeps_order() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let config = PageConfig {
+            page_size: 7,
+            concurrency: 4,
+            max_items: None,
+        };
+
+        let items = collect_all(paginate(config, 3, fetch(50, calls)))
+            .await
+            .unwrap();
+
+        assert_eq!(items, (3..50).collect::<Vec<_>>());
+    }
+
+    #[tokio::test]
+    async fn test_paginate_stops_at_max_items() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let config = PageConfig {
+            page_size: 10,
+            concurrency: 1,
+            max_items: Some(15),
+        };
+
+        let items = collect_all(paginate(config, 0, fetch(1000, calls.clone())))
+            .await
+            .unwrap();
+
+        assert_eq!(items.len(), 15);
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+
+        // A cap below the page size shrinks the page
+        let config = PageConfig {
+            max_items: Some(3),
+            ..config
+        };
+        let items = collect_all(paginate(config, 0, |offset, limit| {
+            assert_eq!(limit, 3);
+            ready(Ok::<_, ApiError>(
+                (offset..offset + limit).collect::<Vec<_>>(),
+            ))
+        }))
+        .await
+        .unwrap();
+        assert_eq!(items, vec![0, 1, 2]);
+    }
+
+    #[tokio::test]
+    async fn test_paginate_ends_on_error() {
+        let stream = paginate(PageConfig::default(), 0, |offset, limit| {
+            ready(if offset == 0 {
+                Ok((0..limit).collect())
+            } else {
+                Err(ApiError::Unexpected(String::from("boom")))
+            })
+        });
+
+        let items: Vec<_> = stream.collect().await;
+
+        assert_eq!(items.len(), 101);
+        assert!(items[..100].iter().all(Result::is_ok));
+        assert!(items[100].is_err());
+    }
+
+    #[tokio::test]
+    async fn test_paginate_cursor() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let counter = calls.clone();
+        let stream = paginate_cursor(PageConfig::default(), None, move |cursor| {
+            counter.fetch_add(1, Ordering::SeqCst);
+            let page = match cursor.as_deref() {
+                None => (vec![1, 2], Some(String::from("MQ=="))),
+                Some("MQ==") => (vec![3], Some(String::from(END_CURSOR))),
+                Some(other) => panic!("unexpected cursor {other}"),
+            };
+            ready(Ok::<_, ApiError>(page))
+        });
+
+        assert_eq!(collect_all(stream).await.unwrap(), vec![1, 2, 3]);
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+    }
+}
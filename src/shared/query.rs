@@ -0,0 +1,152 @@
+use std::fmt::Display;
+
+use chrono::{DateTime, SecondsFormat, Utc};
+
+/// Builds the ordered query pairs returned by
+/// [`QueryParams::as_query_params`](crate::shared::QueryParams::as_query_params).
+///
+/// `None` values are skipped, so a DTO of optional filters maps to one call per field:
+/// ```
+/// use poly_rc::shared::query::QueryBuilder;
+///
+/// let ids = vec![1, 2];
+/// let query = QueryBuilder::new()
+///     .opt("limit", Some(10))
+///     .opt("closed", None::<bool>)
+///     .repeated("id", Some(&ids))
+///     .build();
+///
+/// assert_eq!(query, vec![
+///     ("limit".to_string(), "10".to_string()),
+///     ("id".to_string(), "1".to_string()),
+///     ("id".to_string(), "2".to_string()),
+/// ]);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct QueryBuilder {
+    query: Vec<(String, String)>,
+}
+
+impl QueryBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add `key=value`
+    pub fn param(mut self, key: &str, value: impl Display) -> Self {
+        self.query.push((key.to_string(), value.to_string()));
+        self
+    }
+
+    /// Add `key=value` if the value is set
+    pub fn opt<T: Display>(self, key: &str, value: Option<T>) -> Self {
+        match value {
+            Some(value) => self.param(key, value),
+            None => self,
+        }
+    }
+
+    /// Add the values joined by commas, `key=a,b,c`, if set
+    pub fn comma_list<T: Display>(self, key: &str, values: Option<&Vec<T>>) -> Self {
+        match values {
+            Some(values) => {
+                let joined: Vec<String> = values.iter().map(|value| value.to_string()).collect();
+                self.param(key, joined.join(","))
+            }
+            None => self,
+        }
+    }
+
+    /// Add the key once per value, `key=a&key=b&key=c`, if set
+    pub fn repeated<T: Display>(self, key: &str, values: Option<&Vec<T>>) -> Self {
+        values
+            .into_iter()
+            .flatten()
+            .fold(self, |query, value| query.param(key, value))
+    }
+
+    /// Add an RFC 3339 timestamp, `key=2025-01-01T00:00:00Z`, if set
+    pub fn timestamp(self, key: &str, value: Option<&DateTime<Utc>>) -> Self {
+        self.opt(
+            key,
+            value.map(|value| value.to_rfc3339_opts(SecondsFormat::Secs, true)),
+        )
+    }
+
+    pub fn build(self) -> Vec<(String, String)> {
+        self.query
+    }
+}
+
+/// Owned query pairs from literals, to compare against [`QueryBuilder::build`] in tests
+#[cfg(test)]
+pub(crate) fn pairs(pairs: &[(&str, &str)]) -> Vec<(String, String)> {
+    pairs
+        .iter()
+        .map(|(key, value)| (key.to_string(), value.to_string()))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::pairs;
+    use crate::shared::QueryParams;
+    use chrono::{DateTime, TimeZone, Utc};
+
+    #[derive(Default, QueryParams)]
+    struct Filters {
+        token_id: String,
+        limit: Option<u32>,
+        #[query(rename = "startTs")]
+        start_ts: Option<u64>,
+        #[query(skip)]
+        #[allow(dead_code)]
+        internal: Option<bool>,
+        #[query(comma)]
+        order: Option<Vec<String>>,
+        #[query(repeat)]
+        id: Option<Vec<i64>>,
+        #[query(repeat)]
+        slug: Vec<String>,
+        #[query(timestamp)]
+        end_date_min: Option<DateTime<Utc>>,
+    }
+
+    #[test]
+    fn test_derive_query_params() {
+        let filters = Filters {
+            token_id: String::from("123"),
+            limit: Some(10),
+            start_ts: Some(1700000000),
+            internal: Some(true),
+            order: Some(vec![String::from("volume"), String::from("id")]),
+            id: Some(vec![1, 2]),
+            slug: vec![String::from("a")],
+            end_date_min: Some(Utc.with_ymd_and_hms(2025, 1, 1, 0, 0, 0).unwrap()),
+        };
+
+        assert_eq!(
+            filters.as_query_params(),
+            pairs(&[
+                ("token_id", "123"),
+                ("limit", "10"),
+                ("startTs", "1700000000"),
+                ("order", "volume,id"),
+                ("id", "1"),
+                ("id", "2"),
+                ("slug", "a"),
+                ("end_date_min", "2025-01-01T00:00:00Z"),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_derive_query_params_skips_none() {
+        let filters = Filters {
+            token_id: String::from("123"),
+            ..Default::default()
+        };
+
+        assert_eq!(filters.as_query_params(), pairs(&[("token_id", "123")]));
+    }
+}